@@ -0,0 +1,591 @@
+# Deutsche Meldungen. Was hier fehlt, wird auf Englisch gezeigt.
+
+## Fehler
+
+error = Fehler: { $message }
+warning = Warnung: { $message }
+error-not-found = Keine Aufgabe mit der ID { $id }
+error-empty-description = Die Beschreibung der Aufgabe darf nicht leer sein
+error-empty-comment = Der Kommentar darf nicht leer sein
+error-already-completed = Aufgabe { $id } ist bereits erledigt
+error-not-completed = Aufgabe { $id } ist nicht erledigt
+error-duplicate-tag = Aufgabe { $id } hat das Schlagwort '{ $tag }' bereits
+error-missing-tag = Aufgabe { $id } hat das Schlagwort '{ $tag }' nicht
+error-missing-field = Aufgabe { $id } hat das Feld '{ $name }' nicht
+error-missing-attachment = { $count ->
+        [0] Aufgabe { $id } hat keine Anhänge
+       *[other] Aufgabe { $id } hat keinen Anhang { $number }
+    }
+error-blocked = Aufgabe { $id } wartet noch auf { $count ->
+        [one] die offene Aufgabe
+       *[other] die offenen Aufgaben
+    } { $ids }
+error-dependency-cycle = Aufgabe { $id } kann nicht von Aufgabe { $on } abhängen, sonst würde sie auf sich selbst warten
+error-missing-dependency = Aufgabe { $id } hängt nicht von Aufgabe { $on } ab
+error-nothing-to-undo = Nichts rückgängig zu machen
+error-nothing-to-redo = Nichts zu wiederholen
+error-timer-running = Die Zeitmessung für Aufgabe { $id } läuft bereits
+error-no-timer = Es läuft keine Zeitmessung
+error-io = Dateifehler: { $error }
+error-json = JSON konnte nicht gelesen oder geschrieben werden: { $error }
+error-sqlite = Datenbankfehler: { $error }
+error-locked = Die Liste ist in einer anderen Instanz geöffnet; bitte dort schließen und erneut versuchen (Sperrdatei { $path })
+read-only-refused = Mit --read-only lässt sich die Liste nur ansehen; ohne die Option kann sie geändert werden
+read-only-missing = { $path } existiert nicht
+error-empty-tag = Ein Tag darf nicht leer sein
+error-tag-spaces = Das Tag '{ $tag }' darf keine Leerzeichen enthalten
+error-field-name = Ungültiger Feldname '{ $name }', erwartet wird ein Buchstabe, gefolgt von Buchstaben, Ziffern, '-' oder '_'
+error-field-built-in = '{ $name }' ist ein eingebautes Feld und lässt sich so nicht setzen
+error-field-empty = Das Feld '{ $name }' braucht einen Wert
+file-missing = { $path } existiert nicht
+
+## Eingaben, die sich nicht lesen lassen
+
+in-line = { $error } in Zeile { $line }
+in-row = { $error } in Zeile { $row }
+unreadable-lines = { $count ->
+        [one] 1 Zeile, die sich nicht lesen ließ (Zeile { $lines })
+       *[other] { $count } Zeilen, die sich nicht lesen ließen (Zeilen { $lines })
+    }
+batch-unknown-id = Keine offene Aufgabe hat die ID { $id }
+batch-repeated-id = Aufgabe { $id } steht in mehr als einer Zeile
+invalid-autosave = Ungültiges Autosave '{ $value }', erwartet wird immediate, exit oder eine Zeit wie 30s oder 5m
+invalid-length = Ungültige Länge '{ $value }', erwartet wird eine Zeit wie 25m oder 90s
+invalid-field-option = Ungültiges Feld '{ $value }', erwartet wird name=wert wie customer=ACME
+invalid-page = Ungültige Seite '{ $value }', erwartet wird eine Zahl ab 1
+invalid-days = { $what ->
+        [age] Ungültiges Alter
+       *[other] Ungültige Dauer
+    } '{ $value }', erwartet wird eine Anzahl Tage oder Wochen wie 3d oder 2w
+invalid-priority = Ungültige Priorität '{ $value }', erwartet wird high, medium oder low
+invalid-status = Ungültiger Status '{ $value }', erwartet wird todo, in-progress, blocked oder done
+invalid-sort-order = Ungültige Sortierung '{ $value }', erwartet wird due, priority, created, alpha, id, urgency oder manual
+invalid-recurrence = Ungültige Wiederholung '{ $value }', erwartet wird daily, weekly, monthly oder none
+recurrence-required = Eine Wiederholung muss angegeben werden
+invalid-estimate = Ungültige Schätzung '{ $value }', erwartet werden Stunden, Minuten oder Punkte wie 2h, 90m oder 3pt
+estimate-required = Eine Schätzung muss angegeben werden
+invalid-year = Ungültiges Jahr: { $year }, erwartet wird 1 bis 9999
+invalid-month = Ungültiger Monat: { $month }
+invalid-day = Ungültiger Tag: { $day }
+invalid-date = Ungültiges Datum '{ $value }', erwartet wird YYYY-MM-DD
+invalid-natural-date = Ungültiges Datum '{ $value }', erwartet wird YYYY-MM-DD, "tomorrow", "next friday", "in 3 days" oder "jan 15"
+date-out-of-range = Das Datum '{ $value }' liegt außerhalb der Jahre 1 bis 9999
+ambiguous-date = Mehrdeutiges Datum '{ $value }': bitte als YYYY-MM-DD oder wie "jan 15" schreiben
+invalid-date-format = Ungültiges Datumsformat '{ $value }', erwartet werden Felder wie %Y, %m und %d
+invalid-timestamp = Ungültiger Zeitpunkt '{ $value }', erwartet wird YYYY-MM-DDTHH:MM:SSZ
+invalid-color-pair = Ungültige Farbe '{ $value }', erwartet wird { $names } mit einer Farbe, wie overdue=red
+invalid-color = Ungültige Farbe '{ $value }', erwartet wird ein Name wie red oder bright blue, oder #rrggbb
+invalid-color-name = Ungültiger Farbname '{ $value }', erwartet wird { $names }
+invalid-urgency-weight = Ungültige Gewichtung '{ $value }', erwartet wird priority, due, age, tags oder tag.<name> mit einer Zahl, wie due=12
+invalid-email = Ungültige E-Mail-Adresse '{ $value }'
+empty-attachment = Der Anhang darf nicht leer sein
+invalid-assignee = Der Name der zuständigen Person darf weder leer sein noch Leerzeichen enthalten
+move-before-itself = Aufgabe { $id } kann nicht vor sich selbst verschoben werden
+no-language = Keine Meldungen in '{ $language }'; die Sprachen sind { $languages }
+config-error = { $path }: { $error }
+log-file-failed = Die Protokolldatei { $path } lässt sich nicht öffnen: { $error }
+journal-bad-entry = Fehlerhafter Eintrag im Journal in Zeile { $line }: { $error }
+doctor-no-tasks = Die Datei enthält keine Liste von Aufgaben
+unknown-list-kind = Es ist nicht zu erkennen, was für eine Liste { $path } ist; bitte eine .json-, .txt- oder .db-Datei angeben
+unknown-import-format = Es ist nicht zu erkennen, in welchem Format { $path } ist; bitte mit --format angeben
+list-unreadable = { $path } lässt sich nicht lesen: { $error }; todo doctor kann die beschädigten Aufgaben beiseitelegen
+legacy-unreadable = { $file } hat { $lines }
+unknown-me = Es ist nicht zu erkennen, wer du bist; bitte den Namen mit --me oder TODO_ME angeben
+attach-failed = { $path } lässt sich nicht anhängen: { $error }
+unknown-command = Unbekannter Befehl '{ $name }'; todo --help zeigt die Befehle und todo plugins die von Plugins hinzugefügten
+filter-unmatched-close = ')' ohne passende '(' im Filter
+filter-unexpected = Unerwartetes { $token } im Filter
+filter-unclosed-quote = Nicht geschlossenes '"' im Filter
+filter-missing-close = Fehlende ')' im Filter
+filter-expected-term = Im Filter wurde ein Ausdruck erwartet, aber { $token } gefunden
+filter-ends-early = Der Filter endet, wo ein Ausdruck erwartet wurde
+filter-colon-only = Schlagwörter, Zuständige und Status lassen sich nur mit ':' vergleichen, wie in { $field }:{ $value }
+filter-field-colon-only = Eigene Felder lassen sich nur mit ':' vergleichen, wie in { $field }:{ $value }
+regex-error = Ungültiger regulärer Ausdruck: { $problem }
+regex-unmatched-close = ')' ohne passende '('
+regex-unclosed-group = nicht geschlossene '('
+regex-unsupported-group = nur (?:...)-Gruppen und ein (?i) am Anfang werden unterstützt
+regex-nothing-to-repeat = nichts zu wiederholen
+regex-expected-count = erwartet wird eine Zahl in {"{"}...{"}"}
+regex-expected-count-end = erwartet werden eine Zahl und '{"}"}' in {"{"}...{"}"}
+regex-expected-comma = erwartet wird ',' oder '{"}"}' in {"{"}...{"}"}
+regex-repeat-too-big = Wiederholung öfter als { $max }-mal
+regex-backwards-repeat = der Bereich der Wiederholung ist verkehrt herum
+regex-trailing-backslash = '\' am Ende des Musters
+regex-unknown-escape = unbekanntes Escape '\{ $char }'
+regex-boundary-in-set = '\b' und '\B' können nicht in einer Menge stehen
+regex-range-end = ein Bereich muss mit einem Zeichen enden
+regex-unclosed-set = nicht geschlossene '['
+regex-backwards-range = der Bereich ist verkehrt herum
+regex-too-big = das Muster ist zu groß
+csv-error = CSV konnte nicht gelesen oder geschrieben werden: { $error }
+csv-read-error = CSV konnte nicht gelesen werden: { $error }
+csv-no-description = Die CSV-Datei hat keine Spalte description
+csv-not-a-number = Ungültiges { $column } '{ $value }', erwartet wird eine Zahl
+csv-bad-depends-on = Ungültiges depends_on '{ $value }', erwartet werden IDs von Aufgaben
+csv-bad-status-log = Ungültiger Eintrag in status_log '{ $value }', erwartet wird completed oder reopened
+csv-bad-completed = Ungültiges completed '{ $value }', erwartet wird true oder false
+todotxt-invalid = Ungültiges '{ $value }'
+todotxt-bad-recurrence = Nicht unterstützte Wiederholung 'rec:{ $value }'
+legacy-field-count = Erwartet werden 3 bis 5 Felder, gefunden { $count }
+legacy-bad-id = Ungültige ID '{ $value }'
+ical-no-uid = VTODO hat keine UID
+ical-bad-date = Ungültiges iCalendar-Datum '{ $value }'
+api-cannot-set = '{ $field }' lässt sich nicht auf { $value } setzen
+api-bad-body = Ungültiger Inhalt der Anfrage: { $error }
+
+## Aufgaben auflisten und zeigen
+
+no-tasks = Keine Aufgaben gefunden.
+no-tags = Keine Schlagwörter gefunden.
+tags = Schlagwörter: { $tags }
+priority-high = hoch
+priority-medium = mittel
+priority-low = niedrig
+status-completed = erledigt
+status-pending = offen
+status-in-progress = in Arbeit
+status-blocked = blockiert
+overdue = ÜBERFÄLLIG!
+timer-running = Zeitmessung läuft
+list-due = fällig am { $date }
+list-due-relative = fällig { $when }
+date-today = heute
+date-tomorrow = morgen
+date-yesterday = gestern
+date-in = in { $count } { $unit ->
+        [day] { $count ->
+            [one] Tag
+           *[other] Tagen
+        }
+        [week] { $count ->
+            [one] Woche
+           *[other] Wochen
+        }
+        [month] { $count ->
+            [one] Monat
+           *[other] Monaten
+        }
+       *[year] { $count ->
+            [one] Jahr
+           *[other] Jahren
+        }
+    }
+date-ago = vor { $count } { $unit ->
+        [day] { $count ->
+            [one] Tag
+           *[other] Tagen
+        }
+        [week] { $count ->
+            [one] Woche
+           *[other] Wochen
+        }
+        [month] { $count ->
+            [one] Monat
+           *[other] Monaten
+        }
+       *[year] { $count ->
+            [one] Jahr
+           *[other] Jahren
+        }
+    }
+list-starts = beginnt am { $date }
+list-starts-relative = beginnt { $when }
+list-repeats = wiederholt sich { $recurrence }
+list-blocked-by = wartet auf { $ids }
+list-waiting = wartet auf Rückmeldung
+list-waiting-on = wartet auf Rückmeldung von { $who }
+list-follow-up = nachfassen am { $date }
+list-follow-up-relative = nachfassen { $when }
+project-progress = { $description }: { $done } von { $total } { $total ->
+        [one] Teilaufgabe
+       *[other] Teilaufgaben
+    } erledigt
+other-tasks = Weitere Aufgaben
+show-id = ID
+show-uuid = UUID
+show-description = Beschreibung
+show-status = Status
+show-priority = Priorität
+show-assignee = Zuständig
+show-urgency = Dringlichkeit
+show-due = Fällig
+show-starts = Beginnt
+show-repeats = Wiederholung
+show-estimate = Schätzung
+show-tags = Schlagwörter
+show-fields = Felder
+show-created = Erstellt
+show-completed = Erledigt
+show-reopened = Wieder geöffnet
+show-time-spent = Aufgewendet
+show-pomodoros = Pomodoros
+show-reviewed = Durchgesehen
+show-snoozed = Zurückgestellt
+show-snoozed-until = bis { $date }
+show-waiting = Wartet
+show-waiting-on = auf { $who } seit { $since }
+show-waiting-since = seit { $since }
+show-follow-up = Nachfassen
+show-parent = Teilaufgabe von
+show-depends-on = Hängt ab von
+show-blocked-by = Wartet auf
+show-subtasks = Teilaufgaben
+show-attachments = Anhänge
+show-comments = Kommentare
+
+## Was Befehle getan haben
+
+task-added = Aufgabe mit der ID { $id } hinzugefügt
+subtask-added = Teilaufgabe mit der ID { $id } hinzugefügt
+task-completed = Aufgabe { $id } erledigt
+task-completed-next = Aufgabe { $id } erledigt, nächste Wiederholung mit der ID { $next } hinzugefügt
+task-updated = Aufgabe { $id } geändert
+task-trashed = Aufgabe { $id } in den Papierkorb verschoben
+task-restored = Aufgabe { $id } wiederhergestellt
+task-reopened = Aufgabe { $id } wieder geöffnet
+merged-into = Mit Aufgabe { $id } zusammengeführt
+due-updated = Fälligkeit von Aufgabe { $id } geändert
+priority-updated = Priorität von Aufgabe { $id } geändert
+recurrence-updated = Wiederholung von Aufgabe { $id } geändert
+notes-updated = Notizen von Aufgabe { $id } geändert
+tagged = Aufgabe { $id } verschlagwortet
+untagged = Schlagwort von Aufgabe { $id } entfernt
+archived = { $count ->
+        [one] Eine Aufgabe
+       *[other] { $count } Aufgaben
+    } archiviert
+task-snoozed = Aufgabe { $id } bis { $date } zurückgestellt
+task-snoozed-hidden = Aufgabe { $id } bis { $date } zurückgestellt und ausgeblendet
+task-deferred = Aufgabe { $id } bis { $date } aufgeschoben
+start-cleared = Beginn von Aufgabe { $id } entfernt
+estimate-updated = Schätzung von Aufgabe { $id } geändert
+comment-added = Kommentar zu Aufgabe { $id } hinzugefügt
+attached = { $attachment } an Aufgabe { $id } angehängt
+detached = { $attachment } von Aufgabe { $id } entfernt
+opening = { $attachment } wird geöffnet
+task-moved-before = Aufgabe { $id } vor Aufgabe { $before } verschoben
+task-moved-to-top = Aufgabe { $id } nach oben verschoben
+task-moved-to = Aufgabe { $id } ist jetzt { $column }
+task-assigned = Aufgabe { $id } an { $name } vergeben
+task-unassigned = Aufgabe { $id } ist niemandem mehr zugewiesen
+task-waiting = Aufgabe { $id } wartet auf Rückmeldung
+task-waiting-on = Aufgabe { $id } wartet auf Rückmeldung von { $who }
+task-stopped-waiting = Aufgabe { $id } wartet nicht mehr
+fields-updated = Felder von Aufgabe { $id } geändert
+fields-removed = Felder von Aufgabe { $id } entfernt
+dependency-added = Aufgabe { $id } hängt jetzt von Aufgabe { $on } ab
+dependency-removed = Aufgabe { $id } hängt nicht mehr von Aufgabe { $on } ab
+tasks-reordered = Aufgaben neu geordnet
+renumber-warning = die IDs der Aufgaben ändern sich; alles außerhalb der Liste, was eine Aufgabe über ihre ID nennt, etwa ein Skript, eine Notiz oder ein Link, zeigt danach auf die falsche Aufgabe oder auf keine. Die UUIDs bleiben gleich.
+confirm-renumber = Aufgaben neu nummerieren?
+renumber-nothing = Die IDs der Aufgaben haben bereits keine Lücken
+renumbered = { $count ->
+        [one] Eine Aufgabe
+       *[other] { $count } Aufgaben
+    } neu nummeriert
+no-changes = Nichts geändert
+trash-emptied = { $count ->
+        [one] Eine Aufgabe
+       *[other] { $count } Aufgaben
+    } endgültig gelöscht
+imported = { $count ->
+        [one] Eine Aufgabe
+       *[other] { $count } Aufgaben
+    } importiert
+import-skipped = { $count } doppelte, leere oder gelöschte Aufgaben übersprungen
+merged = { $added } Aufgaben hinzugefügt, { $updated } geändert, { $skipped } schon in der Liste übersprungen
+importing-old-list = Aufgaben werden aus { $from } importiert und in { $to } gespeichert
+rejected-lines = { $file } hat { $lines }; sie liegen mit dem jeweiligen Fehler in { $rejects }
+journal-recovered = { $count } ungespeicherte Änderungen aus einer unterbrochenen Sitzung wiederhergestellt
+undid = Rückgängig gemacht: { $change }
+redid = Wiederholt: { $change }
+batch-header =
+    # Die offenen Aufgaben bearbeiten, eine pro Zeile, dann speichern und beenden.
+    #
+    # Eine Zeile ändern, um ihre Aufgabe zu ändern, oder x hinter die ID setzen, um sie zu erledigen.
+    # Eine Zeile löschen, um ihre Aufgabe samt Unteraufgaben zu entfernen.
+    # Eine Zeile ohne ID hinzufügen, um eine neue Aufgabe anzulegen.
+    # Zeilen verschieben, um die Reihenfolge von --sort manual zu ändern.
+    #
+    # Die Zeilen sind todo.txt: (A) ist hohe Priorität und (C) niedrige, +tag, due:2024-06-01,
+    # t:2024-05-01 für den Beginn und status:in-progress oder status:blocked.
+    # Die Datei leer lassen, um nichts zu ändern.
+
+## Änderungen, wie Rückgängig und Wiederholen sie nennen
+
+change-add = Aufgabe { $id } hinzufügen
+change-edit = Aufgabe { $id } bearbeiten
+change-complete = Aufgabe { $id } erledigen
+change-complete-many = { $count ->
+        [one] Eine Aufgabe
+       *[other] { $count } Aufgaben
+    } erledigen
+change-reopen = Aufgabe { $id } wieder öffnen
+change-remove = Aufgabe { $id } entfernen
+change-remove-many = { $count ->
+        [one] Eine Aufgabe
+       *[other] { $count } Aufgaben
+    } entfernen
+change-restore = Aufgabe { $id } wiederherstellen
+change-import = { $count ->
+        [one] Eine Aufgabe
+       *[other] { $count } Aufgaben
+    } importieren
+change-merge = { $count ->
+        [one] Eine Aufgabe
+       *[other] { $count } Aufgaben
+    } zusammenführen
+change-tag = Tag '{ $tag }' an Aufgabe { $id } setzen
+change-untag = Tag '{ $tag }' von Aufgabe { $id } entfernen
+change-due = Fälligkeit von Aufgabe { $id } setzen
+change-start = Beginn von Aufgabe { $id } setzen
+change-priority = Priorität von Aufgabe { $id } setzen
+change-recurrence = Wiederholung von Aufgabe { $id } setzen
+change-estimate = Schätzung von Aufgabe { $id } setzen
+change-notes = Notizen von Aufgabe { $id } setzen
+change-set-field = { $name } von Aufgabe { $id } setzen
+change-unset-field = { $name } von Aufgabe { $id } entfernen
+change-comment = Aufgabe { $id } kommentieren
+change-attach = Anhang an Aufgabe { $id } hinzufügen
+change-detach = Anhang von Aufgabe { $id } entfernen
+change-depend = Aufgabe { $id } von Aufgabe { $on } abhängig machen
+change-undepend = Abhängigkeit von Aufgabe { $id } von Aufgabe { $on } entfernen
+change-assign = Aufgabe { $id } zuweisen
+change-wait = Aufgabe { $id } als wartend markieren
+change-unwait = Warten von Aufgabe { $id } beenden
+change-snooze = Aufgabe { $id } zurückstellen
+change-review = Aufgabe { $id } durchsehen
+change-move = Aufgabe { $id } verschieben
+change-move-column = Aufgabe { $id } nach { $column } verschieben
+change-reorder = Aufgaben umordnen
+change-start-timer = Zeitmessung für Aufgabe { $id } starten
+change-stop-timer = Zeitmessung für Aufgabe { $id } stoppen
+change-pomodoro = Pomodoro für Aufgabe { $id } zählen
+
+## Die Tafel
+
+board-todo = Zu erledigen
+board-in-progress = In Arbeit
+board-blocked = Blockiert
+board-done = Erledigt
+board-heading = { $column } ({ $count })
+board-heading-limit = { $column } ({ $count }/{ $limit })
+board-heading-over-limit = { $column } ({ $count }/{ $limit }) ZU VIELE!
+wip-limit-exceeded = { $count } Aufgaben sind { $column }, mehr als das Limit von { $limit }
+board-keys = Pfeiltasten oder h/j/k/l: Karte wählen   < und > oder H und L: verschieben   q: zurück zum Menü
+board-needs-terminal = die Tafel braucht ein Terminal
+
+## Schnappschüsse
+
+at-read-only = todo at führt nur Befehle aus, die die Liste lesen, wie list oder show; todo merge kann Aufgaben aus einem Schnappschuss zurückholen
+no-snapshot = Es gibt keinen Schnappschuss der Liste vom { $date } oder davor
+snapshot-from = Die Liste, wie sie am Ende des { $date } war, dem letzten Schnappschuss davor
+snapshot-failed = Schnappschuss in { $dir } konnte nicht geschrieben werden: { $error }
+
+## Zwei Kopien einer Liste vergleichen
+
+diff-none = Keine Unterschiede
+diff-added = Hinzugefügt
+diff-removed = Entfernt
+diff-completed = Erledigt
+diff-reopened = Wieder geöffnet
+diff-edited = Bearbeitet
+dry-run-none = Es würde sich nichts ändern
+dry-run-list = Es wurde nichts geändert; die Liste würde sich so ändern:
+dry-run-trash = Es wurde nichts geändert; der Papierkorb würde sich so ändern:
+
+## Die wöchentliche Übersicht
+
+digest-subject = Aufgaben-Übersicht: { $overdue } überfällig, { $due } bald fällig, { $completed } erledigt
+digest-overdue = Überfällig
+digest-due = { $days ->
+        [one] Heute fällig
+       *[other] Fällig in den nächsten { $days } Tagen
+    }
+digest-completed = { $days ->
+        [one] Heute erledigt
+       *[other] Erledigt in den letzten { $days } Tagen
+    }
+digest-completed-on = erledigt am { $date }
+digest-nothing = Nichts ist überfällig, { $days ->
+        [one] heute fällig oder heute erledigt
+       *[other] in den nächsten { $days } Tagen fällig oder in den letzten { $days } Tagen erledigt
+    }
+digest-sent = Die Übersicht wurde an { $to } geschickt
+
+## Das Menü
+
+menu-title = Aufgabenliste
+menu-add = Aufgabe hinzufügen
+menu-list = Aufgaben auflisten
+menu-complete = Aufgabe erledigen
+menu-remove = Aufgabe entfernen
+menu-set-due = Fälligkeit setzen
+menu-set-priority = Priorität setzen
+menu-edit = Aufgabe bearbeiten
+menu-undo = Rückgängig
+menu-redo = Wiederholen
+menu-filter-tag = Nach Schlagwort filtern
+menu-add-tag = Schlagwort hinzufügen
+menu-remove-tag = Schlagwort entfernen
+menu-set-recurrence = Wiederholung setzen
+menu-add-subtask = Teilaufgabe hinzufügen
+menu-archive = Erledigte Aufgaben archivieren
+menu-list-archived = Archivierte Aufgaben auflisten
+menu-restore = Archivierte Aufgabe wiederherstellen
+menu-show = Aufgabe zeigen
+menu-edit-notes = Notizen bearbeiten
+menu-board = Tafel zeigen
+menu-save-exit = Speichern und beenden
+menu-enter-choice = Ihre Wahl:
+menu-not-a-number = Ungültige Eingabe. Bitte eine Zahl oder den Namen eines Punkts eingeben.
+menu-invalid-choice = Ungültige Wahl. Bitte erneut versuchen.
+menu-invalid-id = Ungültige Aufgaben-ID
+menu-autosave-failed = Fehler: automatisches Speichern fehlgeschlagen: { $message }
+menu-enter-description = Beschreibung der Aufgabe:
+menu-enter-new-description = Neue Beschreibung:
+menu-enter-due = Fälligkeit (JJJJ-MM-TT, "tomorrow", "in 3 days"..., leer für keine):
+menu-enter-priority = Priorität (high/medium/low, leer für medium):
+menu-enter-recurrence = Wiederholung (daily/weekly/monthly, leer für keine):
+menu-enter-tag = Schlagwort:
+menu-enter-tag-filter = Schlagwort zum Filtern:
+menu-enter-tag-remove = Zu entfernendes Schlagwort:
+menu-enter-parent = ID der übergeordneten Aufgabe:
+menu-enter-subtask = Beschreibung der Teilaufgabe:
+menu-id-to-complete = ID der zu erledigenden Aufgabe:
+menu-id-to-remove = ID der zu entfernenden Aufgabe:
+menu-id-to-set-due = ID der Aufgabe für die Fälligkeit:
+menu-id-to-set-priority = ID der Aufgabe für die Priorität:
+menu-id-to-edit = ID der zu bearbeitenden Aufgabe:
+menu-id-to-tag = ID der zu verschlagwortenden Aufgabe:
+menu-id-to-untag = ID der Aufgabe, deren Schlagwort entfernt wird:
+menu-id-to-set-recurrence = ID der Aufgabe für die Wiederholung:
+menu-id-to-restore = ID der wiederherzustellenden archivierten Aufgabe:
+menu-id-to-show = ID der zu zeigenden Aufgabe:
+menu-id-to-edit-notes = ID der Aufgabe für die Notizen:
+menu-goodbye = Aufgaben gespeichert. Auf Wiedersehen!
+menu-save-before-quit = Änderungen vor dem Beenden speichern?
+menu-changes-discarded = Änderungen seit dem letzten Speichern verworfen. Auf Wiedersehen!
+menu-changes-kept = Änderungen behalten; sie werden beim nächsten Öffnen der Liste wiederhergestellt.
+
+## Rückfragen vor Änderungen
+
+confirm-choices = [j/N]
+answer-y = j
+answer-yes = ja
+nothing-changed = Nichts geändert
+confirm-remove = { $count ->
+        [one] Eine Aufgabe
+       *[other] { $count } Aufgaben
+    } entfernen?
+confirm-trash = { $count ->
+        [one] Eine Aufgabe
+       *[other] { $count } Aufgaben
+    } in den Papierkorb verschieben?
+confirm-complete = { $count ->
+        [one] Eine Aufgabe
+       *[other] { $count } Aufgaben
+    } erledigen?
+confirm-delete = { $count ->
+        [one] Eine Aufgabe
+       *[other] { $count } Aufgaben
+    } endgültig löschen?
+confirm-repair = { $count ->
+        [one] Ein Problem
+       *[other] { $count } Probleme
+    } beheben?
+duplicate-found = Das sieht aus wie Aufgabe { $id }: { $description }
+duplicate-choices = Trotzdem hinzufügen (a), mit Aufgabe { $id } zusammenführen (m) oder abbrechen (c)? [a/m/C]
+
+## Die wöchentliche Durchsicht
+
+review-choices = behalten (k), erledigt (d), zurückstellen (s), entfernen (r), Priorität (p), beenden (q):
+review-due = Fällig am { $date }
+review-last-reviewed = Zuletzt durchgesehen am { $date }
+review-never-reviewed = Noch nie durchgesehen
+review-finished = { $count } Aufgaben durchgesehen
+review-kept = Aufgabe { $id } behalten
+review-snooze-for = Zurückstellen für (etwa 3d oder 2w):
+review-enter-priority = Priorität (high/medium/low):
+review-invalid-choice = Ungültige Wahl '{ $choice }'
+
+## Zeitmessung und Pomodoros
+
+timer-started = Zeitmessung für Aufgabe { $id } gestartet
+timer-stopped = Zeitmessung für Aufgabe { $id } beendet
+timer-stopped-after = Zeitmessung für Aufgabe { $id } nach { $time } beendet
+pomodoro-cycle = Pomodoro { $cycle }/{ $cycles } für Aufgabe { $id }
+pomodoro-finished = Pomodoro beendet
+pomodoro-take-break = Zeit für { $length } Pause
+pomodoro-break = Pause
+pomodoro-break-over = Pause vorbei
+pomodoro-back-to-work = Weiter geht's
+pomodoro-all-done = { $count } Pomodoros für Aufgabe { $id } geschafft
+pomodoro-summary = { $count } Pomodoros für Aufgabe { $id } beendet
+pomodoro-left = noch { $length }
+pomodoro-done = fertig
+
+## Eine Aufgabe auswählen
+
+pick-needs-terminal = zum Auswählen einer Aufgabe wird ein Terminal gebraucht
+pick-prompt = Aufgabe auswählen
+pick-new-description = Neue Beschreibung
+
+## Die Datendatei prüfen
+
+doctor-healthy = Keine Probleme in { $path } gefunden
+doctor-quarantined = { $count ->
+        [one] Eine unlesbare Aufgabe
+       *[other] { $count } unlesbare Aufgaben
+    } nach { $path } ausgelagert
+doctor-repaired = { $path } repariert
+
+## Abgleich, Server und Hooks
+
+git-nothing-pulled = Nichts geholt; mit --merge werden beide Stände zusammengeführt und die Änderungen hier behalten
+git-merged = Entfernten Stand zusammengeführt: { $added } Aufgaben hinzugefügt, { $updated } geändert
+git-synced = { $pulled } Commits geholt, { $pushed } hochgeladen
+git-commit-failed = { $path } konnte nicht committet werden: { $error }
+caldav-synced = { $pushed } hochgeladen, { $pulled } geholt, { $deleted_remote } auf dem Server und { $deleted_local } hier gelöscht
+caldav-conflict = Aufgabe { $id } wurde auf beiden Seiten geändert; mit --prefer local oder --prefer remote abgleichen
+serving = Die Aufgabenliste ist unter http://{ $address } erreichbar
+hook-failed = Hook { $hook } fehlgeschlagen: { $error }
+notify-task-due = Aufgabe fällig
+notify-failed = Erinnerung ({ $notifier }) für Aufgabe { $id } konnte nicht gesendet werden: { $error }
+notify-no-notifiers = Erinnerungen gehen nirgendwohin; desktop, webhook, email, slack oder discord in der Tabelle [notify] der Konfigurationsdatei setzen
+notify-task-completed = Aufgabe erledigt
+notify-task-overdue = Aufgabe überfällig
+chat-failed = Aufgabe { $id } konnte nicht an { $chat } gesendet werden: { $error }
+git-needs-json = Der Abgleich mit git braucht das JSON-Backend
+git-not-run = git ließ sich nicht ausführen: { $error }
+git-failed = git { $command } schlug fehl: { $error }
+git-unexpected-count = Unerwartete Ausgabe von git rev-list: { $output }
+caldav-no-url = Die CalDAV-Aufgabenliste mit --url oder TODO_CALDAV_URL angeben, oder --git verwenden
+caldav-invalid-url = Ungültige CalDAV-URL '{ $url }'
+caldav-answered = Der CalDAV-Server antwortete { $code } { $status }
+caldav-unreachable = Der CalDAV-Server ist nicht erreichbar: { $error }
+cannot-listen = Auf { $address } lässt sich nicht lauschen: { $error }
+no-route = Keine Route für { $path }
+webhook-answered = Der Webhook { $name } antwortete { $code } { $status }
+webhook-unreachable = Der Webhook { $name } ist nicht erreichbar: { $error }
+
+## Plugins
+
+no-plugins = Keine Plugins gefunden.
+plugins-commands = Befehle
+plugins-filters = Filter
+plugins-formats = Formate
+plugins-unavailable = Dieses todo wurde ohne Plugins gebaut
+plugin-missing = Kein Plugin fügt { $kind ->
+        [command] einen Befehl
+        [filter] einen Filter
+       *[format] ein Format
+    } namens '{ $name }' hinzu
+plugin-registered-late = { $kind ->
+        [command] Ein Befehl
+        [filter] Ein Filter
+       *[format] Ein Format
+    } lässt sich nur registrieren, während sein Plugin geladen wird
+plugin-filter-not-bool = Der Filter { $name } gab { $kind } zurück statt true oder false
+plugin-invalid-id = Ungültige Aufgaben-ID { $id }
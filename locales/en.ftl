@@ -0,0 +1,591 @@
+# English messages, which the other languages fall back to.
+
+## Errors
+
+error = Error: { $message }
+warning = Warning: { $message }
+error-not-found = Task with ID { $id } not found
+error-empty-description = Task description cannot be empty
+error-empty-comment = Comment cannot be empty
+error-already-completed = Task { $id } is already completed
+error-not-completed = Task { $id } is not completed
+error-duplicate-tag = Task { $id } already has tag '{ $tag }'
+error-missing-tag = Task { $id } does not have tag '{ $tag }'
+error-missing-field = Task { $id } does not have field '{ $name }'
+error-missing-attachment = { $count ->
+        [0] Task { $id } has no attachments
+       *[other] Task { $id } has no attachment { $number }
+    }
+error-blocked = Task { $id } is blocked by open { $count ->
+        [one] task
+       *[other] tasks
+    } { $ids }
+error-dependency-cycle = Task { $id } cannot depend on task { $on }, as that would make it wait on itself
+error-missing-dependency = Task { $id } does not depend on task { $on }
+error-nothing-to-undo = Nothing to undo
+error-nothing-to-redo = Nothing to redo
+error-timer-running = The timer on task { $id } is already running
+error-no-timer = No timer is running
+error-io = File error: { $error }
+error-json = Could not read or write JSON: { $error }
+error-sqlite = Database error: { $error }
+error-locked = The todo list is open in another instance; close it and try again (lock file { $path })
+read-only-refused = With --read-only the list can only be looked at; leave it out to change the list
+read-only-missing = { $path } does not exist
+error-empty-tag = Tag cannot be empty
+error-tag-spaces = Tag '{ $tag }' cannot contain spaces
+error-field-name = Invalid field name '{ $name }', expected a letter followed by letters, digits, '-' or '_'
+error-field-built-in = '{ $name }' is a built-in field and cannot be set this way
+error-field-empty = Field '{ $name }' needs a value
+file-missing = { $path } does not exist
+
+## Input that cannot be read
+
+in-line = { $error } in line { $line }
+in-row = { $error } in row { $row }
+unreadable-lines = { $count ->
+        [one] 1 line that could not be read (line { $lines })
+       *[other] { $count } lines that could not be read (lines { $lines })
+    }
+batch-unknown-id = No open task has ID { $id }
+batch-repeated-id = Task { $id } is on more than one line
+invalid-autosave = Invalid autosave '{ $value }', expected immediate, exit or a time such as 30s or 5m
+invalid-length = Invalid length '{ $value }', expected a time such as 25m or 90s
+invalid-field-option = Invalid field '{ $value }', expected name=value such as customer=ACME
+invalid-page = Invalid page '{ $value }', expected a number from 1 up
+invalid-days = Invalid { $what ->
+        [age] age
+       *[other] duration
+    } '{ $value }', expected a number of days or weeks such as 3d or 2w
+invalid-priority = Invalid priority '{ $value }', expected high, medium or low
+invalid-status = Invalid status '{ $value }', expected todo, in-progress, blocked or done
+invalid-sort-order = Invalid sort order '{ $value }', expected due, priority, created, alpha, id, urgency or manual
+invalid-recurrence = Invalid recurrence '{ $value }', expected daily, weekly, monthly or none
+recurrence-required = A recurrence is required
+invalid-estimate = Invalid estimate '{ $value }', expected hours, minutes or points such as 2h, 90m or 3pt
+estimate-required = An estimate is required
+invalid-year = Invalid year: { $year }, expected 1 to 9999
+invalid-month = Invalid month: { $month }
+invalid-day = Invalid day: { $day }
+invalid-date = Invalid date '{ $value }', expected YYYY-MM-DD
+invalid-natural-date = Invalid date '{ $value }', expected YYYY-MM-DD, "tomorrow", "next friday", "in 3 days" or "jan 15"
+date-out-of-range = The date '{ $value }' falls outside the years 1 to 9999
+ambiguous-date = Ambiguous date '{ $value }': write it as YYYY-MM-DD or like "jan 15"
+invalid-date-format = Invalid date format '{ $value }', expected fields such as %Y, %m and %d
+invalid-timestamp = Invalid timestamp '{ $value }', expected YYYY-MM-DDTHH:MM:SSZ
+invalid-color-pair = Invalid color '{ $value }', expected { $names } set to a color, like overdue=red
+invalid-color = Invalid color '{ $value }', expected a name like red or bright blue, or #rrggbb
+invalid-color-name = Invalid color name '{ $value }', expected { $names }
+invalid-urgency-weight = Invalid urgency weight '{ $value }', expected priority, due, age, tags or tag.<name> set to a number, like due=12
+invalid-email = Invalid email address '{ $value }'
+empty-attachment = Attachment cannot be empty
+invalid-assignee = An assignee's name cannot be empty or contain spaces
+move-before-itself = Task { $id } cannot be moved before itself
+no-language = No messages in '{ $language }'; the languages are { $languages }
+config-error = { $path }: { $error }
+log-file-failed = Cannot open the log file { $path }: { $error }
+journal-bad-entry = Bad journal entry in line { $line }: { $error }
+doctor-no-tasks = The file has no list of tasks in it
+unknown-list-kind = Cannot tell what kind of list { $path } is; give a .json, .txt or .db file
+unknown-import-format = Cannot tell what format { $path } is in; give it with --format
+list-unreadable = { $path } cannot be read: { $error }; todo doctor can set aside the damaged tasks
+legacy-unreadable = { $file } has { $lines }
+unknown-me = Could not tell who you are; give your name with --me or TODO_ME
+attach-failed = Cannot attach { $path }: { $error }
+unknown-command = Unknown command '{ $name }'; todo --help lists the commands and todo plugins those added by plugins
+filter-unmatched-close = Unmatched ')' in filter
+filter-unexpected = Unexpected { $token } in filter
+filter-unclosed-quote = Unclosed '"' in filter
+filter-missing-close = Missing ')' in filter
+filter-expected-term = Expected a term in filter, found { $token }
+filter-ends-early = Filter ends where a term was expected
+filter-colon-only = Tags, assignees and statuses can only be matched with ':', as in { $field }:{ $value }
+filter-field-colon-only = Custom fields can only be matched with ':', as in { $field }:{ $value }
+regex-error = Invalid regex: { $problem }
+regex-unmatched-close = unmatched ')'
+regex-unclosed-group = unclosed '('
+regex-unsupported-group = only (?:...) groups and a leading (?i) are supported
+regex-nothing-to-repeat = nothing to repeat
+regex-expected-count = expected a number in {"{"}...{"}"}
+regex-expected-count-end = expected a number and '{"}"}' in {"{"}...{"}"}
+regex-expected-comma = expected ',' or '{"}"}' in {"{"}...{"}"}
+regex-repeat-too-big = repeat count over { $max }
+regex-backwards-repeat = repeat range is backwards
+regex-trailing-backslash = '\' at the end of the pattern
+regex-unknown-escape = unknown escape '\{ $char }'
+regex-boundary-in-set = '\b' and '\B' cannot go in a set
+regex-range-end = a range must end in a character
+regex-unclosed-set = unclosed '['
+regex-backwards-range = range is backwards
+regex-too-big = pattern too big
+csv-error = Could not read or write CSV: { $error }
+csv-read-error = Could not read CSV: { $error }
+csv-no-description = CSV has no description column
+csv-not-a-number = Invalid { $column } '{ $value }', expected a number
+csv-bad-depends-on = Invalid depends_on '{ $value }', expected task IDs
+csv-bad-status-log = Invalid status_log entry '{ $value }', expected completed or reopened
+csv-bad-completed = Invalid completed '{ $value }', expected true or false
+todotxt-invalid = Invalid '{ $value }'
+todotxt-bad-recurrence = Unsupported recurrence 'rec:{ $value }'
+legacy-field-count = Expected 3 to 5 fields, found { $count }
+legacy-bad-id = Invalid ID '{ $value }'
+ical-no-uid = VTODO has no UID
+ical-bad-date = Invalid iCalendar date '{ $value }'
+api-cannot-set = Cannot set '{ $field }' to { $value }
+api-bad-body = Invalid request body: { $error }
+
+## Listing and showing tasks
+
+no-tasks = No tasks found.
+no-tags = No tags found.
+tags = Tags: { $tags }
+priority-high = high
+priority-medium = medium
+priority-low = low
+status-completed = completed
+status-pending = pending
+status-in-progress = in progress
+status-blocked = blocked
+overdue = OVERDUE!
+timer-running = timer running
+list-due = due { $date }
+list-due-relative = due { $when }
+date-today = today
+date-tomorrow = tomorrow
+date-yesterday = yesterday
+date-in = in { $count } { $unit ->
+        [day] { $count ->
+            [one] day
+           *[other] days
+        }
+        [week] { $count ->
+            [one] week
+           *[other] weeks
+        }
+        [month] { $count ->
+            [one] month
+           *[other] months
+        }
+       *[year] { $count ->
+            [one] year
+           *[other] years
+        }
+    }
+date-ago = { $count } { $unit ->
+        [day] { $count ->
+            [one] day
+           *[other] days
+        }
+        [week] { $count ->
+            [one] week
+           *[other] weeks
+        }
+        [month] { $count ->
+            [one] month
+           *[other] months
+        }
+       *[year] { $count ->
+            [one] year
+           *[other] years
+        }
+    } ago
+list-starts = starts { $date }
+list-starts-relative = starts { $when }
+list-repeats = repeats { $recurrence }
+list-blocked-by = blocked by { $ids }
+list-waiting = waiting
+list-waiting-on = waiting on { $who }
+list-follow-up = follow up { $date }
+list-follow-up-relative = follow up { $when }
+project-progress = { $description }: { $done } of { $total } { $total ->
+        [one] subtask
+       *[other] subtasks
+    } done
+other-tasks = Other tasks
+show-id = ID
+show-uuid = UUID
+show-description = Description
+show-status = Status
+show-priority = Priority
+show-assignee = Assignee
+show-urgency = Urgency
+show-due = Due
+show-starts = Starts
+show-repeats = Repeats
+show-estimate = Estimate
+show-tags = Tags
+show-fields = Fields
+show-created = Created
+show-completed = Completed
+show-reopened = Reopened
+show-time-spent = Time spent
+show-pomodoros = Pomodoros
+show-reviewed = Reviewed
+show-snoozed = Snoozed
+show-snoozed-until = until { $date }
+show-waiting = Waiting
+show-waiting-on = on { $who } since { $since }
+show-waiting-since = since { $since }
+show-follow-up = Follow up
+show-parent = Subtask of
+show-depends-on = Depends on
+show-blocked-by = Blocked by
+show-subtasks = Subtasks
+show-attachments = Attachments
+show-comments = Comments
+
+## What commands did
+
+task-added = Task added with ID: { $id }
+subtask-added = Subtask added with ID: { $id }
+task-completed = Task { $id } completed
+task-completed-next = Task { $id } completed, next occurrence added with ID: { $next }
+task-updated = Task { $id } updated
+task-trashed = Task { $id } moved to the trash
+task-restored = Task { $id } restored
+task-reopened = Task { $id } reopened
+merged-into = Merged into task { $id }
+due-updated = Due date updated for task { $id }
+priority-updated = Priority updated for task { $id }
+recurrence-updated = Recurrence updated for task { $id }
+notes-updated = Notes updated for task { $id }
+tagged = Tagged task { $id }
+untagged = Untagged task { $id }
+archived = Archived { $count ->
+        [one] 1 task
+       *[other] { $count } tasks
+    }
+task-snoozed = Task { $id } snoozed until { $date }
+task-snoozed-hidden = Task { $id } snoozed and hidden until { $date }
+task-deferred = Task { $id } deferred until { $date }
+start-cleared = Start date cleared for task { $id }
+estimate-updated = Estimate updated for task { $id }
+comment-added = Comment added to task { $id }
+attached = Attached { $attachment } to task { $id }
+detached = Removed { $attachment } from task { $id }
+opening = Opening { $attachment }
+task-moved-before = Task { $id } moved before task { $before }
+task-moved-to-top = Task { $id } moved to the top
+task-moved-to = Task { $id } is now { $column }
+task-assigned = Task { $id } assigned to { $name }
+task-unassigned = Task { $id } unassigned
+task-waiting = Task { $id } is waiting
+task-waiting-on = Task { $id } is waiting on { $who }
+task-stopped-waiting = Task { $id } is no longer waiting
+fields-updated = Fields updated for task { $id }
+fields-removed = Fields removed from task { $id }
+dependency-added = Task { $id } now depends on task { $on }
+dependency-removed = Task { $id } no longer depends on task { $on }
+tasks-reordered = Tasks reordered
+renumber-warning = task IDs will change; anything outside the list that names a task by its ID, such as a script, a note or a link, will then point at the wrong task, or none. UUIDs stay the same.
+confirm-renumber = Renumber the tasks?
+renumber-nothing = The task IDs already have no gaps
+renumbered = Renumbered { $count } { $count ->
+        [one] task
+       *[other] tasks
+    }
+no-changes = No changes made
+trash-emptied = Deleted { $count ->
+        [one] 1 task
+       *[other] { $count } tasks
+    }
+imported = Imported { $count ->
+        [one] 1 task
+       *[other] { $count } tasks
+    }
+import-skipped = Skipped { $count } duplicate, empty or deleted tasks
+merged = Added { $added } tasks, updated { $updated }, skipped { $skipped } already in the list
+importing-old-list = Importing tasks from { $from }; they will be saved to { $to }
+rejected-lines = { $file } has { $lines }; they are set aside in { $rejects }, each with what is wrong with it
+journal-recovered = Recovered { $count } unsaved changes from an interrupted session
+undid = Undid: { $change }
+redid = Redid: { $change }
+batch-header =
+    # Edit your open tasks, one per line, then save and quit.
+    #
+    # Change a line to change its task, or put x after the ID to complete it.
+    # Delete a line to remove its task along with its subtasks.
+    # Add a line without an ID for a new task.
+    # Move lines to change the order --sort manual lists tasks in.
+    #
+    # Lines are todo.txt: (A) is high priority and (C) low, +tag, due:2024-06-01,
+    # t:2024-05-01 to start then and status:in-progress or status:blocked.
+    # Leave the file empty to change nothing.
+
+## Changes that can be undone, as undo and redo name them
+
+change-add = add task { $id }
+change-edit = edit task { $id }
+change-complete = complete task { $id }
+change-complete-many = complete { $count ->
+        [one] 1 task
+       *[other] { $count } tasks
+    }
+change-reopen = reopen task { $id }
+change-remove = remove task { $id }
+change-remove-many = remove { $count ->
+        [one] 1 task
+       *[other] { $count } tasks
+    }
+change-restore = restore task { $id }
+change-import = import { $count ->
+        [one] 1 task
+       *[other] { $count } tasks
+    }
+change-merge = merge { $count ->
+        [one] 1 task
+       *[other] { $count } tasks
+    }
+change-tag = tag '{ $tag }' on task { $id }
+change-untag = untag '{ $tag }' on task { $id }
+change-due = set due date of task { $id }
+change-start = set start date of task { $id }
+change-priority = set priority of task { $id }
+change-recurrence = set recurrence of task { $id }
+change-estimate = set estimate of task { $id }
+change-notes = set notes of task { $id }
+change-set-field = set { $name } of task { $id }
+change-unset-field = unset { $name } of task { $id }
+change-comment = comment on task { $id }
+change-attach = attach to task { $id }
+change-detach = detach from task { $id }
+change-depend = add dependency on task { $on } to task { $id }
+change-undepend = remove dependency on task { $on } from task { $id }
+change-assign = assign task { $id }
+change-wait = mark waiting task { $id }
+change-unwait = clear waiting of task { $id }
+change-snooze = snooze task { $id }
+change-review = review task { $id }
+change-move = move task { $id }
+change-move-column = move to { $column } task { $id }
+change-reorder = reorder tasks
+change-start-timer = start timer on task { $id }
+change-stop-timer = stop timer on task { $id }
+change-pomodoro = count pomodoro for task { $id }
+
+## The board
+
+board-todo = To do
+board-in-progress = In progress
+board-blocked = Blocked
+board-done = Done
+board-heading = { $column } ({ $count })
+board-heading-limit = { $column } ({ $count }/{ $limit })
+board-heading-over-limit = { $column } ({ $count }/{ $limit }) OVER!
+wip-limit-exceeded = { $count } tasks are { $column }, over the limit of { $limit }
+board-keys = arrows or h/j/k/l: choose a card   < and > or H and L: move it   q: back to the menu
+board-needs-terminal = the board needs a terminal
+
+## Snapshots
+
+at-read-only = todo at only runs commands that read the list, like list or show; todo merge can bring back tasks from a snapshot
+no-snapshot = There is no snapshot of the list from { $date } or before
+snapshot-from = The list as it was at the end of { $date }, the last snapshot before then
+snapshot-failed = could not write a snapshot to { $dir }: { $error }
+
+## Comparing two copies of a list
+
+diff-none = No differences
+diff-added = Added
+diff-removed = Removed
+diff-completed = Completed
+diff-reopened = Reopened
+diff-edited = Edited
+dry-run-none = Nothing would change
+dry-run-list = Nothing was changed; the list would change like this:
+dry-run-trash = Nothing was changed; the trash would change like this:
+
+## The weekly digest
+
+digest-subject = Todo digest: { $overdue } overdue, { $due } due soon, { $completed } completed
+digest-overdue = Overdue
+digest-due = { $days ->
+        [one] Due today
+       *[other] Due in the next { $days } days
+    }
+digest-completed = { $days ->
+        [one] Completed today
+       *[other] Completed in the last { $days } days
+    }
+digest-completed-on = completed { $date }
+digest-nothing = Nothing is overdue, { $days ->
+        [one] due or completed today
+       *[other] due in the next { $days } days or completed in the last { $days } days
+    }
+digest-sent = Sent the digest to { $to }
+
+## The interactive menu
+
+menu-title = Todo List Manager
+menu-add = Add Task
+menu-list = List Tasks
+menu-complete = Complete Task
+menu-remove = Remove Task
+menu-set-due = Set Due Date
+menu-set-priority = Set Priority
+menu-edit = Edit Task
+menu-undo = Undo
+menu-redo = Redo
+menu-filter-tag = Filter by Tag
+menu-add-tag = Add Tag
+menu-remove-tag = Remove Tag
+menu-set-recurrence = Set Recurrence
+menu-add-subtask = Add Subtask
+menu-archive = Archive Completed Tasks
+menu-list-archived = List Archived Tasks
+menu-restore = Restore Archived Task
+menu-show = Show Task
+menu-edit-notes = Edit Notes
+menu-board = Show Board
+menu-save-exit = Save and Exit
+menu-enter-choice = Enter your choice:
+menu-not-a-number = Invalid input. Please enter a number or the name of a choice.
+menu-invalid-choice = Invalid choice. Please try again.
+menu-invalid-id = Invalid task ID
+menu-autosave-failed = Error: autosave failed: { $message }
+menu-enter-description = Enter task description:
+menu-enter-new-description = Enter new description:
+menu-enter-due = Enter due date (YYYY-MM-DD, "tomorrow", "in 3 days"..., blank for none):
+menu-enter-priority = Enter priority (high/medium/low, blank for medium):
+menu-enter-recurrence = Enter recurrence (daily/weekly/monthly, blank for none):
+menu-enter-tag = Enter tag:
+menu-enter-tag-filter = Enter tag to filter by:
+menu-enter-tag-remove = Enter tag to remove:
+menu-enter-parent = Enter parent task ID:
+menu-enter-subtask = Enter subtask description:
+menu-id-to-complete = Enter task ID to complete:
+menu-id-to-remove = Enter task ID to remove:
+menu-id-to-set-due = Enter task ID to set due date:
+menu-id-to-set-priority = Enter task ID to set priority:
+menu-id-to-edit = Enter task ID to edit:
+menu-id-to-tag = Enter task ID to tag:
+menu-id-to-untag = Enter task ID to untag:
+menu-id-to-set-recurrence = Enter task ID to set recurrence:
+menu-id-to-restore = Enter archived task ID to restore:
+menu-id-to-show = Enter task ID to show:
+menu-id-to-edit-notes = Enter task ID to edit notes:
+menu-goodbye = Tasks saved. Goodbye!
+menu-save-before-quit = Save your changes before quitting?
+menu-changes-discarded = Changes since the last save discarded. Goodbye!
+menu-changes-kept = Changes kept; they will be recovered the next time the list is opened.
+
+## Asking before changes
+
+confirm-choices = [y/N]
+answer-y = y
+answer-yes = yes
+nothing-changed = Nothing changed
+confirm-remove = Remove { $count } { $count ->
+        [one] task
+       *[other] tasks
+    }?
+confirm-trash = Move to the trash { $count } { $count ->
+        [one] task
+       *[other] tasks
+    }?
+confirm-complete = Complete { $count } { $count ->
+        [one] task
+       *[other] tasks
+    }?
+confirm-delete = Permanently delete { $count } { $count ->
+        [one] task
+       *[other] tasks
+    }?
+confirm-repair = Repair { $count } { $count ->
+        [one] problem
+       *[other] problems
+    }?
+duplicate-found = This looks the same as task { $id }: { $description }
+duplicate-choices = [a]dd anyway, [m]erge into task { $id }, or [c]ancel? [a/m/C]
+
+## The weekly review
+
+review-choices = [k]eep, [d]one, [s]nooze, [r]emove, [p]riority, [q]uit:
+review-due = Due { $date }
+review-last-reviewed = Last reviewed { $date }
+review-never-reviewed = Never reviewed
+review-finished = Reviewed { $count } tasks
+review-kept = Task { $id } kept
+review-snooze-for = Snooze for (such as 3d or 2w):
+review-enter-priority = Enter priority (high/medium/low):
+review-invalid-choice = Invalid choice '{ $choice }'
+
+## Timers and pomodoros
+
+timer-started = Started the timer on task { $id }
+timer-stopped = Stopped the timer on task { $id }
+timer-stopped-after = Stopped the timer on task { $id } after { $time }
+pomodoro-cycle = Pomodoro { $cycle }/{ $cycles } on task { $id }
+pomodoro-finished = Pomodoro finished
+pomodoro-take-break = Take a break of { $length }
+pomodoro-break = Break
+pomodoro-break-over = Break over
+pomodoro-back-to-work = Back to work
+pomodoro-all-done = { $count } pomodoros done on task { $id }
+pomodoro-summary = Finished { $count } pomodoros on task { $id }
+pomodoro-left = { $length } left
+pomodoro-done = done
+
+## Picking a task
+
+pick-needs-terminal = picking a task needs a terminal
+pick-prompt = Pick a task
+pick-new-description = New description
+
+## Checking the data file
+
+doctor-healthy = No problems found in { $path }
+doctor-quarantined = Set aside { $count } unreadable { $count ->
+        [one] task
+       *[other] tasks
+    } in { $path }
+doctor-repaired = Repaired { $path }
+
+## Syncing, serving and hooks
+
+git-nothing-pulled = Nothing was pulled; sync with --merge to combine both copies, keeping the changes made here
+git-merged = Merged the remote copy: { $added } tasks added, { $updated } updated
+git-synced = Pulled { $pulled } commits, pushed { $pushed }
+git-commit-failed = could not commit { $path }: { $error }
+caldav-synced = Pushed { $pushed }, pulled { $pulled }, deleted { $deleted_remote } on the server and { $deleted_local } here
+caldav-conflict = Task { $id } was changed on both sides; sync with --prefer local or --prefer remote
+serving = Serving the todo list on http://{ $address }
+hook-failed = hook { $hook } failed: { $error }
+notify-task-due = Task due
+notify-failed = could not send the { $notifier } reminder for task { $id }: { $error }
+notify-no-notifiers = Reminders go nowhere; set desktop, webhook, email, slack or discord in the [notify] table of the config file
+notify-task-completed = Task completed
+notify-task-overdue = Task overdue
+chat-failed = could not post task { $id } to { $chat }: { $error }
+git-needs-json = Syncing with git needs the JSON backend
+git-not-run = Could not run git: { $error }
+git-failed = git { $command } failed: { $error }
+git-unexpected-count = Unexpected output from git rev-list: { $output }
+caldav-no-url = Give the CalDAV task list with --url or TODO_CALDAV_URL, or use --git
+caldav-invalid-url = Invalid CalDAV URL '{ $url }'
+caldav-answered = The CalDAV server answered { $code } { $status }
+caldav-unreachable = Could not reach the CalDAV server: { $error }
+cannot-listen = Cannot listen on { $address }: { $error }
+no-route = No route for { $path }
+webhook-answered = The { $name } webhook answered { $code } { $status }
+webhook-unreachable = Could not reach the { $name } webhook: { $error }
+
+## Plugins
+
+no-plugins = No plugins found.
+plugins-commands = Commands
+plugins-filters = Filters
+plugins-formats = Formats
+plugins-unavailable = This todo was built without plugins
+plugin-missing = No plugin adds a { $kind ->
+        [command] command
+        [filter] filter
+       *[format] format
+    } called '{ $name }'
+plugin-registered-late = A { $kind ->
+        [command] command
+        [filter] filter
+       *[format] format
+    } can only be registered while its plugin is loaded
+plugin-filter-not-bool = Filter { $name } gave { $kind }, not true or false
+plugin-invalid-id = Invalid task ID { $id }
@@ -0,0 +1,102 @@
+//! An agenda: the open tasks grouped by the day they are due, with recurring
+//! tasks shown again on each later day they will come round.
+
+use std::fmt;
+
+use crate::{Date, Task, TodoList};
+
+const WEEKDAYS: [&str; 7] = ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"];
+
+/// The open tasks due over a span of days, printed with `Display`.
+#[derive(Debug, Clone)]
+pub struct Agenda {
+    /// The first day shown.
+    pub today: Date,
+    /// Open tasks due before today, soonest first.
+    pub overdue: Vec<Task>,
+    /// Each day from today on, with the tasks due that day.
+    pub days: Vec<(Date, Vec<Task>)>,
+}
+
+impl Agenda {
+    /// Builds the agenda for `todo_list` covering `days` days from `today`.
+    pub fn new(todo_list: &TodoList, today: Date, days: i64) -> Self {
+        let open: Vec<&Task> = todo_list.sorted_tasks().into_iter().filter(|task| !task.completed).collect();
+
+        let mut overdue: Vec<Task> = open.iter()
+            .filter(|task| task.is_overdue(today))
+            .map(|task| (*task).clone())
+            .collect();
+        overdue.sort_by_key(|task| task.due_date);
+
+        let last_day = today.add_days(days - 1);
+        let mut days: Vec<(Date, Vec<Task>)> = (0..days).map(|offset| (today.add_days(offset), Vec::new())).collect();
+        for task in open {
+            for date in occurrences(task, last_day) {
+                if let Some((_, tasks)) = days.iter_mut().find(|(day, _)| *day == date) {
+                    tasks.push(task.clone());
+                }
+            }
+        }
+
+        Agenda { today, overdue, days }
+    }
+}
+
+// The task's due date followed by the dates it will repeat on, up to
+// `last_day`
+fn occurrences(task: &Task, last_day: Date) -> Vec<Date> {
+    let mut dates = Vec::new();
+    let mut next = task.due_date;
+    let day = task.recurs_on_day.or(task.due_date.map(|due| due.day)).unwrap_or(1);
+    while let Some(date) = next.filter(|date| *date <= last_day) {
+        dates.push(date);
+        next = task.recurrence.map(|recurrence| recurrence.next_date_on(date, day));
+    }
+    dates
+}
+
+impl fmt::Display for Agenda {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.overdue.is_empty() {
+            writeln!(f, "Overdue")?;
+            for task in &self.overdue {
+                write_task(f, task, true)?;
+            }
+            writeln!(f)?;
+        }
+
+        for (index, (date, tasks)) in self.days.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            let weekday = WEEKDAYS[date.weekday() as usize];
+            match date.to_days() - self.today.to_days() {
+                0 => writeln!(f, "Today, {} {}", weekday, date)?,
+                1 => writeln!(f, "Tomorrow, {} {}", weekday, date)?,
+                _ => writeln!(f, "{} {}", weekday, date)?,
+            }
+            if tasks.is_empty() {
+                writeln!(f, "  Nothing due")?;
+            }
+            for task in tasks {
+                write_task(f, task, false)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Tasks under a day are due that day, so only overdue ones show the date
+fn write_task(f: &mut fmt::Formatter, task: &Task, show_due: bool) -> fmt::Result {
+    let due = match task.due_date {
+        Some(date) if show_due => format!(" (due {})", date),
+        _ => String::new(),
+    };
+    let repeats = match task.recurrence {
+        Some(recurrence) => format!(" (repeats {})", recurrence),
+        None => String::new(),
+    };
+    let tags: String = task.tags.iter().map(|tag| format!(" #{}", tag)).collect();
+    writeln!(f, "  ID: {}, [{}] {}{}{}{}", task.id, task.priority, task.description, due, repeats, tags)
+}
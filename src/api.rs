@@ -0,0 +1,180 @@
+//! The routes of the JSON API served by `todo serve`, kept apart from any
+//! HTTP server so other programs can put the same routes behind their own:
+//!
+//! ```text
+//! GET    /tasks          every task
+//! GET    /tasks/{id}     one task
+//! POST   /tasks          add a task
+//! PATCH  /tasks/{id}     change some of a task's fields
+//! DELETE /tasks/{id}     move a task and its subtasks to the trash
+//! ```
+//!
+//! Request and response bodies are JSON. Tasks are written as they are
+//! saved; errors as `{"error": "..."}`.
+
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+use crate::storage::{self, Storage};
+use crate::{tr, Column, Date, Priority, Recurrence, TodoError, TodoList};
+
+/// What to send back for a request.
+#[derive(Debug, Clone)]
+pub struct Response {
+    /// The HTTP status code.
+    pub status: u16,
+    /// The JSON body, empty for 204 No Content.
+    pub body: String,
+    /// Whether `todo_list` was changed and needs saving. It is left alone
+    /// when a request fails part way, so the change can be dropped.
+    pub changed: bool,
+}
+
+impl Response {
+    fn json(status: u16, body: Value, changed: bool) -> Self {
+        Response {
+            status,
+            body: body.to_string(),
+            changed,
+        }
+    }
+
+    fn error(status: u16, message: impl ToString) -> Self {
+        Response::json(status, json!({ "error": message.to_string() }), false)
+    }
+}
+
+// The body of POST /tasks
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NewTask {
+    description: String,
+    due_date: Option<String>,
+    start_date: Option<String>,
+    priority: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    parent: Option<usize>,
+    notes: Option<String>,
+}
+
+/// Answers one request against `todo_list`. `path` may carry a query
+/// string, which is ignored. Deleted tasks go to `trash`, which is saved
+/// straight away.
+pub fn handle(todo_list: &mut TodoList, trash: &dyn Storage, method: &str, path: &str, body: &str) -> Response {
+    let path = path.split('?').next().unwrap_or("").trim_end_matches('/');
+    let segments: Vec<&str> = path.split('/').filter(|segment| !segment.is_empty()).collect();
+    let id = match segments.as_slice() {
+        ["tasks"] => None,
+        ["tasks", id] => match id.parse::<usize>() {
+            Ok(id) => Some(id),
+            Err(_) => return Response::error(404, tr!("no-route", path = path)),
+        },
+        _ => return Response::error(404, tr!("no-route", path = path)),
+    };
+
+    let result = match (method, id) {
+        ("GET", None) => Ok(Response::json(200, json!(todo_list.tasks()), false)),
+        ("GET", Some(id)) => task_json(todo_list, id).map(|task| Response::json(200, task, false)),
+        ("POST", None) => add(todo_list, body),
+        ("PATCH", Some(id)) => update(todo_list, id, body),
+        ("DELETE", Some(id)) => storage::trash_tasks(todo_list, trash, &[id]).map(|_| Response {
+            status: 204,
+            body: String::new(),
+            changed: true,
+        }),
+        _ => return Response::error(405, format!("{} is not allowed on {}", method, path)),
+    };
+    result.unwrap_or_else(|e| Response::error(status_for(&e), e))
+}
+
+fn add(todo_list: &mut TodoList, body: &str) -> Result<Response, TodoError> {
+    let new: NewTask = serde_json::from_str(body).map_err(bad_body)?;
+    let due_date = new.due_date.as_deref().map(|due| Date::parse_natural(due, todo_list.today())).transpose()?;
+    let priority = new.priority.as_deref().map(Priority::parse).transpose()?.unwrap_or_default();
+    let id = match new.parent {
+        Some(parent) => todo_list.add_subtask(parent, new.description, due_date, priority)?,
+        None => todo_list.add_task(new.description, due_date, priority)?,
+    };
+    for tag in &new.tags {
+        todo_list.add_tag(id, tag)?;
+    }
+    if let Some(start) = &new.start_date {
+        todo_list.set_start_date(id, Some(Date::parse_natural(start, todo_list.today())?))?;
+    }
+    if new.notes.is_some() {
+        todo_list.set_notes(id, new.notes)?;
+    }
+    Ok(Response::json(201, task_json(todo_list, id)?, true))
+}
+
+// Only the fields given are changed; setting an optional one to null clears it
+fn update(todo_list: &mut TodoList, id: usize, body: &str) -> Result<Response, TodoError> {
+    let fields: Map<String, Value> = serde_json::from_str(body).map_err(bad_body)?;
+    let task = todo_list.task(id).ok_or(TodoError::NotFound(id))?.clone();
+
+    for (name, value) in &fields {
+        match (name.as_str(), value) {
+            ("description", Value::String(description)) => todo_list.edit_task(id, description.clone())?,
+            ("due_date", Value::Null) => todo_list.set_due_date(id, None)?,
+            ("due_date", Value::String(due)) => {
+                todo_list.set_due_date(id, Some(Date::parse_natural(due, todo_list.today())?))?
+            }
+            ("start_date", Value::Null) => todo_list.set_start_date(id, None)?,
+            ("start_date", Value::String(start)) => {
+                todo_list.set_start_date(id, Some(Date::parse_natural(start, todo_list.today())?))?
+            }
+            ("priority", Value::String(priority)) => todo_list.set_priority(id, Priority::parse(priority)?)?,
+            ("notes", Value::Null) => todo_list.set_notes(id, None)?,
+            ("notes", Value::String(notes)) => todo_list.set_notes(id, Some(notes.clone()))?,
+            ("recurrence", Value::Null) => todo_list.set_recurrence(id, None)?,
+            ("recurrence", Value::String(recurrence)) => todo_list.set_recurrence(id, Recurrence::parse(recurrence)?)?,
+            ("completed", Value::Bool(true)) if !task.completed => {
+                todo_list.complete_task(id)?;
+            }
+            ("completed", Value::Bool(false)) if task.completed => todo_list.reopen_task(id)?,
+            ("completed", Value::Bool(completed)) if *completed == task.completed => {}
+            ("column", Value::String(column)) => match Column::parse(column)? {
+                column if column == task.status() => {}
+                column => {
+                    todo_list.move_to_column(id, column)?;
+                }
+            },
+            ("tags", Value::Array(tags)) => {
+                let tags: Vec<&str> = tags.iter().filter_map(Value::as_str).collect();
+                for old in task.tags.iter().filter(|old| !tags.iter().any(|tag| tag.trim().to_lowercase() == **old)) {
+                    todo_list.remove_tag(id, old)?;
+                }
+                for tag in tags.iter().filter(|tag| !task.has_tag(tag)) {
+                    todo_list.add_tag(id, tag)?;
+                }
+            }
+            _ => return Err(TodoError::Parse(tr!("api-cannot-set", field = name, value = value.to_string()))),
+        }
+    }
+    Ok(Response::json(200, task_json(todo_list, id)?, true))
+}
+
+fn task_json(todo_list: &TodoList, id: usize) -> Result<Value, TodoError> {
+    let task = todo_list.task(id).ok_or(TodoError::NotFound(id))?;
+    Ok(serde_json::to_value(task)?)
+}
+
+fn bad_body(e: serde_json::Error) -> TodoError {
+    TodoError::Parse(tr!("api-bad-body", error = e.to_string()))
+}
+
+// Mistakes in the request are the client's, anything else the server's
+fn status_for(error: &TodoError) -> u16 {
+    match error {
+        TodoError::NotFound(_) => 404,
+        TodoError::AlreadyCompleted(_)
+        | TodoError::Blocked { .. }
+        | TodoError::DuplicateTag { .. }
+        | TodoError::TimerRunning(_) => 409,
+        TodoError::Io(_) | TodoError::Json(_) | TodoError::Sync(_) | TodoError::Locked(_) => 500,
+        #[cfg(feature = "sqlite")]
+        TodoError::Sqlite(_) => 500,
+        _ => 400,
+    }
+}
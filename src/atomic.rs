@@ -0,0 +1,47 @@
+//! Crash safe file writes.
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+// `path` with `suffix` added to the end of the file name
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+// Shifts `path.1` to `path.2` and so on, dropping the oldest, then copies
+// the current file to `path.1`
+fn rotate_backups(path: &Path, backups: usize) -> io::Result<()> {
+    if backups == 0 || !path.exists() {
+        return Ok(());
+    }
+    for n in (1..backups).rev() {
+        let older = with_suffix(path, &format!(".{}", n));
+        if older.exists() {
+            fs::rename(&older, with_suffix(path, &format!(".{}", n + 1)))?;
+        }
+    }
+    fs::copy(path, with_suffix(path, ".1"))?;
+    Ok(())
+}
+
+/// Writes a file by filling a temporary file next to it and renaming it into
+/// place, so a crash part way through never leaves a half written file.
+/// The previous `backups` versions are kept as `path.1`, `path.2`, ...
+pub(crate) fn write_atomic(
+    path: &Path,
+    backups: usize,
+    write: impl FnOnce(&mut dyn Write) -> io::Result<()>,
+) -> io::Result<()> {
+    let temp_path = with_suffix(path, ".tmp");
+    let file = File::create(&temp_path)?;
+    let mut writer = BufWriter::new(file);
+    write(&mut writer)?;
+    let file = writer.into_inner().map_err(|e| e.into_error())?;
+    file.sync_all()?;
+
+    rotate_backups(path, backups)?;
+    fs::rename(&temp_path, path)
+}
@@ -0,0 +1,229 @@
+//! Editing every open task at once as text, the way `git rebase -i` edits
+//! commits. Each task is a line starting with its ID, followed by the task
+//! in the todo.txt format:
+//!
+//! ```text
+//! 3 (A) pay rent +home due:2024-06-01
+//! 5 water plants
+//! ```
+//!
+//! Changing a line changes its task, an `x` after the ID completes it and
+//! deleting it removes the task. A line without an ID is a new task, and
+//! the order of the lines becomes the list's manual order. Lines starting
+//! with `#` are comments.
+
+use std::collections::{BTreeMap, HashSet};
+
+use crate::{todotxt, tr, Column, Task, TodoError, TodoList};
+
+/// What applying an edited batch changed.
+#[derive(Debug, Clone, Default)]
+pub struct BatchEdit {
+    /// The IDs given to new tasks.
+    pub added: Vec<usize>,
+    /// The IDs of tasks whose line was changed.
+    pub changed: Vec<usize>,
+    /// The IDs of tasks marked done with an `x`.
+    pub completed: Vec<usize>,
+    /// The IDs of tasks whose line was deleted. They are left in the list
+    /// for the caller to remove, so they can go to the trash.
+    pub removed: Vec<usize>,
+    /// Whether the lines were put in a new order.
+    pub reordered: bool,
+}
+
+impl BatchEdit {
+    /// Whether nothing was changed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.changed.is_empty()
+            && self.completed.is_empty()
+            && self.removed.is_empty()
+            && !self.reordered
+    }
+}
+
+/// Writes the open tasks in their manual order, after a comment saying how
+/// to edit them.
+pub fn write(todo_list: &TodoList) -> String {
+    let mut text = tr!("batch-header");
+    text.push_str("\n\n");
+    for task in open_tasks(todo_list) {
+        // Creation dates would only get in the way
+        let line = todotxt::to_line(&Task { created_at: None, ..task.clone() });
+        text.push_str(&format!("{} {}\n", task.id, line));
+    }
+    text
+}
+
+// A line of the edited file; `id` is None for a new task
+struct Line {
+    id: Option<usize>,
+    task: Task,
+}
+
+/// Applies an edited copy of what [`write`] wrote to `todo_list`. A file
+/// with no tasks left in it is taken as a change of mind rather than as
+/// removing every open task. Lines that cannot be read are reported before
+/// anything is changed, but a change the list refuses, such as completing a
+/// blocked task, can fail with others already made, so `todo_list` should
+/// not be saved after an error.
+pub fn apply(todo_list: &mut TodoList, text: &str) -> Result<BatchEdit, TodoError> {
+    let open: Vec<usize> = open_tasks(todo_list).iter().map(|task| task.id).collect();
+    let lines = parse(text, &open)?;
+    let mut edit = BatchEdit::default();
+    if lines.is_empty() {
+        return Ok(edit);
+    }
+
+    let mut order = Vec::new();
+    for line in &lines {
+        let id = match line.id {
+            Some(id) => {
+                if update(todo_list, id, &line.task)? {
+                    edit.changed.push(id);
+                }
+                id
+            }
+            None => {
+                let id = add(todo_list, &line.task)?;
+                edit.added.push(id);
+                id
+            }
+        };
+        if line.task.completed || line.task.column == Column::Done {
+            edit.completed.push(id);
+        }
+        order.push(id);
+    }
+    if !edit.completed.is_empty() {
+        todo_list.complete_tasks(&edit.completed)?;
+    }
+
+    // Appending new tasks keeps the order the list already had
+    let kept: Vec<usize> = open.iter().copied().filter(|id| order.contains(id)).collect();
+    let existing: Vec<usize> = order.iter().copied().filter(|id| !edit.added.contains(id)).collect();
+    let appended = order.ends_with(&edit.added);
+    if existing != kept || !appended {
+        todo_list.set_order(&order)?;
+        edit.reordered = true;
+    }
+    edit.removed = open.into_iter().filter(|id| !order.contains(id)).collect();
+    Ok(edit)
+}
+
+fn open_tasks(todo_list: &TodoList) -> Vec<&Task> {
+    let mut tasks: Vec<&Task> = todo_list.iter_pending().collect();
+    tasks.sort_by_key(|task| crate::manual_key(task));
+    tasks
+}
+
+// Every task line, checking the IDs are ones that were written and used only
+// once
+fn parse(text: &str, open: &[usize]) -> Result<Vec<Line>, TodoError> {
+    let mut lines = Vec::new();
+    let mut seen = HashSet::new();
+    for (line_num, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let error = |message: String| TodoError::Parse(tr!("in-line", error = message, line = line_num + 1));
+        let (first, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let id = first.parse::<usize>().ok();
+        let task_line = if id.is_some() { rest } else { line };
+        let task = todotxt::from_line(task_line).map_err(|e| error(e.to_string()))?;
+        if let Some(id) = id {
+            if !open.contains(&id) {
+                return Err(error(tr!("batch-unknown-id", id = id)));
+            }
+            if !seen.insert(id) {
+                return Err(error(tr!("batch-repeated-id", id = id)));
+            }
+        }
+        lines.push(Line { id, task });
+    }
+    Ok(lines)
+}
+
+// Brings the task with ID `id` in line with `edited`, returning whether
+// anything was different
+fn update(todo_list: &mut TodoList, id: usize, edited: &Task) -> Result<bool, TodoError> {
+    let task = todo_list.task(id).ok_or(TodoError::NotFound(id))?.clone();
+    let mut changed = false;
+    if edited.description != task.description {
+        todo_list.edit_task(id, edited.description.clone())?;
+        changed = true;
+    }
+    if edited.priority != task.priority {
+        todo_list.set_priority(id, edited.priority)?;
+        changed = true;
+    }
+    if edited.due_date != task.due_date {
+        todo_list.set_due_date(id, edited.due_date)?;
+        changed = true;
+    }
+    if edited.start_date != task.start_date {
+        todo_list.set_start_date(id, edited.start_date)?;
+        changed = true;
+    }
+    if edited.recurrence != task.recurrence {
+        todo_list.set_recurrence(id, edited.recurrence)?;
+        changed = true;
+    }
+    // Done is left to completing, with the tasks marked x
+    if edited.column != task.column && !edited.completed && edited.column != Column::Done {
+        todo_list.move_to_column(id, edited.column)?;
+        changed = true;
+    }
+    if edited.assignee != task.assignee {
+        todo_list.assign(id, edited.assignee.as_deref())?;
+        changed = true;
+    }
+    for tag in task.tags.iter().filter(|tag| !edited.tags.contains(tag)) {
+        todo_list.remove_tag(id, tag)?;
+        changed = true;
+    }
+    for tag in edited.tags.iter().filter(|tag| !task.has_tag(tag)) {
+        todo_list.add_tag(id, tag)?;
+        changed = true;
+    }
+
+    // Values with spaces are not written, so they are kept as they are
+    let fields: BTreeMap<&String, &String> =
+        task.fields.iter().filter(|(_, value)| !value.contains(char::is_whitespace)).collect();
+    for name in fields.keys().filter(|name| !edited.fields.contains_key(name.as_str())) {
+        todo_list.remove_field(id, name)?;
+        changed = true;
+    }
+    for (name, value) in &edited.fields {
+        if fields.get(name) != Some(&value) {
+            todo_list.set_field(id, name, value)?;
+            changed = true;
+        }
+    }
+    Ok(changed)
+}
+
+fn add(todo_list: &mut TodoList, task: &Task) -> Result<usize, TodoError> {
+    let id = todo_list.add_task(task.description.clone(), task.due_date, task.priority)?;
+    for tag in &task.tags {
+        todo_list.add_tag(id, tag)?;
+    }
+    if task.start_date.is_some() {
+        todo_list.set_start_date(id, task.start_date)?;
+    }
+    if task.recurrence.is_some() {
+        todo_list.set_recurrence(id, task.recurrence)?;
+    }
+    if !task.completed && !matches!(task.column, Column::Todo | Column::Done) {
+        todo_list.move_to_column(id, task.column)?;
+    }
+    if task.assignee.is_some() {
+        todo_list.assign(id, task.assignee.as_deref())?;
+    }
+    for (name, value) in &task.fields {
+        todo_list.set_field(id, name, value)?;
+    }
+    Ok(id)
+}
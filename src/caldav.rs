@@ -0,0 +1,468 @@
+//! Two-way sync with a task list on a CalDAV server such as Nextcloud Tasks
+//! or Fastmail. Tasks are stored on the server as `<uuid>.ics` VTODOs. The
+//! ETag the server gave each one at the last sync, kept in a [`SyncState`],
+//! tells which side changed it since.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+
+use crate::ical::{self, RemoteTodo};
+use crate::{atomic, tr, Task, TodoError, TodoList, Uuid};
+
+/// Which copy wins when a task was changed both locally and on the server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Local,
+    Remote,
+}
+
+/// A CalDAV task collection to sync with.
+pub struct Client {
+    // scheme://host, which hrefs from the server are relative to
+    origin: String,
+    // The path of the collection, ending in `/`
+    collection: String,
+    authorization: Option<String>,
+    agent: ureq::Agent,
+}
+
+// A response to a request whose precondition may fail
+enum Outcome<T> {
+    Done(T),
+    Conflict,
+}
+
+// What an upload may replace on the server
+#[derive(Clone, Copy)]
+enum Condition<'a> {
+    // Nothing; the VTODO must be new
+    Absent,
+    // Exactly the version with this ETag
+    Matches(&'a str),
+    // Whatever is there, for servers that give no ETags
+    Any,
+}
+
+impl Client {
+    /// A client for the collection at `url`, such as
+    /// `https://cloud.example.com/remote.php/dav/calendars/me/tasks/`.
+    pub fn new(url: &str) -> Result<Self, TodoError> {
+        let error = || TodoError::Sync(tr!("caldav-invalid-url", url = url));
+        let scheme_end = url.find("://").ok_or_else(error)? + 3;
+        let path_start = url[scheme_end..].find('/').map_or(url.len(), |index| scheme_end + index);
+        let mut collection = url[path_start..].to_string();
+        if !collection.ends_with('/') {
+            collection.push('/');
+        }
+        Ok(Client {
+            origin: url[..path_start].to_string(),
+            collection,
+            authorization: None,
+            agent: ureq::agent(),
+        })
+    }
+
+    /// Logs in with HTTP basic authentication.
+    pub fn with_login(mut self, user: &str, password: &str) -> Self {
+        self.authorization = Some(format!("Basic {}", base64(format!("{}:{}", user, password).as_bytes())));
+        self
+    }
+
+    fn request(&self, method: &str, href: &str) -> ureq::Request {
+        let request = self.agent.request(method, &format!("{}{}", self.origin, href));
+        match &self.authorization {
+            Some(authorization) => request.set("Authorization", authorization),
+            None => request,
+        }
+    }
+
+    // The href and ETag of every VTODO in the collection
+    fn list(&self) -> Result<Vec<(String, Option<String>)>, TodoError> {
+        let body = r#"<?xml version="1.0" encoding="utf-8"?>
+<d:propfind xmlns:d="DAV:"><d:prop><d:getetag/></d:prop></d:propfind>"#;
+        let response = self.request("PROPFIND", &self.collection)
+            .set("Depth", "1")
+            .set("Content-Type", "application/xml; charset=utf-8")
+            .send_string(body)
+            .map_err(http_error)?;
+        let xml = response.into_string()?;
+        Ok(parse_multistatus(&xml)
+            .into_iter()
+            .filter(|(href, _)| href.ends_with(".ics"))
+            .collect())
+    }
+
+    fn get(&self, href: &str) -> Result<(String, Option<String>), TodoError> {
+        let response = self.request("GET", href).call().map_err(http_error)?;
+        let etag = response.header("ETag").map(str::to_string);
+        Ok((response.into_string()?, etag))
+    }
+
+    // Uploads `body` to `href` and gives the new ETag if the server sent one
+    fn put(&self, href: &str, body: &str, condition: Condition) -> Result<Outcome<Option<String>>, TodoError> {
+        let request = self.request("PUT", href).set("Content-Type", "text/calendar; charset=utf-8");
+        let request = match condition {
+            Condition::Absent => request.set("If-None-Match", "*"),
+            Condition::Matches(etag) => request.set("If-Match", etag),
+            Condition::Any => request,
+        };
+        match request.send_string(body) {
+            Ok(response) => Ok(Outcome::Done(response.header("ETag").map(str::to_string))),
+            Err(ureq::Error::Status(412, _)) => Ok(Outcome::Conflict),
+            Err(e) => Err(http_error(e)),
+        }
+    }
+
+    fn delete(&self, href: &str, etag: Option<&str>) -> Result<Outcome<()>, TodoError> {
+        let request = self.request("DELETE", href);
+        let request = match etag {
+            Some(etag) => request.set("If-Match", etag),
+            None => request,
+        };
+        match request.call() {
+            Ok(_) | Err(ureq::Error::Status(404, _)) => Ok(Outcome::Done(())),
+            Err(ureq::Error::Status(412, _)) => Ok(Outcome::Conflict),
+            Err(e) => Err(http_error(e)),
+        }
+    }
+}
+
+fn http_error(e: ureq::Error) -> TodoError {
+    match e {
+        ureq::Error::Status(code, response) => {
+            TodoError::Sync(tr!("caldav-answered", code = code, status = response.status_text()))
+        }
+        ureq::Error::Transport(transport) => TodoError::Sync(tr!("caldav-unreachable", error = transport.to_string())),
+    }
+}
+
+// What was last seen of one task on the server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Resource {
+    href: String,
+    uid: String,
+    etag: Option<String>,
+    // The task as it was after the last sync, from `ical::fingerprint`
+    fingerprint: String,
+}
+
+/// What was on the server at the last sync, by task UUID. It is kept in a
+/// file next to the data file between syncs.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SyncState {
+    resources: HashMap<Uuid, Resource>,
+}
+
+impl SyncState {
+    /// Reads the state saved by [`SyncState::save`]. A missing file means
+    /// nothing has been synced yet.
+    pub fn load(path: &Path) -> Result<Self, TodoError> {
+        if !path.exists() {
+            return Ok(SyncState::default());
+        }
+        Ok(serde_json::from_reader(BufReader::new(File::open(path)?))?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), TodoError> {
+        Ok(atomic::write_atomic(path, 0, |writer| {
+            serde_json::to_writer_pretty(&mut *writer, self)?;
+            writeln!(writer)
+        })?)
+    }
+}
+
+/// What a sync changed.
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    /// Tasks uploaded to the server.
+    pub pushed: usize,
+    /// Tasks added or updated from the server.
+    pub pulled: usize,
+    /// Tasks deleted on the server because they were removed locally.
+    pub deleted_remote: usize,
+    /// Tasks removed locally because they were deleted on the server.
+    pub deleted_local: usize,
+    /// IDs of tasks changed on both sides and left alone.
+    pub conflicts: Vec<usize>,
+}
+
+/// Brings `todo_list` and the server up to date with each other. Tasks
+/// changed on both sides since the last sync are left alone and reported,
+/// unless `prefer` says which copy to keep. This clears the undo history.
+pub fn sync(
+    todo_list: &mut TodoList,
+    state: &mut SyncState,
+    client: &Client,
+    prefer: Option<Side>,
+) -> Result<SyncReport, TodoError> {
+    let mut report = SyncReport::default();
+    let remote: HashMap<String, Option<String>> = client.list()?.into_iter().collect();
+    let mut seen = HashSet::new();
+    let mut pulled = Vec::new();
+
+    // Tasks that were on the server at the last sync
+    let known: Vec<(Uuid, Resource)> = state.resources.iter().map(|(uuid, resource)| (*uuid, resource.clone())).collect();
+    for (uuid, resource) in known {
+        seen.insert(resource.href.clone());
+        let remote_etag = remote.get(&resource.href);
+        let remote_changed = remote_etag.is_some_and(|etag| etag.is_none() || *etag != resource.etag);
+        let local_changed = todo_list.task_by_uuid(uuid)
+            .map(|task| ical::fingerprint(task, todo_list) != resource.fingerprint);
+
+        match (local_changed, remote_etag) {
+            (None, None) => {
+                state.resources.remove(&uuid);
+            }
+            // Removed here, so removed there too unless changed there since
+            (None, Some(_)) if !remote_changed => {
+                match client.delete(&resource.href, resource.etag.as_deref())? {
+                    Outcome::Done(()) => {
+                        debug!(href = %resource.href, %uuid, "deleted on the server");
+                        report.deleted_remote += 1;
+                    }
+                    Outcome::Conflict => {
+                        debug!(href = %resource.href, %uuid, "changed on the server since, so pulled instead of deleted");
+                        pull(todo_list, state, client, &resource.href, None, &mut pulled)?;
+                        continue;
+                    }
+                }
+                state.resources.remove(&uuid);
+            }
+            (None, Some(_)) => pull(todo_list, state, client, &resource.href, None, &mut pulled)?,
+            // Deleted there, so removed here too unless changed here since
+            (Some(false), None) => {
+                if let Some(task) = todo_list.task_by_uuid(uuid) {
+                    let id = task.id;
+                    debug!(href = %resource.href, id, "deleted on the server, so removed here");
+                    todo_list.remove_synced(id);
+                    report.deleted_local += 1;
+                }
+                state.resources.remove(&uuid);
+            }
+            (Some(true), None) => {
+                state.resources.remove(&uuid);
+                push(todo_list, state, client, uuid, None, &mut report)?;
+            }
+            (Some(local_changed), Some(_)) => match (local_changed, remote_changed, prefer) {
+                (false, false, _) => {}
+                (true, false, _) | (true, true, Some(Side::Local)) => {
+                    // Replacing a newer server copy on purpose needs its ETag
+                    let etag = if remote_changed { remote_etag.cloned().flatten() } else { resource.etag.clone() };
+                    push(todo_list, state, client, uuid, Some(etag), &mut report)?;
+                }
+                (false, true, _) | (true, true, Some(Side::Remote)) => {
+                    pull(todo_list, state, client, &resource.href, Some(uuid), &mut pulled)?
+                }
+                (true, true, None) => {
+                    debug!(href = %resource.href, %uuid, "changed on both sides");
+                    report.conflicts.extend(todo_list.task_by_uuid(uuid).map(|task| task.id));
+                }
+            },
+        }
+    }
+
+    // New on the server
+    for href in remote.keys().filter(|href| !seen.contains(*href)) {
+        pull(todo_list, state, client, href, None, &mut pulled)?;
+    }
+    report.pulled = pulled.len();
+    link_parents(todo_list, state, pulled);
+
+    // New here
+    let new: Vec<Uuid> = todo_list.tasks().iter()
+        .map(|task| task.uuid)
+        .filter(|uuid| !state.resources.contains_key(uuid))
+        .collect();
+    for uuid in new {
+        push(todo_list, state, client, uuid, None, &mut report)?;
+    }
+
+    todo_list.clear_history();
+    info!(
+        pushed = report.pushed,
+        pulled = report.pulled,
+        deleted_remote = report.deleted_remote,
+        deleted_local = report.deleted_local,
+        conflicts = report.conflicts.len(),
+        "synced"
+    );
+    Ok(report)
+}
+
+// Uploads the task with `uuid`. `etag` is the version on the server to
+// replace, if the server has the task at all. A conflict counts the task
+// as changed on both sides.
+fn push(
+    todo_list: &TodoList,
+    state: &mut SyncState,
+    client: &Client,
+    uuid: Uuid,
+    etag: Option<Option<String>>,
+    report: &mut SyncReport,
+) -> Result<(), TodoError> {
+    let Some(task) = todo_list.task_by_uuid(uuid) else {
+        return Ok(());
+    };
+    let (href, uid) = match state.resources.get(&uuid) {
+        Some(resource) => (resource.href.clone(), resource.uid.clone()),
+        None => (format!("{}{}.ics", client.collection, uuid), uuid.to_string()),
+    };
+    let body = ical::calendar_for(task, todo_list, &uid);
+    let condition = match &etag {
+        None => Condition::Absent,
+        Some(Some(etag)) => Condition::Matches(etag),
+        Some(None) => Condition::Any,
+    };
+
+    let new_etag = match client.put(&href, &body, condition)? {
+        Outcome::Done(new_etag) => new_etag,
+        Outcome::Conflict => {
+            debug!(%href, id = task.id, "changed on the server during the sync, so not pushed");
+            report.conflicts.push(task.id);
+            return Ok(());
+        }
+    };
+    debug!(%href, id = task.id, "pushed");
+    state.resources.insert(uuid, Resource { href, uid, etag: new_etag, fingerprint: ical::fingerprint(task, todo_list) });
+    report.pushed += 1;
+    Ok(())
+}
+
+// Downloads the VTODO at `href` and adds it to `todo_list`, or updates the
+// task with `uuid` from it. Its parent is linked later, in `link_parents`.
+fn pull(
+    todo_list: &mut TodoList,
+    state: &mut SyncState,
+    client: &Client,
+    href: &str,
+    uuid: Option<Uuid>,
+    pulled: &mut Vec<(Uuid, Option<String>)>,
+) -> Result<(), TodoError> {
+    let (text, etag) = client.get(href)?;
+    let RemoteTodo { task: remote, uid, related_to } = ical::parse_vtodo(&text)?;
+    debug!(%href, %uid, "pulled");
+
+    // Tasks made elsewhere may have UIDs that are not UUIDs
+    let uuid = uuid
+        .or_else(|| state.resources.iter().find(|(_, resource)| resource.href == href).map(|(uuid, _)| *uuid))
+        .or_else(|| uid.parse().ok())
+        .unwrap_or_else(Uuid::new_v4);
+
+    let id = match todo_list.task_by_uuid(uuid) {
+        // Bookkeeping that has no VTODO equivalent is kept, as are comments,
+        // which are only ever added here
+        Some(local) => {
+            let task = Task {
+                id: local.id,
+                uuid,
+                parent: local.parent,
+                depends_on: local.depends_on.clone(),
+                comments: local.comments.clone(),
+                recurs_from: local.recurs_from,
+                recurs_on_day: local.recurs_on_day,
+                reminded: local.reminded,
+                announced_overdue: local.announced_overdue,
+                ..remote
+            };
+            let id = task.id;
+            todo_list.insert_task(task);
+            id
+        }
+        None => todo_list.add_synced(Task { uuid, ..remote }),
+    };
+
+    let fingerprint = todo_list.task(id).map(|task| ical::fingerprint(task, todo_list)).unwrap_or_default();
+    state.resources.insert(uuid, Resource { href: href.to_string(), uid, etag, fingerprint });
+    pulled.push((uuid, related_to));
+    Ok(())
+}
+
+// Subtasks can arrive before their parents, so pulled tasks are only linked
+// to their parents once everything is in
+fn link_parents(todo_list: &mut TodoList, state: &mut SyncState, pulled: Vec<(Uuid, Option<String>)>) {
+    for (uuid, related_to) in pulled {
+        let parent = related_to
+            .and_then(|related| {
+                state.resources.iter()
+                    .find(|(_, resource)| resource.uid == related)
+                    .map(|(uuid, _)| *uuid)
+                    .or_else(|| related.parse().ok())
+            })
+            .and_then(|parent_uuid| todo_list.task_by_uuid(parent_uuid))
+            .map(|parent| parent.id);
+        if let Some(task) = todo_list.task_by_uuid(uuid).cloned() {
+            todo_list.insert_task(Task { parent, ..task });
+        }
+        if let (Some(task), Some(resource)) = (todo_list.task_by_uuid(uuid), state.resources.get_mut(&uuid)) {
+            resource.fingerprint = ical::fingerprint(task, todo_list);
+        }
+    }
+}
+
+// The href and ETag of each response in a WebDAV multistatus document. Only
+// the element names matter, whatever namespace prefix the server uses.
+fn parse_multistatus(xml: &str) -> Vec<(String, Option<String>)> {
+    let mut entries = Vec::new();
+    let (mut href, mut etag) = (None, None);
+    let mut rest = xml;
+
+    while let Some(start) = rest.find('<') {
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        let closing = tag.starts_with('/');
+        let name = tag.trim_start_matches('/').split_whitespace().next().unwrap_or("");
+        let local_name = name.rsplit(':').next().unwrap_or(name).trim_end_matches('/');
+        match (closing, local_name) {
+            (false, "href") | (false, "getetag") => {
+                let text_end = rest.find('<').unwrap_or(rest.len());
+                let text = unescape_xml(rest[..text_end].trim());
+                if local_name == "href" {
+                    href = Some(text);
+                } else {
+                    etag = Some(text);
+                }
+            }
+            (true, "response") => {
+                if let Some(href) = href.take() {
+                    entries.push((href, etag.take()));
+                }
+                etag = None;
+            }
+            _ => {}
+        }
+    }
+    entries
+}
+
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::new();
+    for chunk in bytes.chunks(3) {
+        let value = chunk.iter().enumerate().fold(0u32, |value, (i, byte)| value | (*byte as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(value >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
@@ -0,0 +1,66 @@
+//! Quick capture: a task's due date, start date, priority and tags picked
+//! out of the text it is added with, so a whole task fits on one line:
+//!
+//! ```text
+//! pay rent due:friday +finance @home p:high
+//! ```
+//!
+//! is "pay rent @home", due on Friday, tagged finance and of high priority.
+//! The tokens are `due:` and `start:` (or `t:`, as todo.txt has it) with a
+//! date, `p:`, `pri:` or `priority:` with a priority, and `+` with a tag
+//! that starts with a letter. Dates are written as for `--due`, with `-` in
+//! place of spaces, like `due:next-friday` or `start:in-2-weeks`. Contexts
+//! such as `@home` are left in the description, where they belong.
+//!
+//! A token starting with `\` is kept as it is, without the `\`, so
+//! `\+1` stays "+1" and `\due:soon` stays "due:soon".
+
+use crate::{Date, Priority, TodoError};
+
+/// What [`parse`] found in the text of a task.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Capture {
+    /// The text without the tokens that were picked out.
+    pub description: String,
+    pub due_date: Option<Date>,
+    pub start_date: Option<Date>,
+    pub priority: Option<Priority>,
+    /// In lowercase and without duplicates.
+    pub tags: Vec<String>,
+}
+
+/// Picks the tokens described in the module docs out of `text`, reading
+/// dates as from `today`. A token that names a date or priority but has a
+/// bad value is an error rather than being left in the description.
+pub fn parse(text: &str, today: Date) -> Result<Capture, TodoError> {
+    let mut capture = Capture::default();
+    let mut words = Vec::new();
+    for word in text.split_whitespace() {
+        if let Some(literal) = word.strip_prefix('\\').filter(|literal| !literal.is_empty()) {
+            words.push(literal);
+            continue;
+        }
+        let (key, value) = word.split_once(':').unwrap_or(("", ""));
+        match key.to_lowercase().as_str() {
+            "due" if !value.is_empty() => capture.due_date = Some(date(value, today)?),
+            "start" | "t" if !value.is_empty() => capture.start_date = Some(date(value, today)?),
+            "p" | "pri" | "priority" if !value.is_empty() => capture.priority = Some(Priority::parse(value)?),
+            _ => match word.strip_prefix('+').filter(|tag| tag.starts_with(char::is_alphabetic)) {
+                Some(tag) => {
+                    let tag = tag.to_lowercase();
+                    if !capture.tags.contains(&tag) {
+                        capture.tags.push(tag);
+                    }
+                }
+                None => words.push(word),
+            },
+        }
+    }
+    capture.description = words.join(" ");
+    Ok(capture)
+}
+
+// `2024-06-01` has dashes of its own, so it is tried as written first
+fn date(value: &str, today: Date) -> Result<Date, TodoError> {
+    Date::parse_natural(value, today).or_else(|e| Date::parse_natural(&value.replace('-', " "), today).map_err(|_| e))
+}
@@ -0,0 +1,92 @@
+//! Telling a team's Slack or Discord channel about the list: each task
+//! completed is posted when the list is saved, and each task that becomes
+//! overdue by the next `todo notify`.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use todo_list::notify::{Event, Notifier};
+use todo_list::storage::{Page, Storage};
+use todo_list::{tr, SortOrder, Task, TodoError, TodoList, Uuid};
+
+/// Storage that posts the tasks a save completed. Which tasks were
+/// completed as last loaded is remembered, to tell which are new.
+pub struct ChatStorage {
+    inner: Box<dyn Storage>,
+    notifiers: Vec<Box<dyn Notifier>>,
+    loaded: RefCell<Option<HashMap<Uuid, bool>>>,
+}
+
+impl ChatStorage {
+    /// Wraps `inner` if there are any channels to post to, and otherwise
+    /// gives it back as it is.
+    pub fn wrap(inner: Box<dyn Storage>, notifiers: Vec<Box<dyn Notifier>>) -> Box<dyn Storage> {
+        if notifiers.is_empty() {
+            return inner;
+        }
+        Box::new(ChatStorage { inner, notifiers, loaded: RefCell::new(None) })
+    }
+
+    fn remember(&self, todo_list: &TodoList) {
+        let completed = todo_list.tasks().iter().map(|task| (task.uuid, task.completed)).collect();
+        *self.loaded.borrow_mut() = Some(completed);
+    }
+}
+
+impl Storage for ChatStorage {
+    fn load(&self) -> Result<TodoList, TodoError> {
+        let todo_list = self.inner.load()?;
+        self.remember(&todo_list);
+        Ok(todo_list)
+    }
+
+    fn save(&self, todo_list: &TodoList) -> Result<(), TodoError> {
+        self.inner.save(todo_list)?;
+        // A list imported from elsewhere was never loaded, so nothing in it
+        // counts as newly completed
+        if let Some(loaded) = self.loaded.borrow_mut().take() {
+            for task in todo_list.iter_completed() {
+                if loaded.get(&task.uuid) == Some(&false) {
+                    post(&self.notifiers, Event::Completed, task);
+                }
+            }
+        }
+        self.remember(todo_list);
+        Ok(())
+    }
+
+    // A new task is never completed, so there is nothing to post
+    fn append(&self, task: &Task) -> Result<(), TodoError> {
+        self.inner.append(task)?;
+        if let Some(loaded) = self.loaded.borrow_mut().as_mut() {
+            loaded.insert(task.uuid, task.completed);
+        }
+        Ok(())
+    }
+
+    fn load_page(
+        &self,
+        sort: SortOrder,
+        offset: usize,
+        limit: usize,
+        keep: &dyn Fn(&Task) -> bool,
+    ) -> Result<Option<Page>, TodoError> {
+        self.inner.load_page(sort, offset, limit, keep)
+    }
+}
+
+/// Tells each of `notifiers` about `event` for `task`, warning about those
+/// that could not be reached. Whether any could is the answer.
+pub fn post(notifiers: &[Box<dyn Notifier>], event: Event, task: &Task) -> bool {
+    let mut delivered = false;
+    for notifier in notifiers {
+        match notifier.notify(event, task) {
+            Ok(()) => delivered = true,
+            Err(e) => {
+                let message = tr!("chat-failed", id = task.id, chat = notifier.name(), error = e.to_string());
+                eprintln!("{}", tr!("warning", message = message));
+            }
+        }
+    }
+    delivered
+}
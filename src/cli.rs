@@ -0,0 +1,773 @@
+//! Command line arguments. Running with no subcommand opens the interactive
+//! menu instead.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use clap::builder::FalseyValueParser;
+use clap::{ArgAction, Parser, Subcommand, ValueEnum};
+use todo_list::storage::DEFAULT_BACKUPS;
+use todo_list::urgency::Weights;
+use todo_list::{tr, Column, Date, Estimate, Priority, Recurrence, SortOrder};
+
+#[derive(Parser)]
+#[command(name = "todo", about = "A simple todo list manager")]
+pub struct Cli {
+    /// The config file to read instead of ~/.config/todo/config.toml
+    #[arg(long, env = "TODO_CONFIG", global = true)]
+    pub config: Option<PathBuf>,
+    /// The data file to use instead of the one in ~/.local/share/todo
+    #[arg(long, env = "TODO_FILE", global = true)]
+    pub file: Option<PathBuf>,
+    /// Where tasks are stored
+    #[arg(long, value_enum, default_value_t = Backend::Json, global = true)]
+    pub backend: Backend,
+    /// How many previous versions of the JSON file to keep
+    #[arg(long, default_value_t = DEFAULT_BACKUPS, global = true)]
+    pub backups: usize,
+    /// Do not complete a task when its last open subtask is completed
+    #[arg(long, global = true)]
+    pub no_auto_complete: bool,
+    /// Print without colors; setting NO_COLOR does the same
+    #[arg(long, global = true)]
+    pub no_color: bool,
+    /// The directory of programs to run when tasks are added, completed or
+    /// saved; ~/.config/todo/hooks when not given
+    #[arg(long, env = "TODO_HOOKS", global = true)]
+    pub hooks: Option<PathBuf>,
+    /// The directory of Rhai scripts adding commands, list filters and list
+    /// formats; ~/.config/todo/plugins when not given
+    #[arg(long, env = "TODO_PLUGINS", global = true)]
+    pub plugins: Option<PathBuf>,
+    /// Stop instead of warning when lines of an old list cannot be read
+    #[arg(long, global = true)]
+    pub strict: bool,
+    /// Your name on a shared list, for --mine and assigning tasks to
+    /// yourself; $USER when not given
+    #[arg(long, env = "TODO_ME", global = true)]
+    pub me: Option<String>,
+    /// Print long listings straight out rather than through $PAGER
+    #[arg(long, global = true)]
+    pub no_pager: bool,
+    /// Open the list only to look at it, without taking the lock or ever
+    /// writing to it, as for a synced copy or a backup; commands that
+    /// change the list are refused
+    #[arg(long, global = true)]
+    pub read_only: bool,
+    /// Do not ask before completing or removing several tasks at once
+    #[arg(short, long, global = true)]
+    pub yes: bool,
+    /// When the menu saves: "immediate" after every change, every few
+    /// seconds like "30s" or "5m", or only on "exit", the default
+    #[arg(long, env = "TODO_AUTOSAVE", value_parser = parse_autosave, global = true)]
+    pub autosave: Option<Autosave>,
+    /// Keep the data file in a git repository, committing every change
+    #[arg(long, env = "TODO_GIT", value_parser = FalseyValueParser::new(), global = true)]
+    pub git: bool,
+    /// How urgency is scored, like "due=12,age=0,tag.next=15"
+    #[arg(long, env = "TODO_URGENCY", global = true)]
+    pub urgency: Option<Weights>,
+    /// Log what happens to stderr: -v for loads, saves and changes, -vv
+    /// for the files parsed and sync requests as well, -vvv for every task
+    /// read
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    pub verbose: u8,
+    /// Append the log to this file instead of stderr, as -v does unless
+    /// more is asked for
+    #[arg(long, env = "TODO_LOG_FILE", global = true)]
+    pub log_file: Option<PathBuf>,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// The command `todo at` runs on a snapshot, parsed from the words after
+/// its date.
+#[derive(Parser)]
+#[command(name = "todo at <DATE>", no_binary_name = true)]
+pub struct AtCommand {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Backend {
+    /// A JSON file, todo_list.json by default
+    Json,
+    /// An SQLite database, todo_list.db by default
+    #[cfg(feature = "sqlite")]
+    Sqlite,
+    /// A todo.txt file, todo_list.txt by default
+    #[cfg(feature = "text")]
+    Text,
+}
+
+/// When the interactive menu saves the list. Changes not saved yet are
+/// still kept in the journal.
+#[derive(Clone, Copy)]
+pub enum Autosave {
+    /// After every change
+    Immediate,
+    /// After a change once this long has passed since the last save
+    Every(Duration),
+    /// Only on Save and Exit
+    OnExit,
+}
+
+impl Autosave {
+    /// Whether to save changes now, `since_save` after the list was last
+    /// saved.
+    pub fn is_due(self, since_save: Duration) -> bool {
+        match self {
+            Autosave::Immediate => true,
+            Autosave::Every(interval) => since_save >= interval,
+            Autosave::OnExit => false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ExportFormat {
+    /// The todo.txt format
+    Todotxt,
+    /// iCalendar VTODO entries, for calendar and reminder apps
+    Ics,
+    /// A Markdown checklist
+    #[value(alias = "markdown")]
+    Md,
+    /// CSV with a header row and every field, for spreadsheets
+    Csv,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ReportFormat {
+    /// A styled page that opens in any browser
+    Html,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum OutputFormat {
+    /// Aligned text for reading
+    Text,
+    /// A JSON array of tasks, for tools such as jq
+    Json,
+    /// Tab separated values with a header row
+    Tsv,
+    /// Only the descriptions, one per line
+    Plain,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ImportFormat {
+    /// The todo.txt format
+    Todotxt,
+    /// A Todoist CSV export
+    Todoist,
+    /// A Trello CSV export
+    Trello,
+    /// The JSON written by Taskwarrior's `task export`
+    Taskwarrior,
+    /// A Markdown checklist
+    #[value(alias = "markdown")]
+    Md,
+    /// CSV as written by `todo export --format csv`, or any sheet with a
+    /// description column
+    Csv,
+}
+
+impl ImportFormat {
+    /// The format a file with this name is in, going by its extension. CSV
+    /// files could come from this or another app, so they need the format
+    /// given.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_string_lossy().to_lowercase();
+        match extension.as_str() {
+            "txt" => Some(ImportFormat::Todotxt),
+            "md" | "markdown" => Some(ImportFormat::Md),
+            _ => None,
+        }
+    }
+}
+
+impl Command {
+    /// Whether `--read-only` allows the command: it only reads the list,
+    /// and does not serve it to clients that could change it.
+    pub fn reads_only(&self) -> bool {
+        !self.changes_list() && !matches!(self, Command::Serve { .. })
+    }
+
+    /// Whether the command can change the list, and so needs the lock.
+    pub fn changes_list(&self) -> bool {
+        !matches!(
+            self,
+            Command::List { .. }
+                | Command::Search { .. }
+                | Command::Show { .. }
+                | Command::Open { .. }
+                | Command::Diff { .. }
+                | Command::At { .. }
+                | Command::Export { .. }
+                | Command::Stats { .. }
+                | Command::Report { .. }
+                | Command::Agenda { .. }
+                | Command::Digest { .. }
+                // The server takes the lock for each request instead
+                | Command::Serve { .. }
+                | Command::Tags
+                | Command::Contexts
+                | Command::Board { .. }
+                | Command::Plugins
+                | Command::Pick { action: None | Some(PickAction::Show), .. }
+                | Command::Trash { command: TrashCommand::List | TrashCommand::Empty { dry_run: true, .. } }
+                | Command::Archive { dry_run: true }
+                | Command::Done { dry_run: true, .. }
+                | Command::Rm { dry_run: true, .. }
+                | Command::Import { dry_run: true, .. }
+                | Command::Merge { dry_run: true, .. }
+        )
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum PickAction {
+    /// Mark the task as completed
+    Done,
+    /// Move the task to the trash
+    Rm,
+    /// Change the task's description
+    Edit,
+    /// Show everything about the task
+    Show,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum SyncSide {
+    /// Keep the copy in this list
+    Local,
+    /// Keep the copy on the server
+    Remote,
+}
+
+#[derive(Subcommand)]
+pub enum TrashCommand {
+    /// List the tasks in the trash
+    List,
+    /// Move a task out of the trash and back into the list
+    Restore { id: usize },
+    /// Delete the tasks in the trash for good
+    Empty {
+        /// Only delete tasks trashed at least this long ago, such as 30d or 2w
+        #[arg(long, value_parser = parse_age)]
+        older_than: Option<i64>,
+        /// Print what would change instead of changing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+fn parse_due(input: &str) -> Result<Date, String> {
+    Date::parse_natural(input, Date::today()).map_err(|e| e.to_string())
+}
+
+// An age such as `30d` or `2w` given in days or weeks, as seconds
+fn parse_age(input: &str) -> Result<i64, String> {
+    parse_days(input, "age").map(|days| days * 86_400)
+}
+
+/// A length of time such as `3d` or `2w` given in days or weeks, as days.
+pub fn parse_span(input: &str) -> Result<i64, String> {
+    parse_days(input, "duration")
+}
+
+/// When to autosave, as given to `--autosave`.
+pub fn parse_autosave(input: &str) -> Result<Autosave, String> {
+    match input.trim().to_lowercase().as_str() {
+        "immediate" => Ok(Autosave::Immediate),
+        "exit" | "on-exit" => Ok(Autosave::OnExit),
+        time => parse_time(time)
+            .map(Autosave::Every)
+            .ok_or_else(|| tr!("invalid-autosave", value = input)),
+    }
+}
+
+fn parse_minutes(input: &str) -> Result<Duration, String> {
+    parse_time(input).ok_or_else(|| tr!("invalid-length", value = input))
+}
+
+// `name=value`; the name is checked when the field is set
+fn parse_field(input: &str) -> Result<(String, String), String> {
+    match input.split_once('=') {
+        Some((name, value)) => Ok((name.to_string(), value.to_string())),
+        None => Err(tr!("invalid-field-option", value = input)),
+    }
+}
+
+// Pages are counted from 1
+fn parse_page(input: &str) -> Result<usize, String> {
+    match input.trim().parse() {
+        Ok(0) | Err(_) => Err(tr!("invalid-page", value = input)),
+        Ok(page) => Ok(page),
+    }
+}
+
+// A time such as `30s` or `5m`
+fn parse_time(input: &str) -> Option<Duration> {
+    let input = input.trim();
+    let (count, seconds) = match (input.strip_suffix('s'), input.strip_suffix('m')) {
+        (Some(count), _) => (count, 1),
+        (_, Some(count)) => (count, 60),
+        _ => return None,
+    };
+    let count: u64 = count.parse().ok()?;
+    Some(Duration::from_secs(count * seconds))
+}
+
+fn parse_days(input: &str, what: &str) -> Result<i64, String> {
+    let error = || tr!("invalid-days", what = what, value = input);
+    let (count, days) = if let Some(count) = input.trim().strip_suffix('d') {
+        (count, 1)
+    } else if let Some(count) = input.trim().strip_suffix('w') {
+        (count, 7)
+    } else {
+        return Err(error());
+    };
+    let count: u32 = count.parse().map_err(|_| error())?;
+    Ok(i64::from(count) * days)
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Add a new task
+    Add {
+        /// The task description, from which tokens like due:friday,
+        /// start:monday, p:high and +tag are taken as the options they name
+        #[arg(required_unless_present = "stdin", conflicts_with = "stdin")]
+        description: Vec<String>,
+        /// Add a task for each line read from stdin, all with the same options
+        #[arg(long)]
+        stdin: bool,
+        /// Keep the description as it is, without taking options from it
+        #[arg(long)]
+        literal: bool,
+        /// Due date as YYYY-MM-DD or like "tomorrow", "next friday", "in 3 days" or "jan 15"
+        #[arg(long, value_parser = parse_due)]
+        due: Option<Date>,
+        /// The day the task can be started on, written like --due; it is
+        /// left out of listings until then
+        #[arg(long, value_parser = parse_due)]
+        start: Option<Date>,
+        /// high, medium or low; medium unless p: in the description says otherwise
+        #[arg(long)]
+        priority: Option<Priority>,
+        /// Tag to attach; may be given more than once
+        #[arg(long)]
+        tag: Vec<String>,
+        /// Repeat daily, weekly or monthly when completed
+        #[arg(long)]
+        repeat: Option<Recurrence>,
+        /// The effort expected, in hours, minutes or points such as 2h, 90m or 3pt
+        #[arg(long)]
+        estimate: Option<Estimate>,
+        /// Who is to do the task
+        #[arg(long)]
+        assign: Option<String>,
+        /// A custom field as name=value, like customer=ACME; may be given
+        /// more than once
+        #[arg(long, value_parser = parse_field)]
+        field: Vec<(String, String)>,
+        /// Add as a subtask of the task with this ID
+        #[arg(long)]
+        parent: Option<usize>,
+        /// ID of a task that has to be completed first; may be given more than once
+        #[arg(long)]
+        depends_on: Vec<usize>,
+    },
+    /// List all tasks
+    List {
+        /// Only show tasks matching a filter, like "tag:work and due<7d and
+        /// not completed"
+        filter: Vec<String>,
+        /// Only show tasks with this tag
+        #[arg(long)]
+        tag: Option<String>,
+        /// Only show tasks assigned to you
+        #[arg(long)]
+        mine: bool,
+        /// Only show open tasks waiting on someone else
+        #[arg(long)]
+        waiting: bool,
+        /// Show archived tasks instead
+        #[arg(long)]
+        archived: bool,
+        /// Show snoozed tasks too
+        #[arg(long)]
+        all: bool,
+        /// Order by due, priority, created, alpha, id, urgency or manual;
+        /// priority unless the config file sets another
+        #[arg(long, env = "TODO_SORT")]
+        sort: Option<SortOrder>,
+        /// Print as text, json, tsv or plain
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        output: OutputFormat,
+        /// Show only this many tasks; with the SQLite backend only those are
+        /// read
+        #[arg(long)]
+        limit: Option<usize>,
+        /// Which page of --limit tasks to show, starting from 1
+        #[arg(long, requires = "limit", value_parser = parse_page)]
+        page: Option<usize>,
+        /// Only show tasks the plugin filter of this name accepts
+        #[arg(long)]
+        plugin_filter: Option<String>,
+        /// Print the tasks with the plugin format of this name
+        #[arg(long, conflicts_with = "output")]
+        plugin_format: Option<String>,
+        /// Group the tasks by project, the top-level tasks with subtasks,
+        /// with a bar showing how far along each is
+        #[arg(long, conflicts_with_all = ["output", "plugin_format"])]
+        by_project: bool,
+    },
+    /// List the tasks whose description or notes contain some text
+    Search {
+        /// The text to look for, ignoring case and accents
+        #[arg(required = true)]
+        query: Vec<String>,
+        /// Match case and accents exactly as written
+        #[arg(long, conflicts_with = "regex")]
+        exact: bool,
+        /// Take the text as a regular expression, like '^call .*bank'
+        #[arg(long)]
+        regex: bool,
+        /// Order by due, priority, created, alpha, id, urgency or manual;
+        /// priority unless the config file sets another
+        #[arg(long, env = "TODO_SORT")]
+        sort: Option<SortOrder>,
+        /// Print as text, json, tsv or plain
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        output: OutputFormat,
+    },
+    /// Move completed tasks to the archive
+    Archive {
+        /// Print what would change instead of changing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Bring a task back from the archive
+    Restore { id: usize },
+    /// Mark tasks as completed
+    Done {
+        /// IDs of the tasks to complete
+        #[arg(required_unless_present = "tag", conflicts_with = "tag")]
+        ids: Vec<usize>,
+        /// Complete every open task with this tag
+        #[arg(long)]
+        tag: Option<String>,
+        /// Complete tasks even when tasks they depend on are still open
+        #[arg(long)]
+        force: bool,
+        /// Print what would change instead of changing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Move tasks to the trash
+    Rm {
+        /// IDs of the tasks to remove
+        #[arg(required_unless_present = "completed", conflicts_with = "completed")]
+        ids: Vec<usize>,
+        /// Remove every completed task
+        #[arg(long)]
+        completed: bool,
+        /// Print what would change instead of changing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Look at, restore or empty the trash
+    Trash {
+        #[command(subcommand)]
+        command: TrashCommand,
+    },
+    /// Go through the open tasks one at a time, deciding what to do with each
+    Review,
+    /// Start timing work on a task, stopping any other timer
+    Start { id: usize },
+    /// Stop the running timer
+    Stop,
+    /// Work on a task in pomodoros, timed work periods with breaks between
+    Pomodoro {
+        id: usize,
+        /// How long each work period lasts
+        #[arg(long, default_value = "25m", value_parser = parse_minutes)]
+        work: Duration,
+        /// How long each break lasts
+        #[arg(long = "break", default_value = "5m", value_parser = parse_minutes)]
+        rest: Duration,
+        /// How many work periods to run
+        #[arg(long, default_value_t = 4)]
+        cycles: u32,
+    },
+    /// Put a task off, moving its due date later
+    Snooze {
+        id: usize,
+        /// How long to put it off, such as 3d or 2w
+        #[arg(value_parser = parse_span)]
+        duration: i64,
+        /// Leave the task out of listings until its new due date
+        #[arg(long)]
+        hide: bool,
+    },
+    /// Leave a task out of listings until the day it can be started on,
+    /// written like "monday" or "in 2 weeks", or clear that with "none"
+    Defer { id: usize, start: String },
+    /// Make a task repeat daily, weekly or monthly, or stop it repeating with "none"
+    Repeat { id: usize, recurrence: String },
+    /// Set the effort a task is expected to take, such as 2h, 90m or 3pt, or clear it with "none"
+    Estimate { id: usize, estimate: String },
+    /// Write the list in another format
+    Export {
+        #[arg(long, value_enum)]
+        format: ExportFormat,
+        /// File to write to instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Add tasks from a file in another format
+    Import {
+        /// Taken from the file's extension when left out
+        #[arg(long, value_enum)]
+        format: Option<ImportFormat>,
+        /// The file to read
+        input: PathBuf,
+        /// Print what would change instead of changing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Combine another copy of the list, such as one from another machine, into this one
+    Merge {
+        /// The other list, a .json, .txt (todo.txt) or .db file
+        input: PathBuf,
+        /// Print what would change instead of changing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Show the tasks added, removed, completed, reopened or edited between
+    /// two copies of a list, such as a backup and the list now
+    Diff {
+        /// The older copy, a .json, .txt (todo.txt) or .db file, or a
+        /// numbered backup like todo_list.json.1
+        old: PathBuf,
+        /// The newer copy
+        new: PathBuf,
+    },
+    /// Show everything about a task, including its notes
+    Show { id: usize },
+    /// Edit a task's notes in $EDITOR
+    Notes {
+        id: usize,
+        /// Remove the notes instead of editing them
+        #[arg(long)]
+        clear: bool,
+    },
+    /// Add a timestamped comment to a task, shown by `show`
+    Comment {
+        id: usize,
+        /// The comment
+        #[arg(required = true)]
+        text: Vec<String>,
+    },
+    /// Attach files or URLs to a task, listed by `show`
+    Attach {
+        id: usize,
+        /// Paths, kept as absolute paths, or URLs
+        #[arg(required = true)]
+        attachments: Vec<String>,
+    },
+    /// Remove an attachment from a task, by its number in `show`
+    Detach { id: usize, number: usize },
+    /// Open a task's first attachment, or the one with this number, with the
+    /// program the system opens it with
+    Open {
+        id: usize,
+        #[arg(default_value_t = 1)]
+        number: usize,
+    },
+    /// Attach a tag to a task
+    Tag { id: usize, tag: String },
+    /// Detach a tag from a task
+    Untag { id: usize, tag: String },
+    /// List every tag in use
+    Tags,
+    /// List every context, like @phone, that open tasks name
+    Contexts,
+    /// Move a task to a column of the board, like in-progress, or in the
+    /// manual order that --sort manual lists tasks in
+    Move {
+        id: usize,
+        /// The column: todo, in-progress, blocked or done
+        #[arg(conflicts_with_all = ["before", "to_top"])]
+        column: Option<Column>,
+        /// Put it just before this task
+        #[arg(long, required_unless_present_any = ["to_top", "column"], conflicts_with = "to_top")]
+        before: Option<usize>,
+        /// Put it first
+        #[arg(long)]
+        to_top: bool,
+    },
+    /// Show the tasks in columns by status: todo, in progress, blocked and
+    /// done
+    Board {
+        /// Only show tasks matching a filter, as for list
+        filter: Vec<String>,
+        /// Only show tasks assigned to you
+        #[arg(long)]
+        mine: bool,
+    },
+    /// Assign a task to someone, or to yourself when no name is given
+    Assign { id: usize, name: Option<String> },
+    /// Assign a task to no one
+    Unassign { id: usize },
+    /// Mark a task as waiting on someone else, like a reply or work handed off
+    Wait {
+        id: usize,
+        /// Who or what the task is waiting on
+        on: Vec<String>,
+        /// The day to follow up on it, such as "friday" or "in 3 days"
+        #[arg(long, value_parser = parse_due)]
+        follow_up: Option<Date>,
+    },
+    /// Stop a task waiting on anyone
+    Unwait { id: usize },
+    /// Set custom fields on a task, like customer=ACME ticket=JIRA-42
+    Set {
+        id: usize,
+        #[arg(required = true, value_parser = parse_field)]
+        fields: Vec<(String, String)>,
+    },
+    /// Remove custom fields from a task
+    Unset {
+        id: usize,
+        #[arg(required = true)]
+        names: Vec<String>,
+    },
+    /// Make a task wait until another is completed
+    Depend {
+        id: usize,
+        /// The ID of the task to wait on
+        on: usize,
+    },
+    /// Stop a task waiting on another
+    Undepend { id: usize, on: usize },
+    /// Sync with a task list on a CalDAV server, or with the git remote
+    /// when using --git
+    Sync {
+        /// The URL of the CalDAV task list
+        #[arg(long, env = "TODO_CALDAV_URL")]
+        url: Option<String>,
+        /// The user name to log in with
+        #[arg(long, env = "TODO_CALDAV_USER")]
+        user: Option<String>,
+        /// The password to log in with; best given through the environment
+        #[arg(long, env = "TODO_CALDAV_PASSWORD", hide_env_values = true)]
+        password: Option<String>,
+        /// Which copy to keep of tasks changed on both sides
+        #[arg(long, value_enum)]
+        prefer: Option<SyncSide>,
+        /// With --git, merge in the remote copy when tasks were changed on
+        /// both sides, keeping this copy's changes where they clash
+        #[arg(long)]
+        merge: bool,
+    },
+    /// Show the tasks due today and tomorrow, day by day
+    Agenda {
+        /// Show the next seven days instead
+        #[arg(long)]
+        week: bool,
+    },
+    /// Sum up the overdue tasks, those due soon and those recently
+    /// completed, to send by email
+    Digest {
+        /// Send the digest to this address through sendmail, or the program
+        /// the config file names
+        #[arg(long)]
+        email: Option<String>,
+        /// Write the digest as an email to this .eml file
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// How many days to look ahead for due tasks and back for completed ones
+        #[arg(long, default_value_t = 7, value_parser = clap::value_parser!(i64).range(1..))]
+        days: i64,
+    },
+    /// Serve the list as a JSON API over HTTP
+    Serve {
+        /// The port to listen on
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+        /// The address to listen on; the API has no login, so think twice
+        /// before making it reachable from other machines
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+    },
+    /// Show counts of open and completed tasks and how quickly they get done
+    Stats {
+        /// Print as text, json, tsv or plain
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        output: OutputFormat,
+    },
+    /// Write a status report to share, with figures, overdue tasks and a
+    /// section for each tag
+    Report {
+        #[arg(long, value_enum, default_value_t = ReportFormat::Html)]
+        format: ReportFormat,
+        /// File to write to instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Show a desktop notification for each task coming due; meant to be
+    /// run from cron
+    Notify {
+        /// Remind about tasks due within this many days
+        #[arg(long, default_value_t = 1)]
+        within: u32,
+    },
+    /// Choose a task by typing part of its description, then act on it
+    Pick {
+        /// What to do with the chosen task; without one its ID is printed
+        #[arg(value_enum)]
+        action: Option<PickAction>,
+        /// Offer completed tasks too
+        #[arg(long)]
+        all: bool,
+    },
+    /// Mark a completed task as open again
+    Reopen { id: usize },
+    /// Give the tasks new IDs counting up from 1, closing the gaps left by
+    /// removed tasks. Anything outside the list that names a task by ID,
+    /// such as a script or a note, will point at the wrong task afterwards
+    Renumber,
+    /// Check the data file for damaged or inconsistent tasks and offer to
+    /// repair it, setting aside tasks that cannot be read
+    Doctor,
+    /// Run a command that only reads the list, like `list` or `show 3`, on
+    /// the list as it was at the end of a past day
+    At {
+        /// The day, as YYYY-MM-DD or like "yesterday" or "2 weeks ago"
+        #[arg(value_parser = parse_due)]
+        date: Date,
+        /// The command and its arguments
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        command: Vec<String>,
+    },
+    /// Edit every open task at once in $EDITOR: change, complete, delete,
+    /// add and reorder lines
+    EditAll,
+    /// Change a task's description
+    Edit {
+        id: usize,
+        /// The new description
+        #[arg(required = true)]
+        description: Vec<String>,
+    },
+    /// List the commands, filters and formats added by plugins
+    Plugins,
+    /// A command added by a plugin, with its arguments
+    #[command(external_subcommand)]
+    Plugin(Vec<String>),
+}
@@ -0,0 +1,35 @@
+//! Where a list gets the time from. Everything a [`TodoList`] works out
+//! from the time, such as which tasks are overdue or snoozed, when a
+//! recurring task comes round next and when a task was completed, goes by
+//! its clock, which is the system's unless [`TodoList::set_clock`] says
+//! otherwise.
+//!
+//! [`TodoList`]: crate::TodoList
+//! [`TodoList::set_clock`]: crate::TodoList::set_clock
+
+use crate::{Date, Timestamp};
+
+/// A source of the current time.
+pub trait Clock: Send + Sync {
+    /// The current time.
+    fn now(&self) -> Timestamp;
+
+    /// Today's date in UTC.
+    fn today(&self) -> Date {
+        self.now().date()
+    }
+}
+
+/// The system's clock, which lists use unless given another.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Timestamp {
+        Timestamp::now()
+    }
+
+    fn today(&self) -> Date {
+        Date::today()
+    }
+}
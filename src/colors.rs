@@ -0,0 +1,101 @@
+//! The colors task listings use. By default completed tasks are dimmed and
+//! struck through, overdue ones red, blocked ones yellow and high priority
+//! ones bold; a scheme can give each of these a color of its own.
+
+use std::str::FromStr;
+
+use colored::{Color, ColoredString, Colorize};
+
+use crate::{tr, Date, Priority, Task, TodoError};
+
+const NAMES: &str = "overdue, blocked, completed or high";
+
+/// The color of each kind of task in a listing, where one is set. The
+/// dimming, strike-through and bold are kept either way.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorScheme {
+    overdue: Option<Color>,
+    blocked: Option<Color>,
+    completed: Option<Color>,
+    high: Option<Color>,
+}
+
+impl Default for ColorScheme {
+    fn default() -> Self {
+        ColorScheme {
+            overdue: Some(Color::Red),
+            blocked: Some(Color::Yellow),
+            completed: None,
+            high: None,
+        }
+    }
+}
+
+impl ColorScheme {
+    /// Parses comma separated `name=color` pairs such as
+    /// `overdue=magenta,high=bright blue`, starting from the defaults. See
+    /// [`ColorScheme::set`] for the names and colors.
+    pub fn parse(input: &str) -> Result<Self, TodoError> {
+        let mut scheme = ColorScheme::default();
+        for pair in input.split(',').map(str::trim).filter(|pair| !pair.is_empty()) {
+            let (name, color) = pair.split_once('=').ok_or_else(|| {
+                TodoError::Parse(tr!("invalid-color-pair", value = pair, names = NAMES))
+            })?;
+            scheme.set(name.trim(), color.trim())?;
+        }
+        Ok(scheme)
+    }
+
+    /// Sets the color of `overdue`, `blocked`, `completed` or `high`
+    /// priority tasks to a color name such as `red` or `bright blue`, a hex
+    /// color like `#ff8800`, or `none` for no color.
+    pub fn set(&mut self, name: &str, color: &str) -> Result<(), TodoError> {
+        let color = match color.to_lowercase().as_str() {
+            "none" => None,
+            color => Some(Color::from_str(color).map_err(|_| {
+                TodoError::Parse(tr!("invalid-color", value = color))
+            })?),
+        };
+        match name.to_lowercase().as_str() {
+            "overdue" => self.overdue = color,
+            "blocked" => self.blocked = color,
+            "completed" => self.completed = color,
+            "high" => self.high = color,
+            name => return Err(TodoError::Parse(tr!("invalid-color-name", value = name, names = NAMES))),
+        }
+        Ok(())
+    }
+
+    // How `text`, the line for `task`, is shown. `colored` leaves it plain
+    // when color is off.
+    pub(crate) fn style(&self, task: &Task, text: &str, today: Date, blocked: bool) -> ColoredString {
+        let mut styled = text.normal();
+        let color = if task.completed {
+            styled = styled.dimmed().strikethrough();
+            self.completed
+        } else if task.is_overdue(today) {
+            self.overdue
+        } else if blocked {
+            self.blocked
+        } else if task.priority == Priority::High {
+            self.high
+        } else {
+            None
+        };
+        if task.priority == Priority::High {
+            styled = styled.bold();
+        }
+        match color {
+            Some(color) => styled.color(color),
+            None => styled,
+        }
+    }
+}
+
+impl FromStr for ColorScheme {
+    type Err = TodoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ColorScheme::parse(s)
+    }
+}
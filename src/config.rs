@@ -0,0 +1,204 @@
+//! Settings read from `~/.config/todo/config.toml`, for defaults that would
+//! otherwise need a flag every time:
+//!
+//! ```toml
+//! file = "~/Dropbox/todo.json"
+//! sort = "due"
+//! autosave = "30s"
+//! date_format = "%d/%m/%Y"
+//! # Listings say "due in 2 days" unless this is false
+//! relative_dates = false
+//! # Otherwise LC_ALL, LC_MESSAGES or LANG decides
+//! language = "de"
+//! # Days of snapshots kept for `todo at`; 0 keeps none
+//! snapshots = 365
+//! # The filter `todo list` uses when it is not given one
+//! list = "not completed and tag:work"
+//!
+//! [colors]
+//! overdue = "magenta"
+//! high = "bright blue"
+//!
+//! # The most tasks each column of the board is meant to hold
+//! [wip_limits]
+//! in-progress = 3
+//!
+//! # Where `todo digest --email` sends from, and the program it sends with
+//! [email]
+//! from = "todo@example.com"
+//! sendmail = "msmtp -t"
+//!
+//! # Where `todo notify` sends reminders; the desktop unless this says not
+//! [notify]
+//! desktop = false
+//! webhook = "https://example.com/hooks/todo"
+//! email = "me@example.com"
+//! # Channels told when tasks are completed or become overdue
+//! slack = "https://hooks.slack.com/services/..."
+//! discord = "https://discord.com/api/webhooks/..."
+//! ```
+//!
+//! Every setting can be left out, and flags and environment variables
+//! override the file.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use todo_list::colors::ColorScheme;
+use todo_list::notify::{Chat, ChatNotifier, DesktopNotifier, EmailNotifier, Notifier, WebhookNotifier};
+use todo_list::{i18n, mail, tr, Column, DateFormat, SortOrder, TodoError};
+
+use crate::cli::{self, Autosave};
+
+// The file as written, before its values are checked
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct File {
+    file: Option<PathBuf>,
+    sort: Option<String>,
+    autosave: Option<String>,
+    date_format: Option<String>,
+    relative_dates: Option<bool>,
+    language: Option<String>,
+    snapshots: Option<usize>,
+    list: Option<String>,
+    #[serde(default)]
+    colors: BTreeMap<String, String>,
+    #[serde(default)]
+    wip_limits: BTreeMap<String, usize>,
+    #[serde(default)]
+    email: Email,
+    #[serde(default)]
+    notify: Notify,
+}
+
+/// How `todo digest` sends email.
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Email {
+    /// The address email is sent from, when the sending program should not
+    /// pick one.
+    pub from: Option<String>,
+    /// The sendmail-compatible program to send with, and its arguments.
+    pub sendmail: Option<String>,
+}
+
+/// Where `todo notify` sends reminders.
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Notify {
+    /// Whether to show them on the desktop, as when it is not set.
+    pub desktop: Option<bool>,
+    /// A URL to POST each one to, as JSON.
+    pub webhook: Option<String>,
+    /// An address to email them to, sent as `[email]` says.
+    pub email: Option<String>,
+    /// A Slack webhook URL to post completed and overdue tasks to.
+    pub slack: Option<String>,
+    /// A Discord webhook URL to post completed and overdue tasks to.
+    pub discord: Option<String>,
+}
+
+/// The settings from the config file, each `None` where it was not set.
+#[derive(Default)]
+pub struct Config {
+    /// The data file, with a leading `~` expanded.
+    pub file: Option<PathBuf>,
+    pub sort: Option<SortOrder>,
+    pub autosave: Option<Autosave>,
+    pub date_format: Option<DateFormat>,
+    pub relative_dates: Option<bool>,
+    /// How many days of snapshots to keep.
+    pub snapshots: Option<usize>,
+    /// The filter `todo list` uses when it is not given one, split into words.
+    pub list: Vec<String>,
+    pub colors: ColorScheme,
+    /// The work-in-progress limit of each column of the board that has one.
+    pub wip_limits: BTreeMap<Column, usize>,
+    pub email: Email,
+    pub notify: Notify,
+}
+
+impl Config {
+    /// Reads the config file at `path`. A missing file gives the defaults;
+    /// one that cannot be read or has a bad value is an error naming it. The
+    /// language it sets is used for messages from then on.
+    pub fn load(path: &Path) -> Result<Self, TodoError> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Config::default()),
+            Err(e) => return Err(e.into()),
+        };
+        let error = |message: String| TodoError::Parse(tr!("config-error", path = path.display().to_string(), error = message));
+        let file: File = toml::from_str(&text).map_err(|e| error(e.message().to_string()))?;
+
+        if let Some(language) = &file.language {
+            i18n::set_language(language).map_err(|e| error(e.to_string()))?;
+        }
+        let mut colors = ColorScheme::default();
+        for (name, color) in &file.colors {
+            colors.set(name, color).map_err(|e| error(e.to_string()))?;
+        }
+        let mut wip_limits = BTreeMap::new();
+        for (column, &limit) in &file.wip_limits {
+            wip_limits.insert(Column::parse(column).map_err(|e| error(e.to_string()))?, limit);
+        }
+        Ok(Config {
+            file: file.file.map(|file| expand_home(&file)),
+            sort: file.sort.map(|sort| sort.parse()).transpose().map_err(|e: TodoError| error(e.to_string()))?,
+            autosave: file.autosave.map(|autosave| cli::parse_autosave(&autosave)).transpose().map_err(error)?,
+            date_format: file.date_format.map(|format| format.parse()).transpose().map_err(|e: TodoError| error(e.to_string()))?,
+            relative_dates: file.relative_dates,
+            snapshots: file.snapshots,
+            list: file.list.map(|list| list.split_whitespace().map(str::to_string).collect()).unwrap_or_default(),
+            colors,
+            wip_limits,
+            email: file.email,
+            notify: file.notify,
+        })
+    }
+
+    /// The notifiers `todo notify` sends reminders with, as set in the
+    /// `[notify]` table.
+    pub fn notifiers(&self) -> Vec<Box<dyn Notifier>> {
+        let mut notifiers: Vec<Box<dyn Notifier>> = Vec::new();
+        if self.notify.desktop.unwrap_or(true) {
+            notifiers.push(Box::new(DesktopNotifier));
+        }
+        if let Some(url) = &self.notify.webhook {
+            notifiers.push(Box::new(WebhookNotifier { url: url.clone() }));
+        }
+        if let Some(to) = &self.notify.email {
+            notifiers.push(Box::new(EmailNotifier {
+                to: to.clone(),
+                from: self.email.from.clone(),
+                sendmail: self.email.sendmail.clone().unwrap_or_else(|| mail::DEFAULT_SENDMAIL.to_string()),
+            }));
+        }
+        notifiers
+    }
+
+    /// The Slack and Discord channels told about tasks that are completed
+    /// or become overdue.
+    pub fn chat_notifiers(&self) -> Vec<Box<dyn Notifier>> {
+        [(Chat::Slack, &self.notify.slack), (Chat::Discord, &self.notify.discord)]
+            .into_iter()
+            .filter_map(|(chat, url)| {
+                let url = url.clone()?;
+                Some(Box::new(ChatNotifier { chat, url }) as Box<dyn Notifier>)
+            })
+            .collect()
+    }
+}
+
+// `~/notes/todo.json` is in the home directory, as a shell would have it
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), env::var_os("HOME")) {
+        (Ok(rest), Some(home)) => Path::new(&home).join(rest),
+        _ => path.to_path_buf(),
+    }
+}
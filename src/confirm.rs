@@ -0,0 +1,78 @@
+//! Asking before changes that are hard to take back, listing exactly which
+//! tasks they touch so a mistyped ID is caught before anything happens, and
+//! before adding a task that is already on the list.
+
+use std::io::{self, IsTerminal, Write};
+
+use todo_list::{tr, Task};
+
+/// Lists `tasks` and asks `question` about them, such as "Remove 2 tasks?".
+/// Anything but y or yes, including no answer at all, is taken as no.
+pub fn ask(question_text: &str, tasks: &[&Task]) -> io::Result<bool> {
+    for task in tasks {
+        let status = if task.completed { "[x]" } else { "[ ]" };
+        println!("  {} ID: {}, {}", status, task.id, task.description);
+    }
+    question(question_text)
+}
+
+/// Asks `question` like [`ask`], for changes that are not to tasks one by
+/// one.
+pub fn question(question: &str) -> io::Result<bool> {
+    print!("{} {} ", question, tr!("confirm-choices"));
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    // At the end of input the cursor is still on the question
+    if io::stdin().read_line(&mut answer)? == 0 {
+        println!();
+    }
+    let yes = is_yes(&answer);
+    if !yes {
+        println!("{}", tr!("nothing-changed"));
+    }
+    Ok(yes)
+}
+
+/// Whether `answer` is y or yes, in English or the language in use.
+pub fn is_yes(answer: &str) -> bool {
+    // The English answers work in every language
+    let answer = answer.trim().to_lowercase();
+    ["y", "yes"].contains(&answer.as_str()) || answer == tr!("answer-y") || answer == tr!("answer-yes")
+}
+
+/// What to do about a new task that looks like one already on the list.
+pub enum Duplicate {
+    /// Add it anyway
+    Add,
+    /// Fold what was given for it into the existing task instead
+    Merge,
+    /// Add nothing
+    Cancel,
+}
+
+/// Points out that `existing` looks the same as the task being added and
+/// asks what to do. When input is not from a terminal, as in scripts, it
+/// only warns and the task is added.
+pub fn duplicate(existing: &Task) -> io::Result<Duplicate> {
+    println!("{}", tr!("duplicate-found", id = existing.id, description = existing.description.as_str()));
+    if !io::stdin().is_terminal() {
+        return Ok(Duplicate::Add);
+    }
+    print!("{} ", tr!("duplicate-choices", id = existing.id));
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    if io::stdin().read_line(&mut answer)? == 0 {
+        println!();
+    }
+    let choice = match answer.trim().to_lowercase().as_str() {
+        "a" | "add" => Duplicate::Add,
+        "m" | "merge" => Duplicate::Merge,
+        _ => Duplicate::Cancel,
+    };
+    if let Duplicate::Cancel = choice {
+        println!("{}", tr!("nothing-changed"));
+    }
+    Ok(choice)
+}
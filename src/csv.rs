@@ -0,0 +1,261 @@
+//! Comma separated values for spreadsheets, one row per task under a header
+//! row, quoted as RFC 4180 has it: a value with a comma, a quote or a line
+//! break in it is put in double quotes, with its quotes doubled.
+//!
+//! ```text
+//! id,uuid,description,completed,priority,due,tags,...
+//! 3,0f6e...,"Call Bob, then Alice",false,high,2024-06-01,work phone,...
+//! ```
+//!
+//! Every field of a task has a column. Tags and dependencies are separated
+//! by spaces; attachments, comments, time spent and completions are one per
+//! line within their cell; and each custom field gets a column of its own, named like
+//! `field:customer`. Importing goes by the header, in any order and any
+//! case, so a sheet made by hand with only a `description` column, or that
+//! and a few others, can be read too.
+
+use std::collections::{BTreeSet, HashMap};
+
+// The crate, not this module
+use ::csv::{ReaderBuilder, StringRecord, WriterBuilder};
+
+use crate::{
+    tr, Column, Comment, Date, Estimate, Interval, Priority, Recurrence, StatusChange, Task, Timestamp, TodoError, TodoList, Waiting,
+};
+
+const COLUMNS: [&str; 29] = [
+    "id",
+    "uuid",
+    "description",
+    "completed",
+    "status",
+    "priority",
+    "due",
+    "start",
+    "tags",
+    "recurrence",
+    "recurs_from",
+    "parent",
+    "depends_on",
+    "notes",
+    "attachments",
+    "assignee",
+    "waiting_on",
+    "waiting_since",
+    "follow_up",
+    "estimate",
+    "rank",
+    "created_at",
+    "completed_at",
+    "hidden_until",
+    "last_reviewed",
+    "pomodoros",
+    "comments",
+    "time_log",
+    "status_log",
+];
+
+const FIELD_PREFIX: &str = "field:";
+
+/// Writes every task as a row, with a header row first.
+pub fn export(todo_list: &TodoList) -> Result<String, TodoError> {
+    let fields: BTreeSet<&str> = todo_list.tasks().iter().flat_map(|task| task.fields.keys().map(String::as_str)).collect();
+    let mut writer = WriterBuilder::new().from_writer(Vec::new());
+    let header = COLUMNS.iter().map(|column| column.to_string()).chain(fields.iter().map(|name| format!("{}{}", FIELD_PREFIX, name)));
+    writer.write_record(header).map_err(csv_error)?;
+    for task in todo_list.tasks() {
+        let custom = fields.iter().map(|name| task.fields.get(*name).cloned().unwrap_or_default());
+        writer.write_record(to_row(task).into_iter().chain(custom)).map_err(csv_error)?;
+    }
+    let bytes = writer.into_inner().map_err(|e| TodoError::Parse(tr!("csv-error", error = e.to_string())))?;
+    Ok(String::from_utf8(bytes).expect("tasks are valid UTF-8"))
+}
+
+/// Adds the rows of CSV `text` to `todo_list` with new IDs and UUIDs and
+/// returns those IDs. Subtasks and dependencies are linked by the `id`
+/// column, to the new IDs of the rows they name; links to rows not in the
+/// file are dropped. Rows without a description are skipped.
+pub fn import(todo_list: &mut TodoList, text: &str) -> Result<Vec<usize>, TodoError> {
+    let mut reader = ReaderBuilder::new().flexible(true).from_reader(text.as_bytes());
+    let headers = reader.headers().map_err(csv_error)?.clone();
+    if !headers.iter().any(|header| header.trim().eq_ignore_ascii_case("description")) {
+        return Err(TodoError::Parse(tr!("csv-no-description")));
+    }
+
+    let mut tasks = Vec::new();
+    // The ID each row had in the file, by its position in `tasks`
+    let mut old_ids = HashMap::new();
+    for (row_num, record) in reader.records().enumerate() {
+        let record = record.map_err(csv_error)?;
+        let row = Row { headers: &headers, record: &record };
+        if row.get("description").is_none() {
+            continue;
+        }
+        let task = from_row(&row, todo_list.now()).map_err(|e| TodoError::Parse(tr!("in-row", error = e.to_string(), row = row_num + 2)))?;
+        if let Some(id) = row.get("id").and_then(|id| id.parse::<usize>().ok()) {
+            old_ids.insert(id, tasks.len());
+        }
+        tasks.push(task);
+    }
+
+    // Imported tasks are numbered in order from the next free ID
+    let new_id = |old: usize| old_ids.get(&old).map(|position| todo_list.next_id + position);
+    for task in &mut tasks {
+        task.parent = task.parent.and_then(new_id);
+        task.recurs_from = task.recurs_from.and_then(new_id);
+        task.depends_on = task.depends_on.iter().filter_map(|&on| new_id(on)).collect();
+    }
+    Ok(todo_list.add_imported(tasks))
+}
+
+fn to_row(task: &Task) -> Vec<String> {
+    let text = |value: Option<String>| value.unwrap_or_default();
+    let ids = |ids: &[usize]| ids.iter().map(usize::to_string).collect::<Vec<_>>().join(" ");
+    let lines = |lines: Vec<String>| lines.join("\n");
+    vec![
+        task.id.to_string(),
+        task.uuid.to_string(),
+        task.description.clone(),
+        task.completed.to_string(),
+        task.status().to_string(),
+        task.priority.to_string(),
+        text(task.due_date.map(|due| due.to_string())),
+        text(task.start_date.map(|start| start.to_string())),
+        task.tags.join(" "),
+        text(task.recurrence.map(|recurrence| recurrence.to_string())),
+        text(task.recurs_from.map(|from| from.to_string())),
+        text(task.parent.map(|parent| parent.to_string())),
+        ids(&task.depends_on),
+        text(task.notes.clone()),
+        lines(task.attachments.clone()),
+        text(task.assignee.clone()),
+        text(task.waiting.as_ref().and_then(|waiting| waiting.on.clone())),
+        text(task.waiting.as_ref().map(|waiting| waiting.since.to_string())),
+        text(task.waiting.as_ref().and_then(|waiting| waiting.follow_up).map(|day| day.to_string())),
+        text(task.estimate.map(|estimate| estimate.to_string())),
+        text(task.rank.map(|rank| rank.to_string())),
+        text(task.created_at.map(|at| at.to_string())),
+        text(task.completed_at.map(|at| at.to_string())),
+        text(task.hidden_until.map(|until| until.to_string())),
+        text(task.last_reviewed.map(|at| at.to_string())),
+        task.pomodoros.to_string(),
+        lines(task.comments.iter().map(|comment| format!("{} {}", comment.at, comment.text)).collect()),
+        lines(task.time_log.iter().map(|interval| match interval.end {
+            Some(end) => format!("{} {}", interval.start, end),
+            None => interval.start.to_string(),
+        }).collect()),
+        lines(task.status_log.iter().map(|change| {
+            format!("{} {}", change.at, if change.completed { "completed" } else { "reopened" })
+        }).collect()),
+    ]
+}
+
+// A row whose cells are looked up by column name, ignoring case; empty
+// cells count as missing
+struct Row<'a> {
+    headers: &'a StringRecord,
+    record: &'a StringRecord,
+}
+
+impl Row<'_> {
+    fn get(&self, column: &str) -> Option<&str> {
+        let index = self.headers.iter().position(|header| header.trim().eq_ignore_ascii_case(column))?;
+        self.record.get(index).map(str::trim).filter(|value| !value.is_empty())
+    }
+
+    fn parse<T>(&self, column: &str, parse: impl Fn(&str) -> Result<T, TodoError>) -> Result<Option<T>, TodoError> {
+        self.get(column).map(parse).transpose()
+    }
+
+    fn number(&self, column: &str) -> Result<Option<usize>, TodoError> {
+        self.parse(column, |value| {
+            value.parse().map_err(|_| TodoError::Parse(tr!("csv-not-a-number", column = column, value = value)))
+        })
+    }
+
+    fn lines(&self, column: &str) -> impl Iterator<Item = &str> {
+        self.get(column).unwrap_or_default().lines().map(str::trim).filter(|line| !line.is_empty())
+    }
+}
+
+fn from_row(row: &Row, now: Timestamp) -> Result<Task, TodoError> {
+    let mut task = Task {
+        description: row.get("description").unwrap_or_default().to_string(),
+        completed: row.parse("completed", parse_bool)?.unwrap_or(false),
+        column: row.parse("status", Column::parse)?.unwrap_or_default(),
+        priority: row.parse("priority", str::parse::<Priority>)?.unwrap_or_default(),
+        due_date: row.parse("due", Date::parse)?,
+        start_date: row.parse("start", Date::parse)?,
+        tags: row.get("tags").unwrap_or_default().split([' ', ',']).filter(|tag| !tag.is_empty()).map(str::to_string).collect(),
+        recurrence: row.parse("recurrence", Recurrence::parse)?.flatten(),
+        recurs_from: row.number("recurs_from")?,
+        parent: row.number("parent")?,
+        notes: row.get("notes").map(str::to_string),
+        assignee: row.get("assignee").map(str::to_string),
+        estimate: row.parse("estimate", Estimate::parse)?.flatten(),
+        rank: row.number("rank")?,
+        created_at: row.parse("created_at", Timestamp::parse)?,
+        completed_at: row.parse("completed_at", Timestamp::parse)?,
+        hidden_until: row.parse("hidden_until", Date::parse)?,
+        last_reviewed: row.parse("last_reviewed", Timestamp::parse)?,
+        pomodoros: row.number("pomodoros")?.unwrap_or(0) as u32,
+        ..Task::default()
+    };
+    for on in row.get("depends_on").unwrap_or_default().split([' ', ',']).filter(|on| !on.is_empty()) {
+        let on = on.parse().map_err(|_| TodoError::Parse(tr!("csv-bad-depends-on", value = on)))?;
+        task.depends_on.push(on);
+    }
+    // A sheet made by hand may only say the task is done in its status
+    task.normalize_status();
+    task.attachments = row.lines("attachments").map(str::to_string).collect();
+    // A sheet made by hand may only say who the task is waiting on
+    let (on, follow_up) = (row.get("waiting_on").map(str::to_string), row.parse("follow_up", Date::parse)?);
+    if let Some(since) = row.parse("waiting_since", Timestamp::parse)? {
+        task.waiting = Some(Waiting { on, since, follow_up });
+    } else if on.is_some() || follow_up.is_some() {
+        task.waiting = Some(Waiting { on, since: now, follow_up });
+    }
+    for line in row.lines("comments") {
+        let (at, text) = line.split_once(' ').unwrap_or((line, ""));
+        task.comments.push(Comment { at: Timestamp::parse(at)?, text: text.trim().to_string() });
+    }
+    for line in row.lines("time_log") {
+        let mut times = line.split_whitespace();
+        let start = Timestamp::parse(times.next().unwrap_or_default())?;
+        let end = times.next().map(Timestamp::parse).transpose()?;
+        task.time_log.push(Interval { start, end });
+    }
+    for line in row.lines("status_log") {
+        let (at, change) = line.split_once(' ').unwrap_or((line, ""));
+        let completed = match change.trim() {
+            "completed" => true,
+            "reopened" => false,
+            other => {
+                return Err(TodoError::Parse(tr!("csv-bad-status-log", value = other)))
+            }
+        };
+        task.status_log.push(StatusChange { at: Timestamp::parse(at)?, completed });
+    }
+    for (index, header) in row.headers.iter().enumerate() {
+        let Some(name) = header.trim().strip_prefix(FIELD_PREFIX) else {
+            continue;
+        };
+        if let Some(value) = row.record.get(index).map(str::trim).filter(|value| !value.is_empty()) {
+            task.fields.insert(name.to_string(), value.to_string());
+        }
+    }
+    Ok(task)
+}
+
+// Spreadsheets write these in all sorts of ways
+fn parse_bool(value: &str) -> Result<bool, TodoError> {
+    match value.to_lowercase().as_str() {
+        "true" | "yes" | "x" | "1" | "done" | "completed" => Ok(true),
+        "false" | "no" | "0" | "open" | "pending" => Ok(false),
+        _ => Err(TodoError::Parse(tr!("csv-bad-completed", value = value))),
+    }
+}
+
+fn csv_error(e: ::csv::Error) -> TodoError {
+    TodoError::Parse(tr!("csv-error", error = e.to_string()))
+}
@@ -0,0 +1,346 @@
+mod natural;
+
+use std::fmt;
+use std::str::FromStr;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{i18n, tr, TodoError};
+
+// The years a date can have, as many as YYYY-MM-DD has room for
+const YEARS: std::ops::RangeInclusive<i32> = 1..=9999;
+
+/// A calendar date without a time of day, used for due dates. Serialized as
+/// a YYYY-MM-DD string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    /// Creates a date, rejecting months and days that do not exist and
+    /// years outside 1 to 9999, which YYYY-MM-DD cannot hold.
+    pub fn new(year: i32, month: u32, day: u32) -> Result<Self, TodoError> {
+        if !YEARS.contains(&year) {
+            return Err(TodoError::Parse(tr!("invalid-year", year = year)));
+        }
+        if !(1..=12).contains(&month) {
+            return Err(TodoError::Parse(tr!("invalid-month", month = month)));
+        }
+        if day < 1 || day > days_in_month(year, month) {
+            return Err(TodoError::Parse(tr!("invalid-day", day = day)));
+        }
+        Ok(Date { year, month, day })
+    }
+
+    /// Parses a date written as YYYY-MM-DD, the format used for input and storage.
+    pub fn parse(input: &str) -> Result<Self, TodoError> {
+        let error = || TodoError::Parse(tr!("invalid-date", value = input.trim()));
+        let parts: Vec<&str> = input.trim().split('-').collect();
+        if parts.len() != 3 {
+            return Err(error());
+        }
+        let year = parts[0].parse().map_err(|_| error())?;
+        let month = parts[1].parse().map_err(|_| error())?;
+        let day = parts[2].parse().map_err(|_| error())?;
+        Date::new(year, month, day)
+    }
+
+    /// Parses a date the way people write it, relative to `today`:
+    /// "tomorrow", "friday", "next week", "in 3 days", "3 days ago", "jan
+    /// 15" or YYYY-MM-DD. A weekday means the first one after today.
+    pub fn parse_natural(input: &str, today: Date) -> Result<Self, TodoError> {
+        natural::parse(input, today)
+    }
+
+    /// How far this date is from `today` in words, like "tomorrow", "in 2
+    /// days" or "3 weeks ago", in the language messages are written in.
+    /// Past two weeks it counts weeks, then months and then years.
+    pub fn relative_to(&self, today: Date) -> String {
+        let days = self.to_days() - today.to_days();
+        let (count, unit) = match days.abs() {
+            0 => return tr!("date-today"),
+            1 if days > 0 => return tr!("date-tomorrow"),
+            1 => return tr!("date-yesterday"),
+            n @ 2..=13 => (n, "day"),
+            n @ 14..=59 => (n / 7, "week"),
+            n @ 60..=364 => (n / 30, "month"),
+            n => (n / 365, "year"),
+        };
+        let message = if days > 0 { "date-in" } else { "date-ago" };
+        i18n::message(message, &[("count", count.into()), ("unit", unit.into())])
+    }
+
+    /// Today's date in UTC.
+    pub fn today() -> Self {
+        Date::from_days(unix_seconds().div_euclid(86_400))
+    }
+
+    /// The number of days since 1970-01-01.
+    pub fn to_days(&self) -> i64 {
+        let year = if self.month <= 2 { self.year - 1 } else { self.year } as i64;
+        let era = year.div_euclid(400);
+        let yoe = year.rem_euclid(400);
+        let month = self.month as i64;
+        let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + self.day as i64 - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146_097 + doe - 719_468
+    }
+
+    /// The day of the week, where Monday is 0 and Sunday 6.
+    pub fn weekday(&self) -> u32 {
+        // 1970-01-01 was a Thursday
+        (self.to_days() + 3).rem_euclid(7) as u32
+    }
+
+    /// The date `days` days later, or earlier when negative.
+    pub fn add_days(&self, days: i64) -> Self {
+        Date::from_days(self.to_days() + days)
+    }
+
+    /// The date `months` months later. Days past the end of the target month
+    /// are moved back to its last day, so Jan 31 plus one month is Feb 28/29.
+    pub fn add_months(&self, months: i32) -> Self {
+        let total = self.year * 12 + (self.month as i32 - 1) + months;
+        let year = total.div_euclid(12);
+        let month = (total.rem_euclid(12) + 1) as u32;
+        let day = self.day.min(days_in_month(year, month));
+        Date { year, month, day }
+    }
+
+    /// The date `days` days later, or earlier when negative, or None when
+    /// that falls outside the years 1 to 9999.
+    pub fn checked_add_days(&self, days: i64) -> Option<Self> {
+        let days = self.to_days().checked_add(days)?;
+        let first = Date { year: *YEARS.start(), month: 1, day: 1 };
+        let last = Date { year: *YEARS.end(), month: 12, day: 31 };
+        (first.to_days()..=last.to_days()).contains(&days).then(|| Date::from_days(days))
+    }
+
+    /// The date `months` months later as [`Date::add_months`] has it, or
+    /// None when that falls outside the years 1 to 9999.
+    pub fn checked_add_months(&self, months: i64) -> Option<Self> {
+        let total = (self.year as i64 * 12 + (self.month as i64 - 1)).checked_add(months)?;
+        let year = i32::try_from(total.div_euclid(12)).ok().filter(|year| YEARS.contains(year))?;
+        let month = (total.rem_euclid(12) + 1) as u32;
+        Some(Date { year, month, day: self.day.min(days_in_month(year, month)) })
+    }
+
+    /// Converts days since 1970-01-01 into a calendar date.
+    pub fn from_days(days: i64) -> Self {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = (yoe + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+        Date { year, month, day }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = TodoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Date::parse(s)
+    }
+}
+
+impl From<Date> for String {
+    fn from(date: Date) -> Self {
+        date.to_string()
+    }
+}
+
+impl TryFrom<String> for Date {
+    type Error = TodoError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Date::parse(&value)
+    }
+}
+
+/// How dates are written in listings, as a pattern such as `%d/%m/%Y` or
+/// `%a %e %b`. Dates are always stored and read as YYYY-MM-DD.
+///
+/// The pattern can use `%Y` for the year, `%y` for its last two digits,
+/// `%m` and `%d` for the month and day as two digits, `%e` for the day
+/// without a leading zero, `%b` and `%B` for the month's name, short and
+/// long, `%a` and `%A` for the weekday's, and `%%` for a percent sign.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DateFormat(String);
+
+impl Default for DateFormat {
+    fn default() -> Self {
+        DateFormat("%Y-%m-%d".to_string())
+    }
+}
+
+impl DateFormat {
+    /// Checks that `pattern` only uses the fields above.
+    pub fn parse(pattern: &str) -> Result<Self, TodoError> {
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            if c == '%' && !matches!(chars.next(), Some('Y' | 'y' | 'm' | 'd' | 'e' | 'b' | 'B' | 'a' | 'A' | '%')) {
+                return Err(TodoError::Parse(tr!("invalid-date-format", value = pattern)));
+            }
+        }
+        Ok(DateFormat(pattern.to_string()))
+    }
+
+    /// Writes `date` in this format.
+    pub fn format(&self, date: Date) -> String {
+        let month = natural::MONTHS[date.month as usize - 1];
+        let weekday = natural::WEEKDAYS[date.weekday() as usize];
+        let mut out = String::new();
+        let mut chars = self.0.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                out.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('Y') => out.push_str(&format!("{:04}", date.year)),
+                Some('y') => out.push_str(&format!("{:02}", date.year.rem_euclid(100))),
+                Some('m') => out.push_str(&format!("{:02}", date.month)),
+                Some('d') => out.push_str(&format!("{:02}", date.day)),
+                Some('e') => out.push_str(&date.day.to_string()),
+                Some('b') => out.push_str(&capitalize(&month[..3])),
+                Some('B') => out.push_str(&capitalize(month)),
+                Some('a') => out.push_str(&capitalize(&weekday[..3])),
+                Some('A') => out.push_str(&capitalize(weekday)),
+                Some(other) => out.push(other),
+                None => out.push('%'),
+            }
+        }
+        out
+    }
+}
+
+impl FromStr for DateFormat {
+    type Err = TodoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        DateFormat::parse(s)
+    }
+}
+
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    chars.next().map(|first| first.to_uppercase().chain(chars).collect()).unwrap_or_default()
+}
+
+/// A moment in time to the second, in UTC, used to record when tasks were
+/// created and completed. Serialized as a YYYY-MM-DDTHH:MM:SSZ string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct Timestamp {
+    seconds: i64,
+}
+
+impl Timestamp {
+    /// The current time.
+    pub fn now() -> Self {
+        Timestamp { seconds: unix_seconds() }
+    }
+
+    /// The moment `seconds` seconds after 1970-01-01T00:00:00Z.
+    pub fn from_seconds(seconds: i64) -> Self {
+        Timestamp { seconds }
+    }
+
+    /// The number of seconds since 1970-01-01T00:00:00Z.
+    pub fn seconds(&self) -> i64 {
+        self.seconds
+    }
+
+    /// The UTC date this moment falls on.
+    pub fn date(&self) -> Date {
+        Date::from_days(self.seconds.div_euclid(86_400))
+    }
+
+    /// Parses a timestamp written as YYYY-MM-DDTHH:MM:SSZ.
+    pub fn parse(input: &str) -> Result<Self, TodoError> {
+        let input = input.trim();
+        let error = || TodoError::Parse(tr!("invalid-timestamp", value = input));
+        let (date, time) = input.split_once('T').ok_or_else(error)?;
+        let date = Date::parse(date).map_err(|_| error())?;
+        let parts: Vec<u32> = time
+            .strip_suffix('Z')
+            .ok_or_else(error)?
+            .split(':')
+            .map(|part| part.parse().map_err(|_| error()))
+            .collect::<Result<_, _>>()?;
+        match parts[..] {
+            [hour, minute, second] if hour < 24 && minute < 60 && second < 60 => {
+                let seconds = date.to_days() * 86_400 + (hour * 3600 + minute * 60 + second) as i64;
+                Ok(Timestamp { seconds })
+            }
+            _ => Err(error()),
+        }
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let time = self.seconds.rem_euclid(86_400);
+        write!(f, "{}T{:02}:{:02}:{:02}Z", self.date(), time / 3600, time % 3600 / 60, time % 60)
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = TodoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Timestamp::parse(s)
+    }
+}
+
+impl From<Timestamp> for String {
+    fn from(timestamp: Timestamp) -> Self {
+        timestamp.to_string()
+    }
+}
+
+impl TryFrom<String> for Timestamp {
+    type Error = TodoError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Timestamp::parse(&value)
+    }
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// The seconds since 1970-01-01T00:00:00Z. The standard library has no clock
+// in the browser, so there it is JavaScript's
+#[cfg(not(target_arch = "wasm32"))]
+fn unix_seconds() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs() as i64).unwrap_or(0)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn unix_seconds() -> i64 {
+    (js_sys::Date::now() / 1000.0) as i64
+}
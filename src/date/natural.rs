@@ -0,0 +1,140 @@
+//! Due dates written the way people say them: "tomorrow", "next friday",
+//! "in 3 days" or "jan 15", and days gone by like "3 days ago".
+
+use crate::{tr, Date, TodoError};
+
+pub(super) const WEEKDAYS: [&str; 7] = ["monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday"];
+
+pub(super) const MONTHS: [&str; 12] = [
+    "january", "february", "march", "april", "may", "june",
+    "july", "august", "september", "october", "november", "december",
+];
+
+pub(crate) fn parse(input: &str, today: Date) -> Result<Date, TodoError> {
+    let input = input.trim().to_lowercase();
+    if let Ok(date) = Date::parse(&input) {
+        return Ok(date);
+    }
+    // 01/02 could be the first of February or the second of January
+    if input.contains('/') {
+        return Err(TodoError::Parse(tr!("ambiguous-date", value = input)));
+    }
+
+    let words: Vec<&str> = input.split_whitespace().collect();
+    let date = match words[..] {
+        ["today"] => Some(today),
+        ["tomorrow"] => Some(today.add_days(1)),
+        ["yesterday"] => Some(today.add_days(-1)),
+        ["next", "week"] => Some(today.add_days(7)),
+        ["next", "month"] => Some(today.add_months(1)),
+        ["next", day] | [day] if weekday(day).is_some() => weekday(day).map(|day| next_weekday(today, day)),
+        ["in", count, unit] => return in_time(count, unit, 1, today, &input),
+        [count, unit, "ago"] => return in_time(count, unit, -1, today, &input),
+        [first, second] => month_day(first, second, None, today),
+        [first, second, year] => year.parse().ok().and_then(|year| month_day(first, second, Some(year), today)),
+        _ => None,
+    };
+    date.ok_or_else(|| {
+        TodoError::Parse(tr!("invalid-natural-date", value = input))
+    })
+}
+
+// Monday is 0. Three letter abbreviations are accepted too.
+fn weekday(word: &str) -> Option<i64> {
+    WEEKDAYS.iter().position(|name| abbreviates(word, name)).map(|index| index as i64)
+}
+
+// January is 1. Three letter abbreviations are accepted too.
+fn month(word: &str) -> Option<u32> {
+    MONTHS.iter().position(|name| abbreviates(word, name)).map(|index| index as u32 + 1)
+}
+
+fn abbreviates(word: &str, name: &str) -> bool {
+    word.len() >= 3 && name.starts_with(word.trim_end_matches('.'))
+}
+
+// The first `weekday` after `today`, so "friday" on a Friday is a week away
+fn next_weekday(today: Date, weekday: i64) -> Date {
+    let current = today.weekday() as i64;
+    let ahead = (weekday - current).rem_euclid(7);
+    today.add_days(if ahead == 0 { 7 } else { ahead })
+}
+
+// "in 3 days", "in 2 weeks", "in 1 month", or with `sign` -1 "3 days ago".
+// A count taking the date past the years a date can have is an error of
+// its own rather than an unreadable date.
+fn in_time(count: &str, unit: &str, sign: i64, today: Date, input: &str) -> Result<Date, TodoError> {
+    let unreadable = || TodoError::Parse(tr!("invalid-natural-date", value = input));
+    let count: i64 = count.parse().map_err(|_| unreadable())?;
+    let date = match unit.trim_end_matches('s') {
+        "day" => count.checked_mul(sign).and_then(|days| today.checked_add_days(days)),
+        "week" => count.checked_mul(sign * 7).and_then(|days| today.checked_add_days(days)),
+        "month" => count.checked_mul(sign).and_then(|months| today.checked_add_months(months)),
+        _ => return Err(unreadable()),
+    };
+    date.ok_or_else(|| TodoError::Parse(tr!("date-out-of-range", value = input)))
+}
+
+// "jan 15" or "15 jan", in the next year that has not passed it yet unless
+// the year is given
+fn month_day(first: &str, second: &str, year: Option<i32>, today: Date) -> Option<Date> {
+    let (month, day) = match (month(first), month(second)) {
+        (Some(month), None) => (month, second.parse().ok()?),
+        (None, Some(month)) => (month, first.parse().ok()?),
+        _ => return None,
+    };
+    match year {
+        Some(year) => Date::new(year, month, day).ok(),
+        None => {
+            let date = Date::new(today.year, month, day).ok()?;
+            if date < today {
+                Date::new(today.year + 1, month, day).ok()
+            } else {
+                Some(date)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today() -> Date {
+        Date::new(2024, 6, 1).unwrap()
+    }
+
+    fn out_of_range(input: &str) -> bool {
+        matches!(parse(input, today()), Err(TodoError::Parse(message)) if message == tr!("date-out-of-range", value = input))
+    }
+
+    #[test]
+    fn counts_ahead() {
+        assert_eq!(parse("in 3 days", today()).unwrap(), Date::new(2024, 6, 4).unwrap());
+        assert_eq!(parse("in 2 weeks", today()).unwrap(), Date::new(2024, 6, 15).unwrap());
+        assert_eq!(parse("in 20 months", today()).unwrap(), Date::new(2026, 2, 1).unwrap());
+    }
+
+    #[test]
+    fn counts_ahead_past_year_9999_are_refused() {
+        assert!(out_of_range("in 9999999999999999 weeks"));
+        assert!(out_of_range("in 9223372036854775807 days"));
+        assert!(out_of_range("in 95707 months"));
+        assert_eq!(parse("in 95706 months", today()).unwrap(), Date::new(9999, 12, 1).unwrap());
+    }
+
+    #[test]
+    fn counts_back() {
+        assert_eq!(parse("3 days ago", today()).unwrap(), Date::new(2024, 5, 29).unwrap());
+        assert_eq!(parse("1 week ago", today()).unwrap(), Date::new(2024, 5, 25).unwrap());
+        assert_eq!(parse("2 months ago", today()).unwrap(), Date::new(2024, 4, 1).unwrap());
+    }
+
+    #[test]
+    fn counts_back_before_year_1_are_refused() {
+        assert!(out_of_range("3000000 days ago"));
+        assert!(out_of_range("9223372036854775807 weeks ago"));
+        assert!(out_of_range("24282 months ago"));
+        assert_eq!(parse("24281 months ago", today()).unwrap(), Date::new(1, 1, 1).unwrap());
+    }
+}
@@ -0,0 +1,103 @@
+//! What changed between two copies of a list, such as today's file and
+//! yesterday's backup. Tasks are matched by UUID, so it does not matter
+//! that one copy was renumbered or that the two gave an ID to different
+//! tasks.
+
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::{Task, TodoList};
+
+/// The tasks added, removed, completed, reopened or edited going from an
+/// older copy of a list to a newer one. Each list is in ID order.
+#[derive(Debug, Clone, Default)]
+pub struct Diff {
+    /// Tasks only in the newer copy.
+    pub added: Vec<Task>,
+    /// Tasks only in the older copy.
+    pub removed: Vec<Task>,
+    /// Tasks open in the older copy and completed in the newer one.
+    pub completed: Vec<Task>,
+    /// Tasks completed in the older copy and open in the newer one.
+    pub reopened: Vec<Task>,
+    /// Tasks changed in other ways, as they are in the newer copy, with the
+    /// names of the fields that changed. A task can be completed and edited
+    /// both.
+    pub edited: Vec<(Task, Vec<&'static str>)>,
+}
+
+impl Diff {
+    /// The changes from `old` to `new`.
+    pub fn new(old: &TodoList, new: &TodoList) -> Self {
+        let old_tasks: HashMap<Uuid, &Task> = old.tasks().iter().map(|task| (task.uuid, task)).collect();
+        let new_tasks: HashMap<Uuid, &Task> = new.tasks().iter().map(|task| (task.uuid, task)).collect();
+
+        let mut diff = Diff {
+            removed: old.tasks().iter().filter(|task| !new_tasks.contains_key(&task.uuid)).cloned().collect(),
+            ..Diff::default()
+        };
+        for task in new.tasks() {
+            let Some(before) = old_tasks.get(&task.uuid) else {
+                diff.added.push(task.clone());
+                continue;
+            };
+            match (before.completed, task.completed) {
+                (false, true) => diff.completed.push(task.clone()),
+                (true, false) => diff.reopened.push(task.clone()),
+                _ => {}
+            }
+            let fields = changed_fields(before, old, task, new);
+            if !fields.is_empty() {
+                diff.edited.push((task.clone(), fields));
+            }
+        }
+        diff
+    }
+
+    /// Whether the two copies hold the same tasks, the same way.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.completed.is_empty()
+            && self.reopened.is_empty()
+            && self.edited.is_empty()
+    }
+}
+
+// Subtasks and dependencies point at IDs, which can differ between copies,
+// so they are compared by the UUIDs of the tasks they point at
+fn changed_fields(old: &Task, old_list: &TodoList, new: &Task, new_list: &TodoList) -> Vec<&'static str> {
+    let uuid = |list: &TodoList, id: usize| list.task(id).map(|task| task.uuid);
+    let uuids = |list: &TodoList, ids: &[usize]| {
+        let mut uuids: Vec<Option<Uuid>> = ids.iter().map(|&id| uuid(list, id)).collect();
+        uuids.sort();
+        uuids
+    };
+
+    let mut fields = Vec::new();
+    for (name, changed) in [
+        ("description", old.description != new.description),
+        ("priority", old.priority != new.priority),
+        ("status", old.column != new.column && !old.completed && !new.completed),
+        ("due", old.due_date != new.due_date),
+        ("start", old.start_date != new.start_date),
+        ("tags", old.tags != new.tags),
+        ("recurrence", old.recurrence != new.recurrence),
+        ("parent", old.parent.map(|id| uuid(old_list, id)) != new.parent.map(|id| uuid(new_list, id))),
+        ("depends_on", uuids(old_list, &old.depends_on) != uuids(new_list, &new.depends_on)),
+        ("notes", old.notes != new.notes),
+        ("attachments", old.attachments != new.attachments),
+        ("assignee", old.assignee != new.assignee),
+        ("waiting", old.waiting != new.waiting),
+        ("estimate", old.estimate != new.estimate),
+        ("fields", old.fields != new.fields),
+        ("comments", old.comments != new.comments),
+        ("time_log", old.time_log != new.time_log),
+    ] {
+        if changed {
+            fields.push(name);
+        }
+    }
+    fields
+}
@@ -0,0 +1,115 @@
+//! A digest of the list to send by email, say once a week from cron: the
+//! open tasks that are overdue, those due in the coming days and those
+//! completed in the past ones. `Display` gives the text, and
+//! [`Digest::to_email`] the whole message, as an `.eml` file holds it or
+//! `sendmail -t` takes it:
+//!
+//! ```text
+//! From: todo@example.com
+//! To: me@example.com
+//! Subject: Todo digest: 1 overdue, 2 due soon, 3 completed
+//! Date: Wed, 14 Oct 2026 08:00:00 +0000
+//! MIME-Version: 1.0
+//! Content-Type: text/plain; charset=utf-8
+//! Content-Transfer-Encoding: 8bit
+//!
+//! Overdue
+//!   ID: 4, pay rent (due 2026-10-12)
+//! ...
+//! ```
+
+use std::fmt;
+
+use crate::{mail, tr, Date, Task, Timestamp, TodoError};
+
+/// The tasks a digest tells about, each list in date order.
+#[derive(Debug, Clone)]
+pub struct Digest {
+    /// How many days the digest looks ahead, and back.
+    pub days: i64,
+    /// Open tasks due before today.
+    pub overdue: Vec<Task>,
+    /// Open tasks due from today through the last of `days` days.
+    pub due: Vec<Task>,
+    /// Tasks completed in the last `days` days, today included.
+    pub completed: Vec<Task>,
+}
+
+impl Digest {
+    /// The digest of `tasks`, such as a list's along with its archive's,
+    /// looking `days` days ahead of `today` and back from it.
+    pub fn new<'a>(tasks: impl IntoIterator<Item = &'a Task>, today: Date, days: i64) -> Self {
+        let last_due = today.add_days(days - 1);
+        let first_completed = today.add_days(1 - days);
+        let mut digest = Digest { days, overdue: Vec::new(), due: Vec::new(), completed: Vec::new() };
+        for task in tasks {
+            if task.completed {
+                let completed_on = task.completed_at.map(|at| at.date());
+                if completed_on.is_some_and(|day| day >= first_completed && day <= today) {
+                    digest.completed.push(task.clone());
+                }
+                continue;
+            }
+            match task.due_date {
+                Some(due) if due < today => digest.overdue.push(task.clone()),
+                Some(due) if due <= last_due => digest.due.push(task.clone()),
+                _ => {}
+            }
+        }
+        digest.overdue.sort_by_key(|task| (task.due_date, task.id));
+        digest.due.sort_by_key(|task| (task.due_date, task.id));
+        digest.completed.sort_by_key(|task| (task.completed_at, task.id));
+        digest
+    }
+
+    /// Whether there is nothing to tell about.
+    pub fn is_empty(&self) -> bool {
+        self.overdue.is_empty() && self.due.is_empty() && self.completed.is_empty()
+    }
+
+    /// The subject line of the email, counting the tasks in each part.
+    pub fn subject(&self) -> String {
+        tr!(
+            "digest-subject",
+            overdue = self.overdue.len(),
+            due = self.due.len(),
+            completed = self.completed.len()
+        )
+    }
+
+    /// The digest as an email from `from` to `to`, sent at `now`, made as
+    /// [`mail::message`] makes one.
+    pub fn to_email(&self, from: Option<&str>, to: Option<&str>, now: Timestamp) -> Result<String, TodoError> {
+        mail::message(from, to, &self.subject(), &self.to_string(), now)
+    }
+}
+
+impl fmt::Display for Digest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "{}", tr!("digest-nothing", days = self.days));
+        }
+        let parts = [
+            (tr!("digest-overdue"), &self.overdue),
+            (tr!("digest-due", days = self.days), &self.due),
+            (tr!("digest-completed", days = self.days), &self.completed),
+        ];
+        let mut first = true;
+        for (heading, tasks) in parts.iter().filter(|(_, tasks)| !tasks.is_empty()) {
+            if !first {
+                writeln!(f)?;
+            }
+            first = false;
+            writeln!(f, "{}", heading)?;
+            for task in tasks.iter() {
+                let when = match (task.completed_at.filter(|_| task.completed), task.due_date) {
+                    (Some(at), _) => format!(" ({})", tr!("digest-completed-on", date = at.date().to_string())),
+                    (None, Some(due)) => format!(" ({})", tr!("list-due", date = due.to_string())),
+                    (None, None) => String::new(),
+                };
+                writeln!(f, "  ID: {}, {}{}", task.id, task.description, when)?;
+            }
+        }
+        Ok(())
+    }
+}
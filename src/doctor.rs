@@ -0,0 +1,167 @@
+//! Checking a list for records it cannot use as they are, and repairing
+//! them. Loading a JSON data file fails outright on a single damaged task,
+//! and other problems, such as two tasks sharing an ID, are not noticed at
+//! all: the second task simply hides the first.
+//!
+//! Repairs keep everything that can be kept. Tasks that cannot be read are
+//! set aside, as they were in the file, for the caller to quarantine; a
+//! repeated ID or UUID is replaced with a new one; and a parent or
+//! dependency on a task that is not in the list is dropped.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::{tr, Task, TodoError, TodoList, Uuid};
+
+/// Something wrong with a list.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Problem {
+    /// The task at this position in the file could not be read.
+    Malformed { index: usize, error: String },
+    /// A task had the same ID as one before it; repairing gives it `new_id`.
+    DuplicateId { id: usize, new_id: usize },
+    /// A task had the same UUID as one before it; repairing gives it a new
+    /// one.
+    DuplicateUuid { id: usize },
+    /// A task's parent is not in the list.
+    MissingParent { id: usize, parent: usize },
+    /// A task's parents lead back to the task itself.
+    ParentCycle { id: usize },
+    /// A task depends on one that is not in the list.
+    MissingDependency { id: usize, on: usize },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Problem::Malformed { index, error } => write!(f, "Task number {} in the file cannot be read: {}", index + 1, error),
+            Problem::DuplicateId { id, new_id } => {
+                write!(f, "Two tasks have ID {}; repairing gives the second ID {}", id, new_id)
+            }
+            Problem::DuplicateUuid { id } => write!(f, "Task {} has the same UUID as another task", id),
+            Problem::MissingParent { id, parent } => write!(f, "Task {} is a subtask of task {}, which is missing", id, parent),
+            Problem::ParentCycle { id } => write!(f, "Task {} is a subtask of itself, through its parents", id),
+            Problem::MissingDependency { id, on } => write!(f, "Task {} depends on task {}, which is missing", id, on),
+        }
+    }
+}
+
+/// What checking a list found, along with the list as it would be after
+/// repairing it.
+pub struct Checkup {
+    /// The problems found, in the order of the tasks they were found in.
+    pub problems: Vec<Problem>,
+    /// The records that could not be read as tasks, as they were in the file.
+    pub quarantined: Vec<Value>,
+    repaired: TodoList,
+}
+
+impl Checkup {
+    /// Whether nothing was wrong.
+    pub fn is_healthy(&self) -> bool {
+        self.problems.is_empty()
+    }
+
+    /// The list with every problem repaired and the quarantined records left
+    /// out.
+    pub fn into_repaired(self) -> TodoList {
+        self.repaired
+    }
+}
+
+/// Checks the JSON written by [`TodoList::save_tasks`] task by task, so one
+/// damaged task does not keep the others from being read. Only text that is
+/// not a list of tasks at all is an error.
+pub fn check_json(json: &str) -> Result<Checkup, TodoError> {
+    let value: Value = serde_json::from_str(json)?;
+    let Some(records) = value.get("tasks").and_then(Value::as_array) else {
+        return Err(TodoError::Parse(tr!("doctor-no-tasks")));
+    };
+
+    let mut problems = Vec::new();
+    let mut quarantined = Vec::new();
+    let mut tasks = Vec::new();
+    for (index, record) in records.iter().enumerate() {
+        match serde_json::from_value::<Task>(record.clone()) {
+            Ok(task) => tasks.push(task),
+            Err(e) => {
+                problems.push(Problem::Malformed { index, error: e.to_string() });
+                quarantined.push(record.clone());
+            }
+        }
+    }
+    let next_id = value.get("next_id").and_then(Value::as_u64).unwrap_or_default() as usize;
+
+    let mut checkup = check_tasks(tasks, next_id);
+    problems.append(&mut checkup.problems);
+    checkup.problems = problems;
+    checkup.quarantined = quarantined;
+    Ok(checkup)
+}
+
+/// Checks a list that was read without trouble for tasks that do not fit
+/// together, such as subtasks of missing tasks.
+pub fn check(todo_list: &TodoList) -> Checkup {
+    check_tasks(todo_list.tasks.clone(), todo_list.next_id)
+}
+
+fn check_tasks(mut tasks: Vec<Task>, next_id: usize) -> Checkup {
+    let mut problems = Vec::new();
+    let mut next_id = tasks.iter().map(|task| task.id + 1).max().unwrap_or(1).max(next_id);
+
+    let mut ids = HashSet::new();
+    let mut uuids = HashSet::new();
+    for task in &mut tasks {
+        if !ids.insert(task.id) {
+            problems.push(Problem::DuplicateId { id: task.id, new_id: next_id });
+            task.id = next_id;
+            ids.insert(next_id);
+            next_id += 1;
+        }
+        if !uuids.insert(task.uuid) {
+            problems.push(Problem::DuplicateUuid { id: task.id });
+            task.uuid = Uuid::new_v4();
+            uuids.insert(task.uuid);
+        }
+    }
+
+    for index in 0..tasks.len() {
+        let id = tasks[index].id;
+        if let Some(parent) = tasks[index].parent.filter(|parent| !ids.contains(parent)) {
+            problems.push(Problem::MissingParent { id, parent });
+            tasks[index].parent = None;
+        } else if leads_back(&tasks, index) {
+            problems.push(Problem::ParentCycle { id });
+            tasks[index].parent = None;
+        }
+        for on in tasks[index].depends_on.clone().into_iter().filter(|on| !ids.contains(on)) {
+            problems.push(Problem::MissingDependency { id, on });
+            tasks[index].depends_on.retain(|&other| other != on);
+        }
+    }
+
+    let mut repaired = TodoList::new();
+    repaired.tasks = tasks;
+    repaired.next_id = next_id;
+    repaired.finish_loading();
+    Checkup { problems, quarantined: Vec::new(), repaired }
+}
+
+// Whether following the parents of the task at `index` comes back to it
+fn leads_back(tasks: &[Task], index: usize) -> bool {
+    let id = tasks[index].id;
+    let mut seen = HashSet::new();
+    let mut parent = tasks[index].parent;
+    while let Some(parent_id) = parent {
+        if parent_id == id {
+            return true;
+        }
+        if !seen.insert(parent_id) {
+            return false;
+        }
+        parent = tasks.iter().find(|task| task.id == parent_id).and_then(|task| task.parent);
+    }
+    false
+}
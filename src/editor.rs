@@ -0,0 +1,39 @@
+//! Editing text in the user's `$EDITOR`.
+
+use std::env;
+use std::fs;
+use std::io;
+use std::process::{self, Command};
+
+use todo_list::{TodoError, TodoList};
+
+/// Opens `initial` in `$VISUAL` or `$EDITOR` (falling back to `vi`) and
+/// returns the text once the editor exits.
+pub fn edit_text(initial: &str, suffix: &str) -> io::Result<String> {
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    // Editors are often configured with arguments, like "code --wait"
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+
+    let path = env::temp_dir().join(format!("todo-{}{}", process::id(), suffix));
+    fs::write(&path, initial)?;
+
+    let status = Command::new(program).args(parts).arg(&path).status();
+    let text = fs::read_to_string(&path);
+    fs::remove_file(&path)?;
+
+    let status = status?;
+    if !status.success() {
+        return Err(io::Error::other(format!("{} exited with {}", program, status)));
+    }
+    text
+}
+
+/// Opens the notes of the task with the given ID in the editor and returns
+/// the edited text.
+pub fn edit_notes(todo_list: &TodoList, id: usize) -> Result<String, TodoError> {
+    let task = todo_list.task(id).ok_or(TodoError::NotFound(id))?;
+    Ok(edit_text(task.notes.as_deref().unwrap_or(""), ".md")?)
+}
@@ -0,0 +1,133 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+use crate::tr;
+
+/// Everything that can go wrong while working with a todo list.
+#[derive(Debug)]
+pub enum TodoError {
+    /// No task has the given ID.
+    NotFound(usize),
+    /// A task description was empty or only whitespace.
+    EmptyDescription,
+    /// A comment was empty or only whitespace.
+    EmptyComment,
+    /// The task is already completed.
+    AlreadyCompleted(usize),
+    /// The task is not completed, so it cannot be reopened.
+    NotCompleted(usize),
+    /// A tag was empty or contained spaces.
+    InvalidTag(String),
+    /// The task already carries the tag.
+    DuplicateTag { id: usize, tag: String },
+    /// The task does not carry the tag.
+    MissingTag { id: usize, tag: String },
+    /// A custom field had an invalid or reserved name, or no value.
+    InvalidField(String),
+    /// The task has no custom field by that name.
+    MissingField { id: usize, name: String },
+    /// The task has `count` attachments, none of them numbered `number`.
+    MissingAttachment { id: usize, number: usize, count: usize },
+    /// The task depends on the listed tasks, which are still open.
+    Blocked { id: usize, by: Vec<usize> },
+    /// Making task `id` depend on task `on` would have it wait on itself.
+    DependencyCycle { id: usize, on: usize },
+    /// Task `id` does not depend on task `on`.
+    MissingDependency { id: usize, on: usize },
+    /// There is no change to undo.
+    NothingToUndo,
+    /// There is no undone change to redo.
+    NothingToRedo,
+    /// The task's timer is already running.
+    TimerRunning(usize),
+    /// No task's timer is running.
+    NoTimer,
+    /// Input such as a date, priority or saved line could not be understood.
+    Parse(String),
+    /// Reading or writing a file failed.
+    Io(io::Error),
+    /// A JSON file could not be read or written.
+    Json(serde_json::Error),
+    /// The SQLite database could not be read or written.
+    #[cfg(feature = "sqlite")]
+    Sqlite(rusqlite::Error),
+    /// Syncing with a CalDAV server or a git remote failed.
+    Sync(String),
+    /// Another instance holds the lock on the list, kept in the given file.
+    Locked(PathBuf),
+    /// A plugin script could not be loaded or failed while it ran.
+    Plugin(String),
+    /// A notifier could not deliver a notification.
+    Notify(String),
+}
+
+impl fmt::Display for TodoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TodoError::NotFound(id) => write!(f, "{}", tr!("error-not-found", id = *id)),
+            TodoError::EmptyDescription => write!(f, "{}", tr!("error-empty-description")),
+            TodoError::EmptyComment => write!(f, "{}", tr!("error-empty-comment")),
+            TodoError::AlreadyCompleted(id) => write!(f, "{}", tr!("error-already-completed", id = *id)),
+            TodoError::NotCompleted(id) => write!(f, "{}", tr!("error-not-completed", id = *id)),
+            TodoError::InvalidTag(message) => write!(f, "{}", message),
+            TodoError::DuplicateTag { id, tag } => write!(f, "{}", tr!("error-duplicate-tag", id = *id, tag = tag.as_str())),
+            TodoError::MissingTag { id, tag } => write!(f, "{}", tr!("error-missing-tag", id = *id, tag = tag.as_str())),
+            TodoError::InvalidField(message) => write!(f, "{}", message),
+            TodoError::MissingField { id, name } => write!(f, "{}", tr!("error-missing-field", id = *id, name = name.as_str())),
+            TodoError::MissingAttachment { id, number, count } => {
+                write!(f, "{}", tr!("error-missing-attachment", id = *id, number = *number, count = *count))
+            }
+            TodoError::Blocked { id, by } => {
+                let ids: Vec<String> = by.iter().map(|id| id.to_string()).collect();
+                write!(f, "{}", tr!("error-blocked", id = *id, count = ids.len(), ids = ids.join(", ")))
+            }
+            TodoError::DependencyCycle { id, on } => write!(f, "{}", tr!("error-dependency-cycle", id = *id, on = *on)),
+            TodoError::MissingDependency { id, on } => write!(f, "{}", tr!("error-missing-dependency", id = *id, on = *on)),
+            TodoError::NothingToUndo => write!(f, "{}", tr!("error-nothing-to-undo")),
+            TodoError::NothingToRedo => write!(f, "{}", tr!("error-nothing-to-redo")),
+            TodoError::TimerRunning(id) => write!(f, "{}", tr!("error-timer-running", id = *id)),
+            TodoError::NoTimer => write!(f, "{}", tr!("error-no-timer")),
+            TodoError::Parse(message) => write!(f, "{}", message),
+            TodoError::Io(e) => write!(f, "{}", tr!("error-io", error = e.to_string())),
+            TodoError::Json(e) => write!(f, "{}", tr!("error-json", error = e.to_string())),
+            #[cfg(feature = "sqlite")]
+            TodoError::Sqlite(e) => write!(f, "{}", tr!("error-sqlite", error = e.to_string())),
+            TodoError::Sync(message) => write!(f, "{}", message),
+            TodoError::Locked(path) => write!(f, "{}", tr!("error-locked", path = path.display().to_string())),
+            TodoError::Plugin(message) => write!(f, "{}", message),
+            TodoError::Notify(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for TodoError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TodoError::Io(e) => Some(e),
+            TodoError::Json(e) => Some(e),
+            #[cfg(feature = "sqlite")]
+            TodoError::Sqlite(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for TodoError {
+    fn from(e: io::Error) -> Self {
+        TodoError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for TodoError {
+    fn from(e: serde_json::Error) -> Self {
+        TodoError::Json(e)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl From<rusqlite::Error> for TodoError {
+    fn from(e: rusqlite::Error) -> Self {
+        TodoError::Sqlite(e)
+    }
+}
@@ -0,0 +1,74 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{tr, TodoError};
+
+/// How much effort a task is expected to take, either as a length of time
+/// or in story points. Serialized as written, like `2h`, `90m` or `3pt`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum Estimate {
+    Minutes(u32),
+    Points(u32),
+}
+
+impl Estimate {
+    /// Parses a time in hours or minutes, like `2h`, `1.5h` or `90m`, or
+    /// story points, like `3pt`. `none` or a blank input means no estimate.
+    pub fn parse(input: &str) -> Result<Option<Self>, TodoError> {
+        let input = input.trim().to_lowercase();
+        let error = || TodoError::Parse(tr!("invalid-estimate", value = input.as_str()));
+        if input.is_empty() || input == "none" {
+            return Ok(None);
+        }
+
+        let estimate = if let Some(points) = input.strip_suffix("pt").or_else(|| input.strip_suffix('p')) {
+            Estimate::Points(points.trim().parse().map_err(|_| error())?)
+        } else if let Some(hours) = input.strip_suffix('h') {
+            let hours: f64 = hours.trim().parse().map_err(|_| error())?;
+            if !hours.is_finite() || hours < 0.0 {
+                return Err(error());
+            }
+            Estimate::Minutes((hours * 60.0).round() as u32)
+        } else if let Some(minutes) = input.strip_suffix('m') {
+            Estimate::Minutes(minutes.trim().parse().map_err(|_| error())?)
+        } else {
+            return Err(error());
+        };
+        Ok(Some(estimate))
+    }
+}
+
+impl FromStr for Estimate {
+    type Err = TodoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Estimate::parse(s)?.ok_or_else(|| TodoError::Parse(tr!("estimate-required")))
+    }
+}
+
+impl fmt::Display for Estimate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Estimate::Minutes(minutes) if minutes % 60 == 0 && *minutes > 0 => write!(f, "{}h", minutes / 60),
+            Estimate::Minutes(minutes) => write!(f, "{}m", minutes),
+            Estimate::Points(points) => write!(f, "{}pt", points),
+        }
+    }
+}
+
+impl From<Estimate> for String {
+    fn from(estimate: Estimate) -> Self {
+        estimate.to_string()
+    }
+}
+
+impl TryFrom<String> for Estimate {
+    type Error = TodoError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
@@ -0,0 +1,430 @@
+//! Filter expressions for picking out tasks, like
+//! `tag:work and due<7d and not completed`.
+//!
+//! A filter is made of terms joined with `and`, `or` and `not`, with
+//! parentheses for grouping; terms written one after another must all
+//! match, and `and` binds tighter than `or`. A term is one of:
+//!
+//! - `tag:work`, for tasks carrying a tag;
+//! - `@phone`, for tasks with that context in their description;
+//! - `priority:high`, or `priority>=medium` for medium and high;
+//! - `due`, `start`, `followup`, `created` or `done` compared to a date with `:`, `<`, `<=`, `>`
+//!   or `>=`, like `due<7d` for due within the next week, `created>-2w` for
+//!   added in the last two weeks or `due:2024-06-01`. `due:none` and
+//!   `due:any` pick out tasks without and with a due date, and likewise
+//!   for the others;
+//! - `assignee:alice`, or `assignee:none` and `assignee:any` for tasks
+//!   assigned to no one and to someone;
+//! - a custom field, like `customer:acme`, ignoring case; `customer:any`
+//!   and `customer:none` pick out tasks with and without the field;
+//! - `status:in-progress`, for tasks in a column of the board: `todo`,
+//!   `in-progress`, `blocked` or `done`;
+//! - a status: `open`, `completed`, `overdue`, `snoozed`, `deferred`
+//!   (not to be started yet), `waiting` (on someone else) or `recurring`;
+//! - any other word, or text in double quotes, for tasks whose description
+//!   or notes contain it, ignoring case and accents.
+//!
+//! Dates are written as a number of days or weeks from today, like `3d`,
+//! `-1w` or `0d`, as `today`, `tomorrow` or `yesterday`, or as YYYY-MM-DD.
+//! Other ways of writing a date, like `friday`, are worked out from the day
+//! the filter is parsed.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{text, tr, Column, Date, Priority, Task, TodoError};
+
+/// A parsed filter expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Filter {
+    And(Box<Filter>, Box<Filter>),
+    Or(Box<Filter>, Box<Filter>),
+    Not(Box<Filter>),
+    Tag(String),
+    /// A context, in lowercase and without the `@`.
+    Context(String),
+    Priority(Comparison, Priority),
+    Date(DateField, Comparison, DateValue),
+    /// Whether the date is set at all.
+    HasDate(DateField, bool),
+    Status(Status),
+    /// In this column of the board.
+    Column(Column),
+    /// Assigned to this person, ignoring case, or to no one when None.
+    Assignee(Option<String>),
+    /// A custom field with this value, ignoring case, or with any value when
+    /// None.
+    Field { name: String, value: Option<String> },
+    /// Text in the description or notes, in lowercase and without accents,
+    /// which are ignored.
+    Text(String),
+}
+
+/// How a task's value is compared with the one in the filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Equal,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+/// The dates of a task a filter can look at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateField {
+    Due,
+    /// When the task can be started.
+    Start,
+    /// When to follow up on a waiting task.
+    FollowUp,
+    Created,
+    /// When the task was completed.
+    Done,
+}
+
+/// A date in a filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateValue {
+    /// This many days after the day the filter is matched on, or before it
+    /// when negative.
+    FromToday(i64),
+    On(Date),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Open,
+    Completed,
+    Overdue,
+    Snoozed,
+    /// With a start date still to come.
+    Deferred,
+    /// Waiting on someone else.
+    Waiting,
+    Recurring,
+}
+
+impl Filter {
+    /// Parses a filter expression, as described in the module docs.
+    pub fn parse(input: &str) -> Result<Self, TodoError> {
+        let tokens = tokenize(input)?;
+        let mut parser = Parser { tokens: &tokens, position: 0 };
+        let filter = parser.or()?;
+        match parser.peek() {
+            None => Ok(filter),
+            Some(Token::Close) => Err(TodoError::Parse(tr!("filter-unmatched-close"))),
+            Some(token) => Err(TodoError::Parse(tr!("filter-unexpected", token = token.to_string()))),
+        }
+    }
+
+    /// Whether `task` matches, with relative dates counted from `today`.
+    pub fn matches(&self, task: &Task, today: Date) -> bool {
+        match self {
+            Filter::And(a, b) => a.matches(task, today) && b.matches(task, today),
+            Filter::Or(a, b) => a.matches(task, today) || b.matches(task, today),
+            Filter::Not(filter) => !filter.matches(task, today),
+            Filter::Tag(tag) => task.has_tag(tag),
+            Filter::Context(context) => task.has_context(context),
+            // More important counts as greater
+            Filter::Priority(comparison, priority) => comparison.holds(priority.cmp(&task.priority)),
+            Filter::Date(field, comparison, value) => {
+                let date = value.resolve(today);
+                field.of(task).is_some_and(|of| comparison.holds(of.cmp(&date)))
+            }
+            Filter::HasDate(field, set) => field.of(task).is_some() == *set,
+            Filter::Status(status) => match status {
+                Status::Open => !task.completed,
+                Status::Completed => task.completed,
+                Status::Overdue => task.is_overdue(today),
+                Status::Snoozed => task.is_snoozed(today),
+                Status::Deferred => task.is_deferred(today),
+                Status::Waiting => task.is_waiting(),
+                Status::Recurring => task.recurrence.is_some(),
+            },
+            Filter::Column(column) => task.status() == *column,
+            Filter::Assignee(Some(name)) => task.is_assigned_to(name),
+            Filter::Assignee(None) => task.assignee.is_none(),
+            Filter::Field { name, value } => task.fields.get(name).is_some_and(|field| {
+                value.as_ref().is_none_or(|value| field.to_lowercase() == value.to_lowercase())
+            }),
+            Filter::Text(text) => {
+                let contains = |field: &str| text::contains(&text::fold(field), text);
+                contains(&task.description) || task.notes.as_deref().is_some_and(contains)
+            }
+        }
+    }
+}
+
+impl FromStr for Filter {
+    type Err = TodoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Filter::parse(s)
+    }
+}
+
+impl Comparison {
+    // `ordering` is the task's value compared with the filter's
+    fn holds(self, ordering: Ordering) -> bool {
+        match self {
+            Comparison::Equal => ordering.is_eq(),
+            Comparison::Less => ordering.is_lt(),
+            Comparison::LessOrEqual => ordering.is_le(),
+            Comparison::Greater => ordering.is_gt(),
+            Comparison::GreaterOrEqual => ordering.is_ge(),
+        }
+    }
+}
+
+impl DateField {
+    fn of(self, task: &Task) -> Option<Date> {
+        match self {
+            DateField::Due => task.due_date,
+            DateField::Start => task.start_date,
+            DateField::FollowUp => task.waiting.as_ref().and_then(|waiting| waiting.follow_up),
+            DateField::Created => task.created_at.map(|created| created.date()),
+            DateField::Done => task.completed_at.map(|completed| completed.date()),
+        }
+    }
+}
+
+impl DateValue {
+    fn parse(input: &str) -> Result<Self, TodoError> {
+        let input = input.to_lowercase();
+        let days = match input.as_str() {
+            "today" => Some(0),
+            "tomorrow" => Some(1),
+            "yesterday" => Some(-1),
+            _ => span(&input),
+        };
+        match days {
+            Some(days) => Ok(DateValue::FromToday(days)),
+            None => Date::parse_natural(&input, Date::today()).map(DateValue::On),
+        }
+    }
+
+    fn resolve(self, today: Date) -> Date {
+        match self {
+            DateValue::FromToday(days) => today.add_days(days),
+            DateValue::On(date) => date,
+        }
+    }
+}
+
+// A number of days or weeks such as `3d`, `-2w` or `+1w`
+fn span(input: &str) -> Option<i64> {
+    let (count, days) = match (input.strip_suffix('d'), input.strip_suffix('w')) {
+        (Some(count), _) => (count, 1),
+        (_, Some(count)) => (count, 7),
+        _ => return None,
+    };
+    let count: i64 = count.strip_prefix('+').unwrap_or(count).parse().ok()?;
+    Some(count * days)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Open,
+    Close,
+    And,
+    Or,
+    Not,
+    Word(String),
+    // Text in double quotes, never taken as a field or keyword
+    Quoted(String),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::Open => write!(f, "'('"),
+            Token::Close => write!(f, "')'"),
+            Token::And => write!(f, "'and'"),
+            Token::Or => write!(f, "'or'"),
+            Token::Not => write!(f, "'not'"),
+            Token::Word(word) => write!(f, "'{}'", word),
+            Token::Quoted(text) => write!(f, "\"{}\"", text),
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, TodoError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' | ')' => {
+                chars.next();
+                tokens.push(if c == '(' { Token::Open } else { Token::Close });
+            }
+            '"' => {
+                chars.next();
+                let text: String = chars.by_ref().take_while(|&c| c != '"').collect();
+                tokens.push(Token::Quoted(text));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek().filter(|c| !c.is_whitespace() && !matches!(c, '(' | ')' | '"')) {
+                    word.push(c);
+                    chars.next();
+                }
+                tokens.push(match word.to_lowercase().as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    _ => Token::Word(word),
+                });
+            }
+        }
+    }
+    if input.matches('"').count() % 2 == 1 {
+        return Err(TodoError::Parse(tr!("filter-unclosed-quote")));
+    }
+    Ok(tokens)
+}
+
+// A recursive descent parser, one function per level of precedence
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.position);
+        self.position += 1;
+        token
+    }
+
+    fn or(&mut self) -> Result<Filter, TodoError> {
+        let mut filter = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            filter = Filter::Or(Box::new(filter), Box::new(self.and()?));
+        }
+        Ok(filter)
+    }
+
+    // `and` may be left out between terms
+    fn and(&mut self) -> Result<Filter, TodoError> {
+        let mut filter = self.not()?;
+        loop {
+            match self.peek() {
+                Some(Token::And) => {
+                    self.next();
+                }
+                Some(Token::Open | Token::Not | Token::Word(_) | Token::Quoted(_)) => {}
+                _ => return Ok(filter),
+            }
+            filter = Filter::And(Box::new(filter), Box::new(self.not()?));
+        }
+    }
+
+    fn not(&mut self) -> Result<Filter, TodoError> {
+        if self.peek() == Some(&Token::Not) {
+            self.next();
+            return Ok(Filter::Not(Box::new(self.not()?)));
+        }
+        self.term()
+    }
+
+    fn term(&mut self) -> Result<Filter, TodoError> {
+        match self.next().cloned() {
+            Some(Token::Open) => {
+                let filter = self.or()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(filter),
+                    _ => Err(TodoError::Parse(tr!("filter-missing-close"))),
+                }
+            }
+            Some(Token::Word(word)) => term(&word),
+            Some(Token::Quoted(text)) => Ok(Filter::Text(text::fold(&text))),
+            Some(token) => Err(TodoError::Parse(tr!("filter-expected-term", token = token.to_string()))),
+            None => Err(TodoError::Parse(tr!("filter-ends-early"))),
+        }
+    }
+}
+
+fn term(word: &str) -> Result<Filter, TodoError> {
+    if let Some(context) = word.strip_prefix('@').filter(|context| !context.is_empty()) {
+        return Ok(Filter::Context(context.to_lowercase()));
+    }
+    let Some((field, comparison, value)) = split_comparison(word) else {
+        return Ok(match word.to_lowercase().as_str() {
+            "open" | "pending" => Filter::Status(Status::Open),
+            "completed" | "done" => Filter::Status(Status::Completed),
+            "overdue" => Filter::Status(Status::Overdue),
+            "snoozed" => Filter::Status(Status::Snoozed),
+            "deferred" => Filter::Status(Status::Deferred),
+            "waiting" => Filter::Status(Status::Waiting),
+            "recurring" => Filter::Status(Status::Recurring),
+            text => Filter::Text(text::fold(text)),
+        });
+    };
+
+    let date_field = match field.as_str() {
+        "tag" if comparison == Comparison::Equal => return Ok(Filter::Tag(value.to_lowercase())),
+        "priority" | "pri" => return Ok(Filter::Priority(comparison, Priority::parse(value)?)),
+        "status" if comparison == Comparison::Equal => return Ok(Filter::Column(Column::parse(value)?)),
+        "due" => DateField::Due,
+        "start" => DateField::Start,
+        "followup" => DateField::FollowUp,
+        "created" => DateField::Created,
+        "done" | "completed" => DateField::Done,
+        "assignee" if comparison == Comparison::Equal => {
+            return Ok(match value.to_lowercase().as_str() {
+                "none" => Filter::Assignee(None),
+                "any" => Filter::Not(Box::new(Filter::Assignee(None))),
+                _ => Filter::Assignee(Some(value.to_string())),
+            })
+        }
+        "tag" | "assignee" | "status" => {
+            return Err(TodoError::Parse(tr!("filter-colon-only", field = field.to_string(), value = value.to_string())))
+        }
+        name if comparison == Comparison::Equal => {
+            let filter = Filter::Field { name: name.to_string(), value: None };
+            return Ok(match value.to_lowercase().as_str() {
+                "any" => filter,
+                "none" => Filter::Not(Box::new(filter)),
+                _ => Filter::Field { name: name.to_string(), value: Some(value.to_string()) },
+            });
+        }
+        name => {
+            return Err(TodoError::Parse(tr!("filter-field-colon-only", field = name.to_string(), value = value.to_string())))
+        }
+    };
+    match value.to_lowercase().as_str() {
+        "none" if comparison == Comparison::Equal => Ok(Filter::HasDate(date_field, false)),
+        "any" if comparison == Comparison::Equal => Ok(Filter::HasDate(date_field, true)),
+        _ => Ok(Filter::Date(date_field, comparison, DateValue::parse(value)?)),
+    }
+}
+
+// Splits `due<=7d` into its field, comparison and value, if it has them
+fn split_comparison(word: &str) -> Option<(String, Comparison, &str)> {
+    let at = word.find([':', '<', '>', '='])?;
+    let field = word[..at].to_lowercase();
+    let named = field.starts_with(|c: char| c.is_ascii_lowercase())
+        && field.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+    if !named {
+        return None;
+    }
+    let rest = &word[at..];
+    let (comparison, length) = match rest.as_bytes() {
+        [b'<', b'=', ..] => (Comparison::LessOrEqual, 2),
+        [b'>', b'=', ..] => (Comparison::GreaterOrEqual, 2),
+        [b'<', ..] => (Comparison::Less, 1),
+        [b'>', ..] => (Comparison::Greater, 1),
+        _ => (Comparison::Equal, 1),
+    };
+    Some((field, comparison, &rest[length..]))
+}
@@ -0,0 +1,275 @@
+//! Keeping the data file in a git repository. With `--git` every save is
+//! committed with a message saying what changed, and `todo sync --git`
+//! pulls and pushes the list like any other repository. Conflicting edits
+//! are reported as the tasks changed on both sides rather than as lines of
+//! JSON.
+
+use std::cell::Cell;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use todo_list::merge::{self, MergeSummary};
+use todo_list::storage::{Page, Storage};
+use todo_list::{tr, SortOrder, Task, TodoError, TodoList};
+
+/// Storage that commits the data file to git after every write, creating
+/// the repository on the first one if the file is not in one yet.
+pub struct GitStorage {
+    inner: Box<dyn Storage>,
+    path: PathBuf,
+    // How many of the list's changes have been described in a commit
+    described: Cell<usize>,
+}
+
+impl GitStorage {
+    pub fn new(inner: Box<dyn Storage>, path: impl Into<PathBuf>) -> Self {
+        GitStorage { inner, path: path.into(), described: Cell::new(0) }
+    }
+
+    // Describes the changes made since the last commit; a list only
+    // remembers the changes it can still undo
+    fn message(&self, todo_list: &TodoList) -> String {
+        let labels = todo_list.change_labels();
+        let new = labels.get(self.described.get()..).unwrap_or_default();
+        self.described.set(labels.len());
+        match new {
+            [] => format!("Update {}", file_name(&self.path)),
+            [label] => capitalize(label),
+            [first, rest @ ..] => {
+                let more = if rest.len() == 1 { "change" } else { "changes" };
+                let mut message = format!("{} and {} more {}\n\n", capitalize(first), rest.len(), more);
+                for label in new {
+                    message.push_str(&format!("- {}\n", capitalize(label)));
+                }
+                message
+            }
+        }
+    }
+}
+
+impl Storage for GitStorage {
+    fn load(&self) -> Result<TodoList, TodoError> {
+        self.inner.load()
+    }
+
+    fn save(&self, todo_list: &TodoList) -> Result<(), TodoError> {
+        self.inner.save(todo_list)?;
+        commit_or_warn(&self.path, &self.message(todo_list));
+        Ok(())
+    }
+
+    fn append(&self, task: &Task) -> Result<(), TodoError> {
+        self.inner.append(task)?;
+        commit_or_warn(&self.path, &format!("Add task {}", task.id));
+        Ok(())
+    }
+
+    fn load_page(
+        &self,
+        sort: SortOrder,
+        offset: usize,
+        limit: usize,
+        keep: &dyn Fn(&Task) -> bool,
+    ) -> Result<Option<Page>, TodoError> {
+        self.inner.load_page(sort, offset, limit, keep)
+    }
+}
+
+// The tasks are saved either way, so a failed commit does not fail the save
+fn commit_or_warn(path: &Path, message: &str) {
+    if let Err(e) = commit(path, message) {
+        let message = tr!("git-commit-failed", path = path.display().to_string(), error = e.to_string());
+        eprintln!("{}", tr!("warning", message = message));
+    }
+}
+
+/// Commits the file at `path`, and nothing else, if it has changed since
+/// the last commit. Returns whether there was anything to commit.
+pub fn commit(path: &Path, message: &str) -> Result<bool, TodoError> {
+    if !path.exists() {
+        return Ok(false);
+    }
+    let dir = dir(path);
+    let name = format!("./{}", file_name(path));
+    if !git(dir, &["rev-parse", "--is-inside-work-tree"])?.status.success() {
+        run(dir, &["init", "--quiet"])?;
+    }
+    run(dir, &["add", "--", &name])?;
+    // Exits with 0 when nothing is staged
+    if git(dir, &["diff", "--cached", "--quiet", "--", &name])?.status.success() {
+        return Ok(false);
+    }
+    run(dir, &["commit", "--quiet", "--message", message, "--", &name])?;
+    Ok(true)
+}
+
+/// What `todo sync --git` did.
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    /// How many commits came from the remote.
+    pub pulled: usize,
+    /// How many commits went to the remote.
+    pub pushed: usize,
+    /// Tasks changed on both sides. While there are any the remote copy has
+    /// not been pulled, unless it was merged.
+    pub conflicts: Vec<Conflict>,
+    /// What merging in the remote copy changed, when the two had diverged in
+    /// a way git could not settle alone.
+    pub merged: Option<MergeSummary>,
+}
+
+/// A task that was changed differently here and in the remote copy.
+#[derive(Debug)]
+pub struct Conflict {
+    pub id: usize,
+    pub description: String,
+    pub kind: ConflictKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictKind {
+    /// Changed in both copies.
+    Changed,
+    /// Removed here but changed in the remote copy.
+    RemovedHere,
+    /// Changed here but removed in the remote copy.
+    RemovedThere,
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let what = match self.kind {
+            ConflictKind::Changed => "was changed both here and in the remote copy",
+            ConflictKind::RemovedHere => "was removed here but changed in the remote copy",
+            ConflictKind::RemovedThere => "was changed here but removed in the remote copy",
+        };
+        write!(f, "Task {} ({}) {}", self.id, self.description, what)
+    }
+}
+
+/// Commits any changes to the data file, then pulls from and pushes to the
+/// upstream branch of its repository. New remote commits are rebased onto
+/// local ones where git can do so cleanly. Where it cannot, the tasks
+/// changed on both sides are reported and nothing is pulled, unless `merge`
+/// is set, in which case the remote copy is merged in the way `todo merge`
+/// does and the result saved through `storage`. Only the JSON format can be
+/// compared task by task.
+pub fn sync(todo_list: &mut TodoList, storage: &dyn Storage, path: &Path, merge: bool) -> Result<SyncReport, TodoError> {
+    let dir = dir(path);
+    let mut report = SyncReport::default();
+    commit(path, &format!("Update {}", file_name(path)))?;
+
+    if !git(dir, &["rev-parse", "--abbrev-ref", "@{upstream}"])?.status.success() {
+        return Err(TodoError::Sync(format!(
+            "{} has no upstream branch to sync with; add a remote and push once with git push --set-upstream",
+            dir.display()
+        )));
+    }
+    run(dir, &["fetch", "--quiet"])?;
+    let ahead = count(dir, "@{upstream}..HEAD")?;
+    report.pulled = count(dir, "HEAD..@{upstream}")?;
+
+    if report.pulled > 0 && ahead == 0 {
+        run(dir, &["merge", "--quiet", "--ff-only", "@{upstream}"])?;
+    } else if report.pulled > 0 && !git(dir, &["rebase", "--quiet", "--autostash", "@{upstream}"])?.status.success() {
+        run(dir, &["rebase", "--abort"])?;
+        let name = format!("./{}", file_name(path));
+        let base = run(dir, &["merge-base", "HEAD", "@{upstream}"])?;
+        let theirs = show(dir, "@{upstream}", &name)?;
+        report.conflicts = conflicts(&show(dir, &base, &name)?, todo_list, &theirs);
+        if !report.conflicts.is_empty() && !merge {
+            report.pulled = 0;
+            return Ok(report);
+        }
+
+        // Git's tree is kept as it is here, and the tasks merged over it
+        run(dir, &["merge", "--quiet", "--no-edit", "--strategy", "ours", "@{upstream}"])?;
+        let summary = merge::merge(todo_list, &theirs);
+        if !summary.added.is_empty() || !summary.updated.is_empty() {
+            storage.save(todo_list)?;
+        }
+        report.merged = Some(summary);
+    }
+
+    report.pushed = count(dir, "@{upstream}..HEAD")?;
+    if report.pushed > 0 {
+        run(dir, &["push", "--quiet"])?;
+    }
+    Ok(report)
+}
+
+// Finds the tasks each side changed from `base` in a different way
+fn conflicts(base: &TodoList, ours: &TodoList, theirs: &TodoList) -> Vec<Conflict> {
+    let mut conflicts = Vec::new();
+    for task in ours.tasks() {
+        let before = base.task_by_uuid(task.uuid);
+        let other = theirs.task_by_uuid(task.uuid);
+        if before != Some(task) && before != other && other != Some(task) {
+            let kind = if other.is_some() { ConflictKind::Changed } else { ConflictKind::RemovedThere };
+            conflicts.push(Conflict { id: task.id, description: task.description.clone(), kind });
+        }
+    }
+    for task in theirs.tasks() {
+        let before = base.task_by_uuid(task.uuid);
+        if ours.task_by_uuid(task.uuid).is_none() && before.is_some() && before != Some(task) {
+            let id = before.map_or(task.id, |before| before.id);
+            conflicts.push(Conflict { id, description: task.description.clone(), kind: ConflictKind::RemovedHere });
+        }
+    }
+    conflicts
+}
+
+// The list as it was in commit `rev`, empty if the file was not there yet
+fn show(dir: &Path, rev: &str, name: &str) -> Result<TodoList, TodoError> {
+    let output = git(dir, &["show", &format!("{}:{}", rev, name)])?;
+    if !output.status.success() {
+        return Ok(TodoList::new());
+    }
+    TodoList::from_json(&String::from_utf8_lossy(&output.stdout))
+}
+
+// The number of commits in a range like `HEAD..@{upstream}`
+fn count(dir: &Path, range: &str) -> Result<usize, TodoError> {
+    let count = run(dir, &["rev-list", "--count", range])?;
+    count.parse().map_err(|_| TodoError::Sync(tr!("git-unexpected-count", output = count.as_str())))
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<Output, TodoError> {
+    Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|e| TodoError::Sync(tr!("git-not-run", error = e.to_string())))
+}
+
+// Runs git and gives what it printed, or what it complained about as an
+// error if it failed
+fn run(dir: &Path, args: &[&str]) -> Result<String, TodoError> {
+    let output = git(dir, args)?;
+    if !output.status.success() {
+        let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(TodoError::Sync(tr!("git-failed", command = args[0], error = message)));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn dir(path: &Path) -> &Path {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().map_or_else(|| path.display().to_string(), |name| name.to_string_lossy().into_owned())
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,115 @@
+//! Undo and redo for changes made to a `TodoList`.
+
+use std::collections::VecDeque;
+
+use tracing::info;
+
+use crate::Task;
+
+/// How many changes are remembered when no other limit is set.
+pub const DEFAULT_HISTORY_LIMIT: usize = 50;
+
+// One change to the task list. `before` and `after` are the task at `index`
+// on either side of the change; `None` means the task did not exist there,
+// so an add has no `before` and a remove has no `after`.
+#[derive(Debug, Clone)]
+pub(crate) struct Change {
+    pub(crate) index: usize,
+    pub(crate) before: Option<Task>,
+    pub(crate) after: Option<Task>,
+}
+
+impl Change {
+    // Puts `to` in place of `from` at this change's index
+    fn apply(&self, tasks: &mut Vec<Task>, from: &Option<Task>, to: &Option<Task>) {
+        match (from, to) {
+            (None, Some(task)) => tasks.insert(self.index, task.clone()),
+            (Some(_), None) => {
+                tasks.remove(self.index);
+            }
+            (Some(_), Some(task)) => tasks[self.index] = task.clone(),
+            (None, None) => {}
+        }
+    }
+}
+
+// Changes made by a single operation, undone and redone together
+#[derive(Debug, Clone)]
+struct Entry {
+    label: String,
+    changes: Vec<Change>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct History {
+    undo: VecDeque<Entry>,
+    redo: Vec<Entry>,
+    limit: usize,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        History {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            limit: DEFAULT_HISTORY_LIMIT,
+        }
+    }
+}
+
+impl History {
+    // Remembers a new operation; anything that could have been redone is lost
+    pub(crate) fn record(&mut self, label: String, changes: Vec<Change>) {
+        if changes.is_empty() {
+            return;
+        }
+        info!(change = %label, tasks = changes.len(), "changed");
+        self.redo.clear();
+        self.undo.push_back(Entry { label, changes });
+        self.trim();
+    }
+
+    // The labels of the operations that can be undone, oldest first
+    pub(crate) fn labels(&self) -> impl Iterator<Item = &str> {
+        self.undo.iter().map(|entry| entry.label.as_str())
+    }
+
+    pub(crate) fn set_limit(&mut self, limit: usize) {
+        self.limit = limit;
+        self.trim();
+    }
+
+    // Forgets everything, for changes whose effects cannot be reverted
+    pub(crate) fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    pub(crate) fn undo(&mut self, tasks: &mut Vec<Task>) -> Option<String> {
+        let entry = self.undo.pop_back()?;
+        for change in entry.changes.iter().rev() {
+            change.apply(tasks, &change.after, &change.before);
+        }
+        info!(change = %entry.label, tasks = entry.changes.len(), "undone");
+        let label = entry.label.clone();
+        self.redo.push(entry);
+        Some(label)
+    }
+
+    pub(crate) fn redo(&mut self, tasks: &mut Vec<Task>) -> Option<String> {
+        let entry = self.redo.pop()?;
+        for change in &entry.changes {
+            change.apply(tasks, &change.before, &change.after);
+        }
+        info!(change = %entry.label, tasks = entry.changes.len(), "redone");
+        let label = entry.label.clone();
+        self.undo.push_back(entry);
+        Some(label)
+    }
+
+    fn trim(&mut self) {
+        while self.undo.len() > self.limit {
+            self.undo.pop_front();
+        }
+    }
+}
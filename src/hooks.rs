@@ -0,0 +1,173 @@
+//! Running the user's own programs when tasks change, for things like
+//! keeping a journal or posting to a chat. Hooks are executable files in
+//! the hooks directory named after the event they are for, or starting with
+//! its name and a dot, like `on-complete.slack`:
+//!
+//! - `on-add` runs for each new task, with the task as JSON on stdin;
+//! - `on-complete` runs for each task that was completed, likewise;
+//! - `on-save` runs once after the list is saved, with a JSON array of the
+//!   tasks that were added or changed.
+//!
+//! Hooks run after the change is saved, in name order, with `TODO_HOOK` set
+//! to the event and `TODO_FILE` to the data file. A hook that fails is
+//! warned about; the change is kept either way.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use todo_list::storage::{Page, Storage};
+use todo_list::{tr, SortOrder, Task, TodoError, TodoList, Uuid};
+
+const EVENTS: [&str; 3] = ["on-add", "on-complete", "on-save"];
+
+/// Storage that runs hooks after every write. The tasks as they were last
+/// loaded are remembered, to tell which a save added or completed.
+pub struct HookStorage {
+    inner: Box<dyn Storage>,
+    path: PathBuf,
+    hooks: HashMap<&'static str, Vec<PathBuf>>,
+    loaded: RefCell<Option<HashMap<Uuid, Task>>>,
+}
+
+impl HookStorage {
+    /// Wraps `inner` if there are any hooks in `dir`, and otherwise gives it
+    /// back as it is.
+    pub fn wrap(inner: Box<dyn Storage>, path: &Path, dir: &Path) -> Box<dyn Storage> {
+        let hooks = find_hooks(dir);
+        if hooks.is_empty() {
+            return inner;
+        }
+        Box::new(HookStorage { inner, path: path.to_path_buf(), hooks, loaded: RefCell::new(None) })
+    }
+
+    fn run(&self, event: &str, input: &str) {
+        for hook in self.hooks.get(event).into_iter().flatten() {
+            if let Err(message) = run_hook(hook, event, &self.path, input) {
+                let message = tr!("hook-failed", hook = hook.display().to_string(), error = message);
+                eprintln!("{}", tr!("warning", message = message));
+            }
+        }
+    }
+
+    fn run_for_task(&self, event: &str, task: &Task) {
+        if self.hooks.contains_key(event) {
+            self.run(event, &to_json(task));
+        }
+    }
+
+    fn remember(&self, todo_list: &TodoList) {
+        let tasks = todo_list.tasks().iter().map(|task| (task.uuid, task.clone())).collect();
+        *self.loaded.borrow_mut() = Some(tasks);
+    }
+}
+
+impl Storage for HookStorage {
+    fn load(&self) -> Result<TodoList, TodoError> {
+        let todo_list = self.inner.load()?;
+        self.remember(&todo_list);
+        Ok(todo_list)
+    }
+
+    fn save(&self, todo_list: &TodoList) -> Result<(), TodoError> {
+        self.inner.save(todo_list)?;
+        // A list imported from elsewhere was never loaded, so nothing in it
+        // counts as added
+        let loaded = self.loaded.borrow_mut().take();
+        let changed: Vec<&Task> = todo_list.tasks().iter()
+            .filter(|task| loaded.as_ref().is_none_or(|loaded| loaded.get(&task.uuid) != Some(*task)))
+            .collect();
+        if let Some(loaded) = &loaded {
+            for task in &changed {
+                match loaded.get(&task.uuid) {
+                    None => self.run_for_task("on-add", task),
+                    Some(before) if task.completed && !before.completed => self.run_for_task("on-complete", task),
+                    Some(_) => {}
+                }
+            }
+        }
+        if !changed.is_empty() && self.hooks.contains_key("on-save") {
+            self.run("on-save", &serde_json::to_string(&changed).unwrap_or_default());
+        }
+        self.remember(todo_list);
+        Ok(())
+    }
+
+    fn append(&self, task: &Task) -> Result<(), TodoError> {
+        self.inner.append(task)?;
+        if let Some(loaded) = self.loaded.borrow_mut().as_mut() {
+            loaded.insert(task.uuid, task.clone());
+        }
+        self.run_for_task("on-add", task);
+        if self.hooks.contains_key("on-save") {
+            self.run("on-save", &serde_json::to_string(&[task]).unwrap_or_default());
+        }
+        Ok(())
+    }
+
+    fn load_page(
+        &self,
+        sort: SortOrder,
+        offset: usize,
+        limit: usize,
+        keep: &dyn Fn(&Task) -> bool,
+    ) -> Result<Option<Page>, TodoError> {
+        self.inner.load_page(sort, offset, limit, keep)
+    }
+}
+
+// The executable files in `dir` for each event, in name order
+fn find_hooks(dir: &Path) -> HashMap<&'static str, Vec<PathBuf>> {
+    let mut hooks: HashMap<&'static str, Vec<PathBuf>> = HashMap::new();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return hooks;
+    };
+    let mut paths: Vec<PathBuf> = entries.filter_map(|entry| Some(entry.ok()?.path())).collect();
+    paths.sort();
+    for path in paths.into_iter().filter(|path| is_executable(path)) {
+        let name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        let event = EVENTS.iter().find(|event| {
+            name.strip_prefix(**event).is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+        });
+        if let Some(event) = event {
+            hooks.entry(event).or_default().push(path);
+        }
+    }
+    hooks
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+fn run_hook(hook: &Path, event: &str, data_file: &Path, input: &str) -> Result<(), String> {
+    let mut child = Command::new(hook)
+        .env("TODO_HOOK", event)
+        .env("TODO_FILE", data_file)
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A hook that does not read its input closes the pipe, which is fine
+        let _ = stdin.write_all(input.as_bytes());
+    }
+    let status = child.wait().map_err(|e| e.to_string())?;
+    if !status.success() {
+        return Err(format!("exited with {}", status));
+    }
+    Ok(())
+}
+
+fn to_json(task: &Task) -> String {
+    serde_json::to_string(task).unwrap_or_default()
+}
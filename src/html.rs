@@ -0,0 +1,140 @@
+//! A status report as a single HTML page, for sharing with people who do not
+//! use the command line. It has the figures from [`stats::Report`], the
+//! overdue tasks and a section for each tag, and its styles are inline, so
+//! the one file can be mailed or put on a web server as it is.
+
+use std::collections::BTreeSet;
+
+use crate::stats::{self, Report};
+use crate::{Date, Task, TodoList};
+
+const STYLE: &str = "\
+body { font-family: system-ui, sans-serif; max-width: 60em; margin: 2em auto; padding: 0 1em; color: #222; }
+h1 { margin-bottom: 0; }
+.date { color: #666; margin-top: 0.2em; }
+.figures { display: flex; flex-wrap: wrap; gap: 1em; padding: 0; list-style: none; }
+.figures li { border: 1px solid #ddd; border-radius: 6px; padding: 0.6em 1em; min-width: 7em; }
+.figures strong { display: block; font-size: 1.6em; }
+.bar { background: #eee; border-radius: 4px; height: 0.6em; overflow: hidden; }
+.bar span { display: block; height: 100%; background: #4a8; }
+table { border-collapse: collapse; width: 100%; margin-bottom: 1em; }
+th, td { text-align: left; padding: 0.3em 0.6em; border-bottom: 1px solid #eee; }
+tr.overdue td { color: #b00; font-weight: bold; }
+tr.completed td { color: #999; text-decoration: line-through; }
+.days td.count { width: 100%; }
+";
+
+/// The report on `todo_list` as of `today`, as a complete HTML document.
+pub fn report(todo_list: &TodoList, today: Date) -> String {
+    let figures = Report::new(todo_list, today);
+    let tasks = todo_list.tasks();
+    let overdue: Vec<&Task> = tasks.iter().filter(|task| task.is_overdue(today)).collect();
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<title>Todo list report</title>\n");
+    html.push_str(&format!("<style>\n{}</style>\n</head>\n<body>\n", STYLE));
+    html.push_str(&format!("<h1>Todo list report</h1>\n<p class=\"date\">As of {}</p>\n", today));
+
+    html.push_str("<ul class=\"figures\">\n");
+    let total = figures.pending + figures.completed;
+    for (label, value) in [
+        ("Open", figures.pending.to_string()),
+        ("Completed", figures.completed.to_string()),
+        ("Overdue", overdue.len().to_string()),
+        ("Done", format!("{}%", percent(figures.completed, total))),
+    ] {
+        html.push_str(&format!("<li><strong>{}</strong>{}</li>\n", value, label));
+    }
+    if let Some(seconds) = figures.average_seconds_to_complete {
+        html.push_str(&format!("<li><strong>{}</strong>Average time to complete</li>\n", stats::format_duration(seconds)));
+    }
+    html.push_str("</ul>\n");
+    html.push_str(&progress(figures.completed, total));
+
+    html.push_str(&format!("<h2>Completed in the last {} days</h2>\n<table class=\"days\">\n", stats::REPORT_DAYS));
+    let most = figures.completed_per_day.iter().map(|(_, count)| *count).max().unwrap_or(0);
+    for (day, count) in &figures.completed_per_day {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td class=\"count\">{}</td></tr>\n",
+            day,
+            count,
+            progress(*count, most).trim_end()
+        ));
+    }
+    html.push_str("</table>\n");
+
+    if !overdue.is_empty() {
+        html.push_str("<h2>Overdue</h2>\n");
+        html.push_str(&task_table(&overdue, today));
+    }
+
+    let tags: BTreeSet<&str> = tasks.iter().flat_map(|task| task.tags.iter().map(String::as_str)).collect();
+    for tag in tags {
+        let tagged: Vec<&Task> = tasks.iter().filter(|task| task.has_tag(tag)).collect();
+        html.push_str(&section(&format!("#{}", tag), &tagged, today));
+    }
+    let untagged: Vec<&Task> = tasks.iter().filter(|task| task.tags.is_empty()).collect();
+    if !untagged.is_empty() {
+        html.push_str(&section("Untagged", &untagged, today));
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+// A heading with how much of `tasks` is done, then the tasks
+fn section(title: &str, tasks: &[&Task], today: Date) -> String {
+    let done = tasks.iter().filter(|task| task.completed).count();
+    format!(
+        "<h2>{}</h2>\n<p>{} of {} done</p>\n{}{}",
+        escape(title),
+        done,
+        tasks.len(),
+        progress(done, tasks.len()),
+        task_table(tasks, today)
+    )
+}
+
+// Open tasks first, soonest due first, then the completed ones
+fn task_table(tasks: &[&Task], today: Date) -> String {
+    let mut tasks = tasks.to_vec();
+    tasks.sort_by_key(|task| (task.completed, task.due_date.is_none(), task.due_date, task.id));
+    let mut html = String::from("<table>\n<tr><th>ID</th><th>Task</th><th>Priority</th><th>Due</th><th>Assignee</th></tr>\n");
+    for task in tasks {
+        let class = if task.completed {
+            " class=\"completed\""
+        } else if task.is_overdue(today) {
+            " class=\"overdue\""
+        } else {
+            ""
+        };
+        html.push_str(&format!(
+            "<tr{}><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            class,
+            task.id,
+            escape(&task.description),
+            task.priority,
+            task.due_date.map(|due| due.to_string()).unwrap_or_default(),
+            escape(task.assignee.as_deref().unwrap_or_default())
+        ));
+    }
+    html.push_str("</table>\n");
+    html
+}
+
+fn progress(part: usize, whole: usize) -> String {
+    format!("<div class=\"bar\"><span style=\"width: {}%\"></span></div>\n", percent(part, whole))
+}
+
+fn percent(part: usize, whole: usize) -> usize {
+    (part * 100).checked_div(whole).unwrap_or(0)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
@@ -0,0 +1,109 @@
+//! The messages the program shows, in the user's language. They are kept
+//! in Fluent (<https://projectfluent.org>) catalogs under `locales/`, one
+//! per language, and looked up by ID with [`tr!`](crate::tr):
+//!
+//! ```text
+//! task-completed = Task { $id } completed
+//! ```
+//!
+//! gives `tr!("task-completed", id = 3)`, "Task 3 completed".
+//!
+//! A message missing from a catalog falls back to English. The language is
+//! the one given to [`set_language`], or else the one `LC_ALL`,
+//! `LC_MESSAGES` or `LANG` names, or English when there is no catalog for
+//! it.
+
+use std::env;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use unic_langid::LanguageIdentifier;
+
+use crate::TodoError;
+
+pub use fluent_bundle::FluentValue as Arg;
+
+// English first, as it is what others fall back to
+const CATALOGS: [(&str, &str); 2] = [
+    ("en", include_str!("../locales/en.ftl")),
+    ("de", include_str!("../locales/de.ftl")),
+];
+
+// Which of CATALOGS is in use; past the end until it is worked out
+static LANGUAGE: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+static BUNDLES: OnceLock<Vec<FluentBundle<FluentResource>>> = OnceLock::new();
+
+/// The languages there are catalogs for, as codes such as `de`.
+pub fn languages() -> Vec<&'static str> {
+    CATALOGS.iter().map(|(code, _)| *code).collect()
+}
+
+/// Writes messages in `language`, a code such as `de` or a locale such as
+/// `de_DE.UTF-8`, from now on.
+pub fn set_language(language: &str) -> Result<(), TodoError> {
+    let index = find(language).ok_or_else(|| {
+        TodoError::Parse(crate::tr!("no-language", language = language, languages = languages().join(", ")))
+    })?;
+    LANGUAGE.store(index, Ordering::Relaxed);
+    Ok(())
+}
+
+/// The message with this ID, filled in with `args`. Use [`tr!`](crate::tr)
+/// rather than calling this directly.
+pub fn message(id: &str, args: &[(&str, Arg)]) -> String {
+    let bundles = BUNDLES.get_or_init(|| CATALOGS.iter().map(|(code, text)| bundle(code, text)).collect());
+    let mut fluent_args = FluentArgs::new();
+    for (name, value) in args {
+        fluent_args.set(*name, value.clone());
+    }
+    for bundle in [&bundles[language()], &bundles[0]] {
+        if let Some(pattern) = bundle.get_message(id).and_then(|message| message.value()) {
+            let mut errors = Vec::new();
+            return bundle.format_pattern(pattern, Some(&fluent_args), &mut errors).into_owned();
+        }
+    }
+    id.to_string()
+}
+
+/// Looks up a message by ID, with `name = value` pairs for the variables
+/// it uses, like `tr!("task-added", id = 4)`.
+#[macro_export]
+macro_rules! tr {
+    ($id:literal $(, $name:ident = $value:expr)* $(,)?) => {
+        $crate::i18n::message($id, &[$((stringify!($name), $crate::i18n::Arg::from($value))),*])
+    };
+}
+
+fn language() -> usize {
+    let index = LANGUAGE.load(Ordering::Relaxed);
+    if index < CATALOGS.len() {
+        return index;
+    }
+    // The first of these that is set decides, as with gettext
+    let index = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .find_map(|name| env::var(name).ok().filter(|value| !value.is_empty()))
+        .and_then(|locale| find(&locale))
+        .unwrap_or(0);
+    LANGUAGE.store(index, Ordering::Relaxed);
+    index
+}
+
+// `de_DE.UTF-8` and `de-AT` are both German
+fn find(locale: &str) -> Option<usize> {
+    let code = locale.split(['_', '-', '.', '@']).next().unwrap_or_default().to_lowercase();
+    CATALOGS.iter().position(|(catalog, _)| *catalog == code)
+}
+
+fn bundle(code: &str, text: &str) -> FluentBundle<FluentResource> {
+    let language: LanguageIdentifier = code.parse().expect("catalog codes are valid language tags");
+    let mut bundle = FluentBundle::new_concurrent(vec![language]);
+    // The marks Fluent puts around variables only get in the way in a terminal
+    bundle.set_use_isolating(false);
+    let resource = FluentResource::try_new(text.to_string()).expect("catalogs are valid Fluent");
+    bundle.add_resource(resource).expect("catalogs do not repeat message IDs");
+    bundle
+}
@@ -0,0 +1,339 @@
+//! iCalendar (RFC 5545) export, writing each task as a VTODO so calendar
+//! and reminder apps can show it, and reading VTODOs back for CalDAV sync:
+//!
+//! ```text
+//! BEGIN:VTODO
+//! UID:67e55044-10b1-426f-9247-bb680e5fe0c8
+//! SUMMARY:pay rent
+//! DTSTART;VALUE=DATE:20240525
+//! DUE;VALUE=DATE:20240601
+//! PRIORITY:1
+//! STATUS:NEEDS-ACTION
+//! END:VTODO
+//! ```
+//!
+//! Task UUIDs become UIDs, tags become CATEGORIES, notes the DESCRIPTION,
+//! each comment a COMMENT starting with when it was made, each attachment
+//! an ATTACH, with paths as `file://` URLs, and subtasks point at their
+//! parent with RELATED-TO. Tasks in progress have the status IN-PROCESS.
+//! Blocked tasks, assignees and custom fields are written as
+//! `X-TODO-BLOCKED:TRUE`, `X-TODO-ASSIGNEE:alice` and
+//! `X-TODO-FIELD;X-NAME=customer:ACME`, which other apps ignore.
+
+use crate::{tr, Column, Date, Priority, Recurrence, Task, Timestamp, TodoError, TodoList};
+
+// Lines longer than this many bytes are folded onto continuation lines
+const MAX_LINE: usize = 75;
+
+/// Writes every task as a VTODO inside one VCALENDAR.
+pub fn export(todo_list: &TodoList) -> String {
+    let now = todo_list.now();
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//todo-list//todo//EN".to_string(),
+    ];
+    for task in todo_list.tasks() {
+        lines.extend(vtodo(task, todo_list, &task.uuid.to_string(), Some(now)));
+    }
+    lines.push("END:VCALENDAR".to_string());
+    lines.iter().map(|line| fold(line)).collect()
+}
+
+// A VCALENDAR holding just `task`, stored under `uid`
+#[cfg(feature = "native")]
+pub(crate) fn calendar_for(task: &Task, todo_list: &TodoList, uid: &str) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//todo-list//todo//EN".to_string(),
+    ];
+    lines.extend(vtodo(task, todo_list, uid, Some(todo_list.now())));
+    lines.push("END:VCALENDAR".to_string());
+    lines.iter().map(|line| fold(line)).collect()
+}
+
+// Everything the VTODO for `task` says apart from DTSTAMP, which changes on
+// every export. Two fingerprints differ exactly when the task has changed.
+#[cfg(feature = "native")]
+pub(crate) fn fingerprint(task: &Task, todo_list: &TodoList) -> String {
+    vtodo(task, todo_list, &task.uuid.to_string(), None).join("\n")
+}
+
+// A task read from a VTODO, with the UID it is stored under and the UID of
+// its parent. Its ID is left at 0.
+#[cfg(feature = "native")]
+pub(crate) struct RemoteTodo {
+    pub(crate) task: Task,
+    pub(crate) uid: String,
+    pub(crate) related_to: Option<String>,
+}
+
+// Reads the first VTODO in `text`. Properties this crate does not know are
+// ignored.
+#[cfg(feature = "native")]
+pub(crate) fn parse_vtodo(text: &str) -> Result<RemoteTodo, TodoError> {
+    let mut task = Task::default();
+    let mut uid = None;
+    let mut related_to = None;
+    let mut in_vtodo = false;
+
+    for line in unfold(text) {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        // DUE;VALUE=DATE:20240601 has parameters after the name
+        let mut parameters = name.split(';');
+        let name = parameters.next().unwrap_or("").to_ascii_uppercase();
+        match (in_vtodo, name.as_str()) {
+            (false, "BEGIN") if value.eq_ignore_ascii_case("VTODO") => in_vtodo = true,
+            (false, _) => {}
+            (true, "END") if value.eq_ignore_ascii_case("VTODO") => break,
+            (true, "UID") => uid = Some(value.to_string()),
+            (true, "SUMMARY") => task.description = unescape(value),
+            (true, "DESCRIPTION") => task.notes = Some(unescape(value)).filter(|notes| !notes.trim().is_empty()),
+            (true, "DUE") => task.due_date = Some(parse_date_time(value)?.date()),
+            (true, "DTSTART") => task.start_date = Some(parse_date_time(value)?.date()),
+            (true, "CREATED") => task.created_at = Some(parse_date_time(value)?),
+            (true, "COMPLETED") => task.completed_at = Some(parse_date_time(value)?),
+            (true, "STATUS") => {
+                task.completed = value.eq_ignore_ascii_case("COMPLETED");
+                if value.eq_ignore_ascii_case("IN-PROCESS") {
+                    task.column = Column::InProgress;
+                }
+            }
+            (true, "X-TODO-BLOCKED") if value.eq_ignore_ascii_case("TRUE") => task.column = Column::Blocked,
+            // 1 to 4 are high, 5 (and 0, undefined) medium and 6 to 9 low
+            (true, "PRIORITY") => {
+                task.priority = match value.trim().parse::<u32>() {
+                    Ok(1..=4) => Priority::High,
+                    Ok(6..=9) => Priority::Low,
+                    _ => Priority::Medium,
+                }
+            }
+            (true, "CATEGORIES") => {
+                for category in unescape_list(value) {
+                    let tag = category.trim().to_lowercase().replace(char::is_whitespace, "-");
+                    if !tag.is_empty() && !task.tags.contains(&tag) {
+                        task.tags.push(tag);
+                    }
+                }
+            }
+            (true, "RRULE") => {
+                task.recurrence = value.split(';').find_map(|part| match part.to_ascii_uppercase().as_str() {
+                    "FREQ=DAILY" => Some(Recurrence::Daily),
+                    "FREQ=WEEKLY" => Some(Recurrence::Weekly),
+                    "FREQ=MONTHLY" => Some(Recurrence::Monthly),
+                    _ => None,
+                })
+            }
+            (true, "RELATED-TO") => related_to = Some(value.to_string()),
+            (true, "ATTACH") if !value.is_empty() => {
+                let attachment = value.strip_prefix("file://").unwrap_or(value).to_string();
+                if !task.attachments.contains(&attachment) {
+                    task.attachments.push(attachment);
+                }
+            }
+            (true, "X-TODO-ASSIGNEE") => task.assignee = Some(unescape(value)).filter(|name| !name.trim().is_empty()),
+            (true, "X-TODO-FIELD") => {
+                let field = parameters.find_map(|parameter| parameter.strip_prefix("X-NAME="));
+                if let Some(field) = field.filter(|field| crate::is_field_name(field)) {
+                    task.fields.insert(field.to_string(), unescape(value));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let uid = uid.ok_or_else(|| TodoError::Parse(tr!("ical-no-uid")))?;
+    if task.description.trim().is_empty() {
+        return Err(TodoError::EmptyDescription);
+    }
+    Ok(RemoteTodo { task, uid, related_to })
+}
+
+fn vtodo(task: &Task, todo_list: &TodoList, uid: &str, dtstamp: Option<Timestamp>) -> Vec<String> {
+    let mut lines = vec!["BEGIN:VTODO".to_string(), format!("UID:{}", uid)];
+    if let Some(dtstamp) = dtstamp {
+        lines.push(format!("DTSTAMP:{}", date_time(dtstamp)));
+    }
+    lines.push(format!("SUMMARY:{}", escape(&task.description)));
+    if let Some(created_at) = task.created_at {
+        lines.push(format!("CREATED:{}", date_time(created_at)));
+    }
+    if let Some(notes) = &task.notes {
+        lines.push(format!("DESCRIPTION:{}", escape(notes)));
+    }
+    for comment in &task.comments {
+        lines.push(format!("COMMENT:{} {}", comment.at, escape(&comment.text)));
+    }
+    for attachment in &task.attachments {
+        // ATTACH takes a URI, and an absolute path is one after file://
+        let uri = if attachment.contains("://") { attachment.clone() } else { format!("file://{}", attachment) };
+        lines.push(format!("ATTACH:{}", uri));
+    }
+    if let Some(start) = task.start_date {
+        lines.push(format!("DTSTART;VALUE=DATE:{}", date(start)));
+    }
+    if let Some(due) = task.due_date {
+        lines.push(format!("DUE;VALUE=DATE:{}", date(due)));
+    }
+    // 1 is the highest priority and 9 the lowest
+    let priority = match task.priority {
+        Priority::High => 1,
+        Priority::Medium => 5,
+        Priority::Low => 9,
+    };
+    lines.push(format!("PRIORITY:{}", priority));
+    if task.completed {
+        lines.push("STATUS:COMPLETED".to_string());
+        if let Some(completed_at) = task.completed_at {
+            lines.push(format!("COMPLETED:{}", date_time(completed_at)));
+        }
+    } else if task.column == Column::InProgress {
+        lines.push("STATUS:IN-PROCESS".to_string());
+    } else {
+        lines.push("STATUS:NEEDS-ACTION".to_string());
+    }
+    if task.status() == Column::Blocked {
+        lines.push("X-TODO-BLOCKED:TRUE".to_string());
+    }
+    if !task.tags.is_empty() {
+        let tags: Vec<String> = task.tags.iter().map(|tag| escape(tag)).collect();
+        lines.push(format!("CATEGORIES:{}", tags.join(",")));
+    }
+    if let Some(recurrence) = task.recurrence {
+        let frequency = match recurrence {
+            Recurrence::Daily => "DAILY",
+            Recurrence::Weekly => "WEEKLY",
+            Recurrence::Monthly => "MONTHLY",
+        };
+        lines.push(format!("RRULE:FREQ={}", frequency));
+    }
+    if let Some(parent) = task.parent.and_then(|id| todo_list.task(id)) {
+        lines.push(format!("RELATED-TO:{}", parent.uuid));
+    }
+    if let Some(assignee) = &task.assignee {
+        lines.push(format!("X-TODO-ASSIGNEE:{}", escape(assignee)));
+    }
+    for (name, value) in &task.fields {
+        lines.push(format!("X-TODO-FIELD;X-NAME={}:{}", name, escape(value)));
+    }
+    lines.push("END:VTODO".to_string());
+    lines
+}
+
+// 20240601
+fn date(date: Date) -> String {
+    format!("{:04}{:02}{:02}", date.year, date.month, date.day)
+}
+
+// 20240601T093000Z
+fn date_time(timestamp: Timestamp) -> String {
+    timestamp.to_string().replace(['-', ':'], "")
+}
+
+// 20240601 or 20240601T093000Z; times without a zone are taken as UTC
+pub(crate) fn parse_date_time(value: &str) -> Result<Timestamp, TodoError> {
+    let error = || TodoError::Parse(tr!("ical-bad-date", value = value));
+    let value = value.trim();
+    let digits = |range: std::ops::Range<usize>| value.get(range).filter(|part| part.bytes().all(|b| b.is_ascii_digit()));
+    let (Some(year), Some(month), Some(day)) = (digits(0..4), digits(4..6), digits(6..8)) else {
+        return Err(error());
+    };
+    let date = Date::parse(&format!("{}-{}-{}", year, month, day)).map_err(|_| error())?;
+    let time = match value.get(8..) {
+        Some("") | None => "00:00:00".to_string(),
+        Some(rest) => {
+            let rest = rest.strip_prefix('T').ok_or_else(error)?;
+            let (Some(hour), Some(minute), Some(second)) = (digits(9..11), digits(11..13), digits(13..15)) else {
+                return Err(error());
+            };
+            if !matches!(&rest[6..], "" | "Z") {
+                return Err(error());
+            }
+            format!("{}:{}:{}", hour, minute, second)
+        }
+    };
+    Timestamp::parse(&format!("{}T{}Z", date, time))
+}
+
+// Joins continuation lines, which start with a space or tab, back onto the
+// line before
+#[cfg(feature = "native")]
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        let line = line.trim_end_matches('\r');
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+#[cfg(feature = "native")]
+fn unescape(value: &str) -> String {
+    let mut text = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => text.push('\n'),
+            Some(escaped) => text.push(escaped),
+            None => {}
+        }
+    }
+    text
+}
+
+// Splits a comma separated value on the commas that are not escaped
+#[cfg(feature = "native")]
+fn unescape_list(value: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut start = 0;
+    let mut escaped = false;
+    for (index, c) in value.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            ',' => {
+                items.push(unescape(&value[start..index]));
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(unescape(&value[start..]));
+    items
+}
+
+// Backslashes, commas, semicolons and line breaks are escaped in text values
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+        .replace('\r', "")
+}
+
+// Ends the line with CRLF, breaking it into continuation lines starting
+// with a space so none is longer than MAX_LINE bytes
+fn fold(line: &str) -> String {
+    let mut folded = String::new();
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > MAX_LINE {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
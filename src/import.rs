@@ -0,0 +1,290 @@
+//! Importers for the files other todo apps export. Tasks whose
+//! description matches one already in the list are skipped, so importing
+//! the same export twice does not create duplicates.
+
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+
+use serde::Deserialize;
+
+use crate::{ical, tr, Date, Priority, Recurrence, Task, Timestamp, TodoError, TodoList};
+
+/// What an import added, and how many tasks it left out because they were
+/// already in the list, had no description or were deleted in the app.
+#[derive(Debug, Clone, Default)]
+pub struct ImportSummary {
+    pub added: Vec<usize>,
+    pub skipped: usize,
+}
+
+/// Reads a Todoist CSV export. Labels written as `@label` in the content
+/// become tags, the description becomes notes and priority 1 to 3 maps to
+/// high, medium and low. Dates that are not YYYY-MM-DD, such as
+/// "every monday", are left off.
+pub fn todoist(todo_list: &mut TodoList, reader: impl Read) -> Result<ImportSummary, TodoError> {
+    let rows = read_rows(reader)?;
+    let mut tasks = Vec::new();
+
+    for row in &rows {
+        // Sections and comments share the file with tasks
+        if !row.get("type").unwrap_or("task").eq_ignore_ascii_case("task") {
+            continue;
+        }
+
+        let mut task = Task::default();
+        let mut words = Vec::new();
+        for word in row.get("content").unwrap_or("").split_whitespace() {
+            match word.strip_prefix('@').filter(|label| !label.is_empty()) {
+                Some(label) => add_tag(&mut task, label),
+                None => words.push(word),
+            }
+        }
+        task.description = words.join(" ");
+        task.notes = row.get("description").filter(|notes| !notes.is_empty()).map(str::to_string);
+        task.priority = match row.get("priority").unwrap_or("") {
+            "1" => Priority::High,
+            "3" => Priority::Low,
+            _ => Priority::Medium,
+        };
+        task.due_date = row.get("date").and_then(parse_date_prefix);
+        tasks.push(task);
+    }
+
+    Ok(add_new(todo_list, tasks))
+}
+
+/// Reads a Trello CSV export. Card names become descriptions, card
+/// descriptions become notes, labels become tags and archived or
+/// due-complete cards are imported as completed.
+pub fn trello(todo_list: &mut TodoList, reader: impl Read) -> Result<ImportSummary, TodoError> {
+    let rows = read_rows(reader)?;
+    let mut tasks = Vec::new();
+
+    for row in &rows {
+        let mut task = Task {
+            description: row.get("card name").unwrap_or("").trim().to_string(),
+            ..Task::default()
+        };
+        task.notes = row.get("card description").filter(|notes| !notes.is_empty()).map(str::to_string);
+        // Labels look like "Urgent (red), Errands (green)"
+        for label in row.get("labels").unwrap_or("").split(',') {
+            let name = match label.rfind(" (") {
+                Some(index) => &label[..index],
+                None => label,
+            };
+            add_tag(&mut task, name);
+        }
+        task.due_date = row.get("due date").and_then(parse_date_prefix);
+        task.completed = ["archived", "due complete"]
+            .iter()
+            .any(|column| row.get(column).is_some_and(|value| value.eq_ignore_ascii_case("true")));
+        tasks.push(task);
+    }
+
+    Ok(add_new(todo_list, tasks))
+}
+
+/// Reads the JSON written by Taskwarrior's `task export`, either as one
+/// array or one task per line as older versions write it. Pending and
+/// waiting tasks are imported as open and completed ones as completed;
+/// deleted tasks and recurring templates are skipped, though the instances
+/// of a recurring task are kept with their recurrence. H, M and L
+/// priorities map to high, medium and low, the project becomes a tag as
+/// well, annotations become notes and dependencies between imported tasks
+/// are kept.
+pub fn taskwarrior(todo_list: &mut TodoList, mut reader: impl Read) -> Result<ImportSummary, TodoError> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    let text = text.trim();
+    let exported: Vec<TaskwarriorTask> = if text.starts_with('[') {
+        serde_json::from_str(text)?
+    } else {
+        text.lines()
+            .map(|line| line.trim().trim_end_matches(','))
+            .filter(|line| !line.is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()?
+    };
+
+    let mut skipped = 0;
+    let mut tasks = Vec::new();
+    for exported in exported {
+        if matches!(exported.status.as_str(), "deleted" | "recurring") {
+            skipped += 1;
+            continue;
+        }
+        let uuid = exported.uuid.clone().unwrap_or_default();
+        let depends = match &exported.depends {
+            Depends::List(uuids) => uuids.clone(),
+            Depends::Text(uuids) => uuids.split(',').map(|uuid| uuid.trim().to_string()).collect(),
+        };
+        tasks.push((exported.into_task()?, uuid, depends));
+    }
+    skipped += drop_duplicates(todo_list, &mut tasks, |(task, _, _)| task);
+
+    // Imported tasks are numbered in order from the next free ID
+    let ids: HashMap<String, usize> = tasks.iter()
+        .enumerate()
+        .filter(|(_, (_, uuid, _))| !uuid.is_empty())
+        .map(|(position, (_, uuid, _))| (uuid.clone(), todo_list.next_id + position))
+        .collect();
+    let tasks = tasks.into_iter()
+        .map(|(mut task, _, depends)| {
+            task.depends_on = depends.iter().filter_map(|uuid| ids.get(uuid).copied()).collect();
+            task
+        })
+        .collect();
+
+    Ok(ImportSummary {
+        added: todo_list.add_imported(tasks),
+        skipped,
+    })
+}
+
+// The fields of a Taskwarrior task this crate has a place for
+#[derive(Deserialize)]
+struct TaskwarriorTask {
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    status: String,
+    uuid: Option<String>,
+    entry: Option<String>,
+    end: Option<String>,
+    due: Option<String>,
+    priority: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    project: Option<String>,
+    #[serde(default)]
+    annotations: Vec<Annotation>,
+    #[serde(default)]
+    depends: Depends,
+    recur: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Annotation {
+    #[serde(default)]
+    description: String,
+}
+
+// Taskwarrior 2.6 writes dependencies as an array, earlier versions as one
+// comma separated string
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Depends {
+    List(Vec<String>),
+    Text(String),
+}
+
+impl Default for Depends {
+    fn default() -> Self {
+        Depends::List(Vec::new())
+    }
+}
+
+impl TaskwarriorTask {
+    fn into_task(self) -> Result<Task, TodoError> {
+        let mut task = Task {
+            description: self.description.trim().to_string(),
+            completed: self.status == "completed",
+            ..Task::default()
+        };
+        task.priority = match self.priority.as_deref() {
+            Some("H") => Priority::High,
+            Some("L") => Priority::Low,
+            _ => Priority::Medium,
+        };
+        for tag in self.tags.iter().chain(&self.project) {
+            add_tag(&mut task, tag);
+        }
+        // Due dates are local midnight written in UTC, so up to half a day
+        // either side of the day meant
+        task.due_date = match &self.due {
+            Some(due) => Some(Timestamp::from_seconds(ical::parse_date_time(due)?.seconds() + 43_200).date()),
+            None => None,
+        };
+        task.created_at = self.entry.as_deref().map(ical::parse_date_time).transpose()?;
+        task.completed_at = self.end.as_deref().filter(|_| task.completed).map(ical::parse_date_time).transpose()?;
+        let notes: Vec<&str> = self.annotations.iter()
+            .map(|annotation| annotation.description.trim())
+            .filter(|note| !note.is_empty())
+            .collect();
+        task.notes = Some(notes.join("\n")).filter(|notes| !notes.is_empty());
+        task.recurrence = match self.recur.as_deref() {
+            Some("daily" | "day" | "1d") => Some(Recurrence::Daily),
+            Some("weekly" | "week" | "1w" | "7d") => Some(Recurrence::Weekly),
+            Some("monthly" | "month" | "1m" | "1mo") => Some(Recurrence::Monthly),
+            _ => None,
+        };
+        Ok(task)
+    }
+}
+
+// A CSV row that can be looked up by lowercase column name
+struct Row {
+    headers: csv::StringRecord,
+    record: csv::StringRecord,
+}
+
+impl Row {
+    fn get(&self, column: &str) -> Option<&str> {
+        let index = self.headers.iter().position(|header| header.trim().eq_ignore_ascii_case(column))?;
+        self.record.get(index).map(str::trim)
+    }
+}
+
+fn read_rows(reader: impl Read) -> Result<Vec<Row>, TodoError> {
+    let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(reader);
+    let headers = reader.headers().map_err(csv_error)?.clone();
+    reader
+        .records()
+        .map(|record| {
+            Ok(Row {
+                headers: headers.clone(),
+                record: record.map_err(csv_error)?,
+            })
+        })
+        .collect()
+}
+
+fn csv_error(e: csv::Error) -> TodoError {
+    TodoError::Parse(tr!("csv-read-error", error = e.to_string()))
+}
+
+// Labels may contain spaces, which tags cannot
+fn add_tag(task: &mut Task, label: &str) {
+    let tag = label.split_whitespace().collect::<Vec<_>>().join("-").to_lowercase();
+    if !tag.is_empty() && !task.tags.contains(&tag) {
+        task.tags.push(tag);
+    }
+}
+
+// Exports write dates like 2024-06-01T12:00:00.000Z; only the date is kept
+fn parse_date_prefix(value: &str) -> Option<Date> {
+    value.get(..10).and_then(|date| Date::parse(date).ok())
+}
+
+// Adds the tasks that have a description and are not already in the list
+fn add_new(todo_list: &mut TodoList, mut tasks: Vec<Task>) -> ImportSummary {
+    let skipped = drop_duplicates(todo_list, &mut tasks, |task| task);
+    ImportSummary {
+        added: todo_list.add_imported(tasks),
+        skipped,
+    }
+}
+
+// Leaves out the items whose task has no description or one already in the
+// list or earlier in `items`, returning how many were left out
+fn drop_duplicates<T>(todo_list: &TodoList, items: &mut Vec<T>, task: impl Fn(&T) -> &Task) -> usize {
+    let mut seen: HashSet<String> = todo_list.tasks().iter()
+        .map(|task| task.description.trim().to_lowercase())
+        .collect();
+    let count = items.len();
+    items.retain(|item| {
+        let description = task(item).description.to_lowercase();
+        !description.is_empty() && seen.insert(description)
+    });
+    count - items.len()
+}
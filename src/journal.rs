@@ -0,0 +1,131 @@
+//! A write-ahead journal for long sessions that only save the list at the
+//! end, such as the interactive menu. Each change is appended to the journal
+//! as soon as it is made, one JSON entry per line:
+//!
+//! ```text
+//! {"put":{"id":4,"description":"water the plants",...}}
+//! {"remove":2}
+//! ```
+//!
+//! `put` adds a task or replaces the one with the same ID, and `remove`
+//! takes one out. Once the list has been saved the journal is deleted; one
+//! that is still there on the next run is left from a session that was
+//! killed, and is replayed over the saved list.
+
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, trace};
+
+use crate::{tr, Task, TodoError, TodoList};
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Entry {
+    Put(Box<Task>),
+    Remove(usize),
+}
+
+/// The journal for one session, open for appending.
+pub struct Journal {
+    path: PathBuf,
+    file: File,
+    // The tasks as of the last entry written, to tell what changed since
+    seen: HashMap<usize, Task>,
+}
+
+impl Journal {
+    /// Opens the journal at `path` for a session starting from `todo_list`,
+    /// keeping any entries already in it.
+    pub fn open(path: impl Into<PathBuf>, todo_list: &TodoList) -> Result<Self, TodoError> {
+        let path = path.into();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let seen = todo_list.tasks().iter().map(|task| (task.id, task.clone())).collect();
+        Ok(Journal { path, file, seen })
+    }
+
+    /// Appends whatever changed in `todo_list` since the last call, and
+    /// makes sure it reached the disk before returning. Returns whether
+    /// anything had changed.
+    pub fn record(&mut self, todo_list: &TodoList) -> Result<bool, TodoError> {
+        let mut lines = String::new();
+        for task in todo_list.tasks() {
+            if self.seen.get(&task.id) != Some(task) {
+                lines.push_str(&serde_json::to_string(&Entry::Put(Box::new(task.clone())))?);
+                lines.push('\n');
+                self.seen.insert(task.id, task.clone());
+            }
+        }
+        let removed: Vec<usize> = self.seen.keys().copied().filter(|&id| todo_list.task(id).is_none()).collect();
+        for id in removed {
+            lines.push_str(&serde_json::to_string(&Entry::Remove(id))?);
+            lines.push('\n');
+            self.seen.remove(&id);
+        }
+
+        if lines.is_empty() {
+            return Ok(false);
+        }
+        self.file.write_all(lines.as_bytes())?;
+        self.file.sync_data()?;
+        debug!(path = %self.path.display(), entries = lines.lines().count(), "journaled changes");
+        Ok(true)
+    }
+
+    /// Empties the journal once the list has been saved part way through a
+    /// session, keeping it open for the changes still to come.
+    pub fn clear(&mut self) -> Result<(), TodoError> {
+        self.file.set_len(0)?;
+        self.file.sync_data()?;
+        Ok(())
+    }
+
+    /// Deletes the journal, for once the list it describes has been saved.
+    pub fn compact(self) -> Result<(), TodoError> {
+        drop(self.file);
+        fs::remove_file(&self.path)?;
+        Ok(())
+    }
+}
+
+/// Applies the entries in the journal at `path` to `todo_list`, returning
+/// how many there were. A missing journal has none. A last line cut short
+/// by the session being killed part way through writing it is ignored.
+pub fn replay(path: &Path, todo_list: &mut TodoList) -> Result<usize, TodoError> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e.into()),
+    };
+
+    if !text.is_empty() && !text.ends_with('\n') {
+        debug!(path = %path.display(), "ignored a last journal entry cut short");
+    }
+    let mut count = 0;
+    // Every complete entry ends with a newline
+    for (line_num, line) in text.split_inclusive('\n').filter(|line| line.ends_with('\n')).enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry: Entry = serde_json::from_str(line)
+            .map_err(|e| TodoError::Parse(tr!("journal-bad-entry", line = line_num + 1, error = e.to_string())))?;
+        match entry {
+            Entry::Put(task) => {
+                trace!(line = line_num + 1, id = task.id, "replayed put");
+                todo_list.insert_task(*task);
+            }
+            Entry::Remove(id) => {
+                trace!(line = line_num + 1, id, "replayed remove");
+                todo_list.remove_synced(id);
+            }
+        }
+        count += 1;
+    }
+    if count > 0 {
+        info!(path = %path.display(), entries = count, "replayed journal");
+    }
+    Ok(count)
+}
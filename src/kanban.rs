@@ -0,0 +1,154 @@
+//! The board drawn across the whole terminal, from the menu: a column for
+//! each status, with the arrow keys or h, j, k and l to go from card to
+//! card, and `<` and `>` or H and L to move the chosen card a column to the
+//! left or right. Moving a card to done completes its task. A column with
+//! more tasks than its WIP limit says so in its heading, and the line at
+//! the bottom says so when a move takes it over.
+
+use std::io;
+
+use console::{pad_str, style, Alignment, Key, Term};
+use todo_list::{tr, Column, TodoError, TodoList};
+
+/// Shows the board until q or Esc is pressed, moving tasks in `todo_list`
+/// between columns as asked.
+pub fn run(todo_list: &mut TodoList) -> Result<(), TodoError> {
+    let term = Term::stdout();
+    if !term.is_term() {
+        return Err(io::Error::other(tr!("board-needs-terminal")).into());
+    }
+    let mut board = Board { column: 0, cards: [0; Column::ALL.len()], message: None };
+    term.hide_cursor()?;
+    let result = board.run(&term, todo_list);
+    term.clear_screen()?;
+    term.show_cursor()?;
+    result
+}
+
+/// Warns on stderr when `column` has more tasks than its WIP limit.
+pub fn warn_over_wip_limit(todo_list: &TodoList, column: Column) {
+    if let Some(message) = wip_warning(todo_list, column) {
+        eprintln!("{}", tr!("warning", message = message));
+    }
+}
+
+// What to warn about when `column` has more tasks than its WIP limit
+fn wip_warning(todo_list: &TodoList, column: Column) -> Option<String> {
+    let (count, limit) = todo_list.over_wip_limit(column)?;
+    Some(tr!("wip-limit-exceeded", column = column.label(), count = count, limit = limit))
+}
+
+struct Board {
+    // The index of the chosen column, and of the chosen card in each column
+    column: usize,
+    cards: [usize; Column::ALL.len()],
+    // What the last move did, shown at the bottom
+    message: Option<String>,
+}
+
+impl Board {
+    fn run(&mut self, term: &Term, todo_list: &mut TodoList) -> Result<(), TodoError> {
+        loop {
+            let columns = columns(todo_list);
+            for (card, ids) in self.cards.iter_mut().zip(&columns) {
+                *card = (*card).min(ids.len().saturating_sub(1));
+            }
+            self.draw(term, todo_list, &columns)?;
+
+            let chosen = columns[self.column].get(self.cards[self.column]).copied();
+            match term.read_key()? {
+                Key::ArrowLeft | Key::Char('h') => self.column = self.column.saturating_sub(1),
+                Key::ArrowRight | Key::Char('l') => self.column = (self.column + 1).min(Column::ALL.len() - 1),
+                Key::ArrowUp | Key::Char('k') => self.cards[self.column] = self.cards[self.column].saturating_sub(1),
+                Key::ArrowDown | Key::Char('j') => self.cards[self.column] += 1,
+                Key::Char('<' | 'H') => {
+                    if let (Some(id), Some(to)) = (chosen, self.column.checked_sub(1)) {
+                        self.move_card(todo_list, id, to);
+                    }
+                }
+                Key::Char('>' | 'L') => {
+                    if let Some(id) = chosen.filter(|_| self.column + 1 < Column::ALL.len()) {
+                        self.move_card(todo_list, id, self.column + 1);
+                    }
+                }
+                Key::Char('q') | Key::Escape => return Ok(()),
+                _ => {}
+            }
+        }
+    }
+
+    // Moves the task to column `to`, which is then chosen with the task's
+    // card in it
+    fn move_card(&mut self, todo_list: &mut TodoList, id: usize, to: usize) {
+        let column = Column::ALL[to];
+        self.message = Some(match todo_list.move_to_column(id, column) {
+            Ok(next_id) => {
+                self.column = to;
+                self.cards[to] = columns(todo_list)[to].iter().position(|&card| card == id).unwrap_or(0);
+                let moved = match next_id {
+                    Some(next_id) => tr!("task-completed-next", id = id, next = next_id),
+                    None => tr!("task-moved-to", id = id, column = column.label()),
+                };
+                match wip_warning(todo_list, column) {
+                    Some(message) => format!("{}. {}", moved, tr!("warning", message = message)),
+                    None => moved,
+                }
+            }
+            Err(e) => tr!("error", message = e.to_string()),
+        });
+    }
+
+    fn draw(&self, term: &Term, todo_list: &TodoList, columns: &[Vec<usize>]) -> io::Result<()> {
+        let (rows, width) = term.size();
+        let width = (width as usize).saturating_sub(Column::ALL.len() - 1) / Column::ALL.len();
+        // Below the cards come a blank line, the message and the keys
+        let height = (rows as usize).saturating_sub(4).max(1);
+        let cell = |text: &str| pad_str(text, width, Alignment::Left, Some("…")).into_owned();
+
+        let headings: Vec<String> = Column::ALL
+            .iter()
+            .zip(columns)
+            .map(|(&column, ids)| style(cell(&todo_list.board_heading(column, ids.len()))).bold().to_string())
+            .collect();
+        let mut lines = vec![headings.join(" ")];
+        // Each column scrolls on its own to keep its chosen card in sight
+        let first: Vec<usize> = self.cards.iter().map(|&card| (card + 1).saturating_sub(height)).collect();
+        let longest = columns.iter().map(Vec::len).max().unwrap_or(0);
+        for line in 0..height.min(longest) {
+            let cells: Vec<String> = columns
+                .iter()
+                .enumerate()
+                .map(|(n, ids)| {
+                    let index = first[n] + line;
+                    let Some(task) = ids.get(index).and_then(|&id| todo_list.task(id)) else {
+                        return cell("");
+                    };
+                    if n == self.column && index == self.cards[n] {
+                        style(cell(&format!("> {}: {}", task.id, task.description))).reverse().to_string()
+                    } else {
+                        cell(&format!("  {}: {}", task.id, task.description))
+                    }
+                })
+                .collect();
+            lines.push(cells.join(" "));
+        }
+        lines.push(String::new());
+        lines.push(self.message.clone().unwrap_or_default());
+        lines.push(style(tr!("board-keys")).dim().to_string());
+
+        term.clear_screen()?;
+        for line in lines {
+            term.write_line(line.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+// The IDs of the tasks in each column, in the list's sort order
+fn columns(todo_list: &TodoList) -> Vec<Vec<usize>> {
+    let mut columns = vec![Vec::new(); Column::ALL.len()];
+    for task in todo_list.sorted_tasks() {
+        columns[task.status() as usize].push(task.id);
+    }
+    columns
+}
@@ -0,0 +1,88 @@
+//! Reader for the comma separated format tasks were saved in before the
+//! switch to JSON.
+
+use std::io::BufRead;
+
+use tracing::{debug, trace};
+use uuid::Uuid;
+
+use crate::{tr, Date, Priority, Rejected, Task, TodoError};
+
+// Splits a saved line on commas that were not escaped with a backslash
+fn split_fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\\' && chars.peek() == Some(&',') {
+            current.push(',');
+            chars.next();
+        } else if c == ',' {
+            fields.push(current);
+            current = String::new();
+        } else {
+            current.push(c);
+        }
+    }
+    fields.push(current);
+    fields
+}
+
+// The tasks read, along with the lines that could not be read as tasks and
+// why, so one bad line costs only itself. Blank lines are not counted.
+pub(crate) fn read_tasks(reader: impl BufRead) -> Result<(Vec<Task>, Vec<Rejected>), TodoError> {
+    let mut tasks = Vec::new();
+    let mut rejected = Vec::new();
+
+    for (line_num, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match read_task(&line) {
+            Ok(task) => {
+                trace!(line = line_num + 1, id = task.id, "read task");
+                tasks.push(task);
+            }
+            Err(e) => {
+                let error = e.to_string();
+                debug!(line = line_num + 1, text = %line, %error, "rejected line");
+                rejected.push(Rejected { line: line_num + 1, text: line, error });
+            }
+        }
+    }
+
+    debug!(tasks = tasks.len(), rejected = rejected.len(), "parsed old comma separated list");
+    Ok((tasks, rejected))
+}
+
+fn read_task(line: &str) -> Result<Task, TodoError> {
+    let parts = split_fields(line);
+    // Older files have no due date or priority columns
+    if !(3..=5).contains(&parts.len()) {
+        return Err(TodoError::Parse(tr!("legacy-field-count", count = parts.len())));
+    }
+
+    let id = parts[0].parse().map_err(|_| TodoError::Parse(tr!("legacy-bad-id", value = parts[0].as_str())))?;
+    let completed = parts[1] == "completed";
+    let description = parts[2].clone();
+    let due_date = match parts.get(3).map(|due| due.trim()) {
+        Some(due) if !due.is_empty() => Some(Date::parse(due)?),
+        _ => None,
+    };
+    let priority = match parts.get(4) {
+        Some(priority) => Priority::parse(priority)?,
+        None => Priority::Medium,
+    };
+
+    Ok(Task {
+        id,
+        uuid: Uuid::new_v4(),
+        description,
+        completed,
+        due_date,
+        priority,
+        ..Task::default()
+    })
+}
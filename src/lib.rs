@@ -0,0 +1,1956 @@
+//! A small todo list engine: tasks with due dates and priorities, plus
+//! loading and saving them to a JSON file.
+//!
+//! Everything that needs files, programs or the network is behind the
+//! `native` feature, on by default. Without it the engine builds for
+//! `wasm32-unknown-unknown`, reading and writing the same JSON with
+//! [`TodoList::from_json`] and [`TodoList::to_json`] for storage of the
+//! embedder's own, such as a browser's local storage.
+
+pub mod agenda;
+pub mod api;
+#[cfg(feature = "native")]
+mod atomic;
+pub mod batch;
+#[cfg(feature = "native")]
+pub mod caldav;
+pub mod capture;
+mod clock;
+pub mod colors;
+pub mod csv;
+mod date;
+pub mod diff;
+pub mod digest;
+pub mod doctor;
+mod error;
+mod estimate;
+pub mod filter;
+pub mod html;
+mod history;
+pub mod i18n;
+pub mod ical;
+pub mod import;
+#[cfg(feature = "native")]
+pub mod journal;
+#[cfg(feature = "native")]
+mod legacy;
+pub mod mail;
+pub mod markdown;
+pub mod merge;
+pub mod notify;
+mod pattern;
+mod recurrence;
+pub mod render;
+mod similar;
+pub mod stats;
+pub mod storage;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod text;
+pub mod todotxt;
+pub mod urgency;
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+#[cfg(feature = "native")]
+use std::fs::File;
+#[cfg(feature = "native")]
+use std::io::{self, BufReader};
+#[cfg(feature = "native")]
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+pub use clock::{Clock, SystemClock};
+pub use date::{Date, DateFormat, Timestamp};
+pub use error::TodoError;
+pub use estimate::Estimate;
+pub use history::DEFAULT_HISTORY_LIMIT;
+pub use recurrence::Recurrence;
+pub use uuid::Uuid;
+
+use history::{Change, History};
+use pattern::Pattern;
+
+// More robust task description validation
+fn validate_description(description: &str) -> Result<String, TodoError> {
+    let trimmed_desc = description.trim();
+    if trimmed_desc.is_empty() {
+        return Err(TodoError::EmptyDescription);
+    }
+    Ok(trimmed_desc.to_string())
+}
+
+fn validate_tag(tag: &str) -> Result<String, TodoError> {
+    let tag = tag.trim().to_lowercase();
+    if tag.is_empty() {
+        return Err(TodoError::InvalidTag(tr!("error-empty-tag")));
+    }
+    if tag.contains(char::is_whitespace) {
+        return Err(TodoError::InvalidTag(tr!("error-tag-spaces", tag = tag)));
+    }
+    Ok(tag)
+}
+
+// Names that already mean something in filters or todo.txt lines
+const RESERVED_FIELDS: [&str; 17] = [
+    "tag", "priority", "pri", "due", "start", "t", "followup", "created", "done", "completed", "status", "assignee", "rec",
+    "id", "parent", "uuid", "rank",
+];
+
+// Custom field names are lowercase, start with a letter and hold only
+// letters, digits, `-` and `_`, so they read back from filters and todo.txt
+fn validate_field_name(name: &str) -> Result<String, TodoError> {
+    let name = name.trim().to_lowercase();
+    let valid = name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+    if !valid {
+        return Err(TodoError::InvalidField(tr!("error-field-name", name = name)));
+    }
+    if RESERVED_FIELDS.contains(&name.as_str()) {
+        return Err(TodoError::InvalidField(tr!("error-field-built-in", name = name)));
+    }
+    Ok(name)
+}
+
+// Whether `name` can be used as a custom field name as it is
+fn is_field_name(name: &str) -> bool {
+    validate_field_name(name).is_ok_and(|valid| valid == name)
+}
+
+// Ties are broken by the order tasks were added
+fn sort_tasks(tasks: &mut [&Task], sort: SortOrder, weights: &urgency::Weights, today: Date) {
+    match sort {
+        SortOrder::Priority => tasks.sort_by_key(|task| (task.priority, task.id)),
+        // Tasks without a due date go last
+        SortOrder::Due => tasks.sort_by_key(|task| (task.due_date.is_none(), task.due_date, task.priority, task.id)),
+        SortOrder::Created => tasks.sort_by_key(|task| (task.created_at, task.id)),
+        SortOrder::Alpha => tasks.sort_by_cached_key(|task| (text::fold(&task.description), task.id)),
+        SortOrder::Id => tasks.sort_by_key(|task| task.id),
+        SortOrder::Manual => tasks.sort_by_key(|task| manual_key(task)),
+        SortOrder::Urgency => {
+            let mut scored: Vec<(f64, &Task)> = tasks.iter().map(|task| (weights.score(task, today), *task)).collect();
+            scored.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.id.cmp(&b.1.id)));
+            for (slot, (_, task)) in tasks.iter_mut().zip(scored) {
+                *slot = task;
+            }
+        }
+    }
+}
+
+// Where a task comes in the manual order
+fn manual_key(task: &Task) -> (bool, Option<usize>, usize) {
+    (task.rank.is_none(), task.rank, task.id)
+}
+
+// "3, 4, 7"
+fn join_ids(ids: &[usize]) -> String {
+    ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(", ")
+}
+
+/// Turns colored output on or off. Color is otherwise used unless the
+/// `NO_COLOR` environment variable is set.
+pub fn set_color_enabled(enabled: bool) {
+    if enabled {
+        colored::control::unset_override();
+    } else {
+        colored::control::set_override(false);
+    }
+}
+
+/// How urgent a task is. Declared from most to least urgent so sorting
+/// puts high priority first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    High,
+    #[default]
+    Medium,
+    Low,
+}
+
+impl Priority {
+    /// Parses `high`, `medium` or `low` (or their first letter). A blank
+    /// input means medium.
+    pub fn parse(input: &str) -> Result<Self, TodoError> {
+        match input.trim().to_lowercase().as_str() {
+            "high" | "h" => Ok(Priority::High),
+            "medium" | "m" | "" => Ok(Priority::Medium),
+            "low" | "l" => Ok(Priority::Low),
+            other => Err(TodoError::Parse(tr!("invalid-priority", value = other))),
+        }
+    }
+
+    /// The priority's name in the language messages are shown in. Display
+    /// gives the English name, which is what files and input use.
+    pub fn label(&self) -> String {
+        match self {
+            Priority::High => tr!("priority-high"),
+            Priority::Medium => tr!("priority-medium"),
+            Priority::Low => tr!("priority-low"),
+        }
+    }
+}
+
+impl FromStr for Priority {
+    type Err = TodoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Priority::parse(s)
+    }
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Priority::High => "high",
+            Priority::Medium => "medium",
+            Priority::Low => "low",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Where a task stands, as a column of the board `todo board` shows.
+/// Declared in the order the columns are shown in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Column {
+    #[default]
+    Todo,
+    InProgress,
+    Blocked,
+    Done,
+}
+
+impl Column {
+    /// Every column, in the order they are shown in.
+    pub const ALL: [Column; 4] = [Column::Todo, Column::InProgress, Column::Blocked, Column::Done];
+
+    /// Parses `todo`, `in-progress`, `blocked` or `done`, ignoring case and
+    /// whether words are joined with `-`, `_` or a space.
+    pub fn parse(input: &str) -> Result<Self, TodoError> {
+        match input.trim().to_lowercase().replace(['_', ' '], "-").as_str() {
+            "todo" | "to-do" => Ok(Column::Todo),
+            "in-progress" | "doing" => Ok(Column::InProgress),
+            "blocked" => Ok(Column::Blocked),
+            "done" => Ok(Column::Done),
+            other => Err(TodoError::Parse(tr!("invalid-status", value = other))),
+        }
+    }
+
+    /// The column's name in the language messages are shown in. Display
+    /// gives the English name, which is what files and input use.
+    pub fn label(&self) -> String {
+        match self {
+            Column::Todo => tr!("status-pending"),
+            Column::InProgress => tr!("status-in-progress"),
+            Column::Blocked => tr!("status-blocked"),
+            Column::Done => tr!("status-completed"),
+        }
+    }
+}
+
+impl FromStr for Column {
+    type Err = TodoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Column::parse(s)
+    }
+}
+
+impl fmt::Display for Column {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Column::Todo => "todo",
+            Column::InProgress => "in-progress",
+            Column::Blocked => "blocked",
+            Column::Done => "done",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// The order tasks are listed in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Highest priority first.
+    #[default]
+    Priority,
+    /// Soonest due date first.
+    Due,
+    /// Oldest first.
+    Created,
+    /// Alphabetically by description.
+    Alpha,
+    /// By ID, which is the order tasks were added in.
+    Id,
+    /// Most urgent first, by the score from [`urgency::Weights`].
+    Urgency,
+    /// The order set with [`TodoList::move_before`] and
+    /// [`TodoList::move_to_top`].
+    Manual,
+}
+
+impl SortOrder {
+    /// Parses `priority`, `due`, `created`, `alpha`, `id`, `urgency` or
+    /// `manual`.
+    pub fn parse(input: &str) -> Result<Self, TodoError> {
+        match input.trim().to_lowercase().as_str() {
+            "priority" => Ok(SortOrder::Priority),
+            "due" => Ok(SortOrder::Due),
+            "created" => Ok(SortOrder::Created),
+            "alpha" => Ok(SortOrder::Alpha),
+            "id" => Ok(SortOrder::Id),
+            "urgency" => Ok(SortOrder::Urgency),
+            "manual" => Ok(SortOrder::Manual),
+            other => Err(TodoError::Parse(tr!("invalid-sort-order", value = other))),
+        }
+    }
+}
+
+impl FromStr for SortOrder {
+    type Err = TodoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SortOrder::parse(s)
+    }
+}
+
+impl fmt::Display for SortOrder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            SortOrder::Priority => "priority",
+            SortOrder::Due => "due",
+            SortOrder::Created => "created",
+            SortOrder::Alpha => "alpha",
+            SortOrder::Id => "id",
+            SortOrder::Urgency => "urgency",
+            SortOrder::Manual => "manual",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A single entry in the todo list.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Task {
+    pub id: usize,
+    /// Identifies the task across copies of the list when merging or
+    /// syncing. Unlike `id` it never changes once given.
+    #[serde(default = "Uuid::new_v4")]
+    pub uuid: Uuid,
+    pub description: String,
+    pub completed: bool,
+    /// The board column of an open task, never [`Column::Done`]: a
+    /// completed task is in that one and keeps [`Column::Todo`] here.
+    /// [`Task::set_status`] sets this and `completed` together, and
+    /// [`Task::status`] gives the column a task is really in.
+    #[serde(default)]
+    pub column: Column,
+    #[serde(default)]
+    pub due_date: Option<Date>,
+    #[serde(default)]
+    pub priority: Priority,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub recurrence: Option<Recurrence>,
+    /// The ID of the task this one was scheduled from when a recurring task
+    /// was completed.
+    #[serde(default)]
+    pub recurs_from: Option<usize>,
+    /// The day of the month a monthly task is really due on when a shorter
+    /// month moved its due date earlier, so the next occurrence goes back
+    /// to it. Setting the due date clears it.
+    #[serde(default)]
+    pub recurs_on_day: Option<u32>,
+    /// The ID of the task this one is a subtask of.
+    #[serde(default)]
+    pub parent: Option<usize>,
+    /// The IDs of tasks that have to be completed before this one can be.
+    #[serde(default)]
+    pub depends_on: Vec<usize>,
+    /// Free-form, possibly multi-line notes kept apart from the description.
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// Files, as absolute paths, and URLs that go with the task.
+    #[serde(default)]
+    pub attachments: Vec<String>,
+    /// The due date a reminder was last sent for, so each due date is only
+    /// reminded about once.
+    #[serde(default)]
+    pub reminded: Option<Date>,
+    /// The due date the task was last announced as overdue for, so it is
+    /// only announced once for each.
+    #[serde(default)]
+    pub announced_overdue: Option<Date>,
+    /// When the task was added. Tasks from before this was recorded have none.
+    #[serde(default)]
+    pub created_at: Option<Timestamp>,
+    /// When the task was last completed. Reopening the task keeps it.
+    #[serde(default)]
+    pub completed_at: Option<Timestamp>,
+    /// Each time the task was completed or reopened, oldest first.
+    #[serde(default)]
+    pub status_log: Vec<StatusChange>,
+    /// When the task was moved to the trash, for tasks in the trash.
+    #[serde(default)]
+    pub deleted_at: Option<Timestamp>,
+    /// The task is left out of listings before this day.
+    #[serde(default)]
+    pub hidden_until: Option<Date>,
+    /// The day the task can be started on. Until then it is left out of
+    /// listings, like a snoozed task, but its due date stays as it is.
+    #[serde(default)]
+    pub start_date: Option<Date>,
+    /// Remarks added over time, such as progress made, oldest first.
+    #[serde(default)]
+    pub comments: Vec<Comment>,
+    /// When the task last came up in a review.
+    #[serde(default)]
+    pub last_reviewed: Option<Timestamp>,
+    /// The stretches of time spent working on the task, oldest first. Only
+    /// the last can still be running.
+    #[serde(default)]
+    pub time_log: Vec<Interval>,
+    /// How many pomodoros of work on the task have been finished.
+    #[serde(default)]
+    pub pomodoros: u32,
+    /// How much effort the task is expected to take.
+    #[serde(default)]
+    pub estimate: Option<Estimate>,
+    /// Fields the user made up, like `customer` or `ticket`, by name.
+    #[serde(default)]
+    pub fields: BTreeMap<String, String>,
+    /// Who is doing the task, for lists shared between people.
+    #[serde(default)]
+    pub assignee: Option<String>,
+    /// Set while the task is waiting on someone else, such as a reply or a
+    /// piece of work handed off. Completing the task clears it.
+    #[serde(default)]
+    pub waiting: Option<Waiting>,
+    /// Where the task comes in the list's manual order. Tasks never moved
+    /// have none and come after those that have been, by ID.
+    #[serde(default)]
+    pub rank: Option<usize>,
+}
+
+/// A task being completed, or reopened after it was.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusChange {
+    pub at: Timestamp,
+    /// Whether the task was completed, rather than reopened.
+    pub completed: bool,
+}
+
+/// A stretch of time spent on a task, from starting its timer to stopping it.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Interval {
+    pub start: Timestamp,
+    /// None while the timer is still running.
+    pub end: Option<Timestamp>,
+}
+
+impl Interval {
+    /// How long the interval lasted, or has lasted so far at `now`, in
+    /// seconds.
+    pub fn seconds(&self, now: Timestamp) -> i64 {
+        (self.end.unwrap_or(now).seconds() - self.start.seconds()).max(0)
+    }
+}
+
+/// What a task is waiting for: who is to get back, since when, and the day
+/// to chase them on if they have not.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Waiting {
+    #[serde(default)]
+    pub on: Option<String>,
+    pub since: Timestamp,
+    #[serde(default)]
+    pub follow_up: Option<Date>,
+}
+
+/// A remark on a task, with when it was made.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Comment {
+    pub at: Timestamp,
+    pub text: String,
+}
+
+impl Task {
+    /// Whether the task carries `tag`, ignoring case. Tags are kept in
+    /// lowercase, so `tag` is lowered the same way, `ÄRGER` to `ärger`.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        let tag = tag.trim().to_lowercase();
+        self.tags.contains(&tag)
+    }
+
+    /// The contexts the task can be done in, written as `@phone` or
+    /// `@home` in its description as todo.txt has them, in lowercase and
+    /// without the `@`.
+    pub fn contexts(&self) -> Vec<String> {
+        let mut contexts: Vec<String> = Vec::new();
+        for word in self.description.split_whitespace() {
+            if let Some(context) = word.strip_prefix('@').filter(|context| !context.is_empty()) {
+                let context = context.to_lowercase();
+                if !contexts.contains(&context) {
+                    contexts.push(context);
+                }
+            }
+        }
+        contexts
+    }
+
+    /// Whether the task's description names `context`, ignoring case.
+    pub fn has_context(&self, context: &str) -> bool {
+        let context = context.trim().to_lowercase();
+        self.contexts().contains(&context)
+    }
+
+    /// Whether the task is still open and its due date is before `today`.
+    pub fn is_overdue(&self, today: Date) -> bool {
+        !self.completed && self.due_date.is_some_and(|due| due < today)
+    }
+
+    /// Whether the task was completed at `at`, in seconds, going by its
+    /// status log, or by when it was completed for tasks from before the log
+    /// was kept.
+    pub fn was_completed_at(&self, at: i64) -> bool {
+        let completed_by = self.completed_at.is_none_or(|completed_at| completed_at.seconds() <= at);
+        match (self.status_log.iter().rev().find(|change| change.at.seconds() <= at), self.status_log.first()) {
+            (Some(change), _) => change.completed,
+            // Reopened after a completion from before the log was kept
+            (None, Some(first)) => !first.completed && completed_by,
+            (None, None) => self.completed && completed_by,
+        }
+    }
+
+    /// The board column the task is in.
+    pub fn status(&self) -> Column {
+        if self.completed {
+            Column::Done
+        } else {
+            self.column
+        }
+    }
+
+    /// Puts the task in a column of the board, completing it for
+    /// [`Column::Done`] and reopening it for any other. Only the two fields
+    /// change; [`TodoList::move_to_column`] also records when.
+    pub fn set_status(&mut self, column: Column) {
+        self.completed = column == Column::Done;
+        self.column = if self.completed { Column::Todo } else { column };
+    }
+
+    // Brings a task read from a file or another app back in line with
+    // `set_status`, where it says it is done in one field but not the other
+    fn normalize_status(&mut self) {
+        let done = self.completed || self.column == Column::Done;
+        self.set_status(if done { Column::Done } else { self.column });
+    }
+
+    /// Whether the task is still open and snoozed past `today`.
+    pub fn is_snoozed(&self, today: Date) -> bool {
+        !self.completed && self.hidden_until.is_some_and(|until| until > today)
+    }
+
+    /// Whether the task is still open and its start date is after `today`.
+    pub fn is_deferred(&self, today: Date) -> bool {
+        !self.completed && self.start_date.is_some_and(|start| start > today)
+    }
+
+    /// Whether listings leave the task out on `today`, as it is snoozed or
+    /// waiting for its start date.
+    pub fn is_hidden(&self, today: Date) -> bool {
+        self.is_snoozed(today) || self.is_deferred(today)
+    }
+
+    /// Whether the task is still open and waiting on someone else.
+    pub fn is_waiting(&self) -> bool {
+        !self.completed && self.waiting.is_some()
+    }
+
+    /// Whether the task is waiting and its follow-up day is `today` or past.
+    pub fn needs_follow_up(&self, today: Date) -> bool {
+        !self.completed && self.waiting.as_ref().and_then(|waiting| waiting.follow_up).is_some_and(|day| day <= today)
+    }
+
+    /// Whether the task is assigned to `name`, ignoring case.
+    pub fn is_assigned_to(&self, name: &str) -> bool {
+        self.assignee.as_deref().is_some_and(|assignee| assignee.eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Whether the task's timer is running.
+    pub fn is_timing(&self) -> bool {
+        self.time_log.last().is_some_and(|interval| interval.end.is_none())
+    }
+
+    /// The total time spent on the task as of `now`, in seconds.
+    pub fn time_spent(&self, now: Timestamp) -> i64 {
+        self.time_log.iter().map(|interval| interval.seconds(now)).sum()
+    }
+
+    // Ends the running interval, if there is one, at `now`
+    fn stop_timer(&mut self, now: Timestamp) {
+        if let Some(interval) = self.time_log.last_mut().filter(|interval| interval.end.is_none()) {
+            interval.end = Some(now);
+        }
+    }
+}
+
+/// A line of a file that could not be read as a task.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejected {
+    /// The line's number, counting from 1.
+    pub line: usize,
+    /// The line as it is in the file.
+    pub text: String,
+    /// What is wrong with it.
+    pub error: String,
+}
+
+/// What reading a file left out.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseReport {
+    /// The lines that could not be read, in order.
+    pub rejected: Vec<Rejected>,
+}
+
+impl ParseReport {
+    /// Whether every line was read.
+    pub fn is_clean(&self) -> bool {
+        self.rejected.is_empty()
+    }
+
+    /// The rejected lines as they are kept in a rejects file: each after a
+    /// comment saying where it was and what is wrong with it, so it can be
+    /// fixed and added back by hand.
+    ///
+    /// ```text
+    /// # line 3: Invalid ID 'x'
+    /// x,pending,buy milk
+    /// ```
+    pub fn rejects(&self) -> String {
+        self.rejected.iter().map(|rejected| format!("# line {}: {}\n{}\n", rejected.line, rejected.error, rejected.text)).collect()
+    }
+}
+
+impl fmt::Display for ParseReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let lines: Vec<String> = self.rejected.iter().map(|rejected| rejected.line.to_string()).collect();
+        match lines.as_slice() {
+            [line] => write!(f, "{}", tr!("unreadable-lines", count = 1, lines = line.as_str())),
+            _ => write!(f, "{}", tr!("unreadable-lines", count = lines.len(), lines = lines.join(", "))),
+        }
+    }
+}
+
+/// The list of tasks along with the ID that will be given to the next one.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TodoList {
+    tasks: Vec<Task>,
+    next_id: usize,
+    #[serde(skip)]
+    history: History,
+    // Where each task is in `tasks`, by ID, so lookups need no scan. Kept up
+    // to date by `push_task` and rebuilt by `reindex` after anything else
+    // that moves tasks around.
+    #[serde(skip)]
+    index: HashMap<usize, usize>,
+    #[serde(skip, default = "default_auto_complete_parents")]
+    auto_complete_parents: bool,
+    #[serde(skip)]
+    sort_order: SortOrder,
+    #[serde(skip)]
+    urgency: urgency::Weights,
+    #[serde(skip)]
+    show_snoozed: bool,
+    #[serde(skip)]
+    date_format: DateFormat,
+    #[serde(skip, default = "default_relative_dates")]
+    relative_dates: bool,
+    #[serde(skip)]
+    colors: colors::ColorScheme,
+    #[serde(skip)]
+    wip_limits: BTreeMap<Column, usize>,
+    #[serde(skip)]
+    width: Option<usize>,
+    #[serde(skip, default = "default_clock")]
+    clock: Arc<dyn Clock>,
+}
+
+fn default_auto_complete_parents() -> bool {
+    true
+}
+
+fn default_relative_dates() -> bool {
+    true
+}
+
+fn default_clock() -> Arc<dyn Clock> {
+    Arc::new(SystemClock)
+}
+
+impl Default for TodoList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> IntoIterator for &'a TodoList {
+    type Item = &'a Task;
+    type IntoIter = std::slice::Iter<'a, Task>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tasks.iter()
+    }
+}
+
+impl TodoList {
+    /// Creates an empty list whose first task will get ID 1.
+    pub fn new() -> Self {
+        TodoList {
+            tasks: Vec::new(),
+            next_id: 1,
+            history: History::default(),
+            index: HashMap::new(),
+            auto_complete_parents: default_auto_complete_parents(),
+            sort_order: SortOrder::default(),
+            urgency: urgency::Weights::default(),
+            show_snoozed: false,
+            date_format: DateFormat::default(),
+            relative_dates: default_relative_dates(),
+            colors: colors::ColorScheme::default(),
+            wip_limits: BTreeMap::new(),
+            width: None,
+            clock: default_clock(),
+        }
+    }
+
+    /// Sets where the list gets the time from, such as a fixed clock in a
+    /// test. Lists use the system's clock unless told otherwise.
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.clock = clock;
+    }
+
+    /// The current time by the list's clock.
+    pub fn now(&self) -> Timestamp {
+        self.clock.now()
+    }
+
+    /// Today's date in UTC by the list's clock.
+    pub fn today(&self) -> Date {
+        self.clock.today()
+    }
+
+    /// All tasks in the order they were added.
+    pub fn tasks(&self) -> &[Task] {
+        &self.tasks
+    }
+
+    /// Goes over all tasks in the order they were added, as `for task in
+    /// &todo_list` does.
+    pub fn iter(&self) -> std::slice::Iter<'_, Task> {
+        self.tasks.iter()
+    }
+
+    /// Goes over the tasks not completed yet, in the order they were added.
+    /// Snoozed and deferred tasks are included.
+    pub fn iter_pending(&self) -> impl Iterator<Item = &Task> + '_ {
+        self.tasks.iter().filter(|task| !task.completed)
+    }
+
+    /// Goes over the completed tasks, in the order they were added.
+    pub fn iter_completed(&self) -> impl Iterator<Item = &Task> + '_ {
+        self.tasks.iter().filter(|task| task.completed)
+    }
+
+    /// The task with the given ID, if there is one.
+    pub fn task(&self, id: usize) -> Option<&Task> {
+        self.index.get(&id).map(|&index| &self.tasks[index])
+    }
+
+    /// Looks up a task by its UUID.
+    pub fn task_by_uuid(&self, uuid: Uuid) -> Option<&Task> {
+        self.tasks.iter().find(|task| task.uuid == uuid)
+    }
+
+    /// Adds a task and returns its ID. The description is trimmed and must
+    /// not be empty.
+    pub fn add_task(
+        &mut self,
+        description: String,
+        due_date: Option<Date>,
+        priority: Priority,
+    ) -> Result<usize, TodoError> {
+        self.insert_new_task(description, due_date, priority, None)
+    }
+
+    /// Adds a subtask under the task with ID `parent_id` and returns its ID.
+    /// The same rules as [`TodoList::add_task`] apply.
+    pub fn add_subtask(
+        &mut self,
+        parent_id: usize,
+        description: String,
+        due_date: Option<Date>,
+        priority: Priority,
+    ) -> Result<usize, TodoError> {
+        self.position(parent_id)?;
+        self.insert_new_task(description, due_date, priority, Some(parent_id))
+    }
+
+    /// The subtasks directly under the task with the given ID.
+    pub fn subtasks(&self, id: usize) -> Vec<&Task> {
+        self.tasks.iter().filter(|task| task.parent == Some(id)).collect()
+    }
+
+    /// The tasks with the given IDs followed by all of their subtasks, their
+    /// subtasks' subtasks and so on, each once. Unknown IDs are skipped.
+    pub fn with_subtasks(&self, ids: &[usize]) -> Vec<&Task> {
+        let mut seen = HashSet::new();
+        ids.iter()
+            .filter(|id| self.task(**id).is_some())
+            .flat_map(|&id| self.with_descendants(id))
+            .filter(|id| seen.insert(*id))
+            .filter_map(|id| self.task(id))
+            .collect()
+    }
+
+    /// Sets whether completing the last open subtask of a task also
+    /// completes that task. This is on by default.
+    pub fn set_auto_complete_parents(&mut self, enabled: bool) {
+        self.auto_complete_parents = enabled;
+    }
+
+    /// Sets the order tasks are listed in. Highest priority first is the
+    /// default.
+    pub fn set_sort_order(&mut self, sort: SortOrder) {
+        self.sort_order = sort;
+    }
+
+    /// Sets how urgency is scored, for sorting by urgency and for
+    /// [`TodoList::format_task`].
+    pub fn set_urgency_weights(&mut self, weights: urgency::Weights) {
+        self.urgency = weights;
+    }
+
+    /// Sets how due dates are written by [`TodoList::format_task`], and in
+    /// listings when they are not relative. YYYY-MM-DD is the default.
+    pub fn set_date_format(&mut self, format: DateFormat) {
+        self.date_format = format;
+    }
+
+    /// Sets whether listings give due dates as how far off they are, like
+    /// "due in 2 days", rather than as the date itself. They do by default;
+    /// [`TodoList::format_task`] always gives the date.
+    pub fn set_relative_dates(&mut self, relative: bool) {
+        self.relative_dates = relative;
+    }
+
+    /// Sets the colors listings use.
+    pub fn set_color_scheme(&mut self, colors: colors::ColorScheme) {
+        self.colors = colors;
+    }
+
+    /// Sets how many columns wide listings may be, such as the terminal's
+    /// width: descriptions that would make a row wider are cut short.
+    /// Without a width, rows are as wide as they need to be.
+    pub fn set_width(&mut self, width: usize) {
+        self.width = Some(width);
+    }
+
+    /// Sets how many tasks a column of the board is meant to hold at most,
+    /// its work-in-progress limit. Going over is allowed, but the board and
+    /// [`TodoList::over_wip_limit`] point it out.
+    pub fn set_wip_limit(&mut self, column: Column, limit: usize) {
+        self.wip_limits.insert(column, limit);
+    }
+
+    /// The limit set on `column` with [`TodoList::set_wip_limit`], if any.
+    pub fn wip_limit(&self, column: Column) -> Option<usize> {
+        self.wip_limits.get(&column).copied()
+    }
+
+    /// How many tasks are in `column` and its limit, when there are more
+    /// than the limit.
+    pub fn over_wip_limit(&self, column: Column) -> Option<(usize, usize)> {
+        let limit = self.wip_limit(column)?;
+        let count = self.tasks.iter().filter(|task| task.status() == column).count();
+        (count > limit).then_some((count, limit))
+    }
+
+    /// Sets whether snoozed tasks, and tasks whose start date has not come
+    /// yet, are listed. They are left out by default.
+    pub fn set_show_snoozed(&mut self, show: bool) {
+        self.show_snoozed = show;
+    }
+
+    /// Every task that is not snoozed or waiting for its start date, in the
+    /// list's sort order.
+    pub fn sorted_tasks(&self) -> Vec<&Task> {
+        self.sorted(self.listed().collect())
+    }
+
+    /// The tasks carrying `tag` that are not snoozed or waiting for their
+    /// start date, in the list's sort order.
+    pub fn tasks_with_tag(&self, tag: &str) -> Vec<&Task> {
+        self.sorted(self.listed().filter(|task| task.has_tag(tag)).collect())
+    }
+
+    // The tasks listings show, leaving out snoozed and deferred ones unless
+    // asked not to
+    fn listed(&self) -> impl Iterator<Item = &Task> {
+        let today = self.today();
+        self.tasks.iter().filter(move |task| self.show_snoozed || !task.is_hidden(today))
+    }
+
+    /// An open task whose description is much the same as `description`,
+    /// ignoring case, punctuation and small typos, if there is one.
+    pub fn find_similar(&self, description: &str) -> Option<&Task> {
+        self.tasks.iter().find(|task| !task.completed && similar::similar(&task.description, description))
+    }
+
+    /// The open tasks a review goes through, leaving out snoozed ones, with
+    /// those never reviewed first and then the least recently reviewed.
+    pub fn review_queue(&self) -> Vec<&Task> {
+        let mut tasks: Vec<&Task> = self.listed().filter(|task| !task.completed).collect();
+        tasks.sort_by_key(|task| (task.last_reviewed, task.id));
+        tasks
+    }
+
+    /// The ID of the task whose timer is running, if any.
+    pub fn running_timer(&self) -> Option<usize> {
+        self.tasks.iter().find(|task| task.is_timing()).map(|task| task.id)
+    }
+
+    /// Starts timing the task with the given ID, first stopping the timer
+    /// of any other task, as only one runs at a time. Returns the ID of the
+    /// task that was being timed.
+    pub fn start_timer(&mut self, id: usize) -> Result<Option<usize>, TodoError> {
+        let task = self.task(id).ok_or(TodoError::NotFound(id))?;
+        if task.completed {
+            return Err(TodoError::AlreadyCompleted(id));
+        }
+        let running = self.running_timer();
+        if running == Some(id) {
+            return Err(TodoError::TimerRunning(id));
+        }
+
+        let now = self.now();
+        let index = self.position(id)?;
+        let mut changes = Vec::new();
+        if let Some(running) = running {
+            let running_index = self.position(running)?;
+            changes.push(self.change_task(running_index, |task| task.stop_timer(now)));
+        }
+        changes.push(self.change_task(index, |task| task.time_log.push(Interval { start: now, end: None })));
+        self.history.record(tr!("change-start-timer", id = id), changes);
+        Ok(running)
+    }
+
+    /// Stops the running timer and returns the ID of the task it was timing
+    /// along with how long this stretch lasted, in seconds.
+    pub fn stop_timer(&mut self) -> Result<(usize, i64), TodoError> {
+        let id = self.running_timer().ok_or(TodoError::NoTimer)?;
+        let now = self.now();
+        self.update_task(id, tr!("change-stop-timer", id = id), |task| task.stop_timer(now))?;
+        let seconds = self.task(id).and_then(|task| task.time_log.last()).map_or(0, |interval| interval.seconds(now));
+        Ok((id, seconds))
+    }
+
+    /// Counts one more finished pomodoro for the task with the given ID.
+    pub fn add_pomodoro(&mut self, id: usize) -> Result<(), TodoError> {
+        self.update_task(id, tr!("change-pomodoro", id = id), |task| task.pomodoros += 1)
+    }
+
+    /// Records that the task with the given ID was reviewed just now.
+    pub fn mark_reviewed(&mut self, id: usize) -> Result<(), TodoError> {
+        let now = self.now();
+        self.update_task(id, tr!("change-review", id = id), |task| task.last_reviewed = Some(now))
+    }
+
+    /// The tasks whose description or notes contain `query`, ignoring case
+    /// and accents, in the list's sort order: `cafe` finds "Café". Only
+    /// whole characters match, so an emoji is never found inside another
+    /// one.
+    pub fn search(&self, query: &str) -> Vec<&Task> {
+        let query = text::fold(query.trim());
+        self.search_with(|text| text::contains(&text::fold(text), &query))
+    }
+
+    /// The tasks whose description or notes contain `query` just as it is
+    /// written, case and accents and all, in the list's sort order.
+    pub fn search_exact(&self, query: &str) -> Vec<&Task> {
+        let query = query.trim();
+        self.search_with(|text| text::contains(text, query))
+    }
+
+    /// The tasks whose description or notes match the regular expression
+    /// `pattern`, in the list's sort order. The syntax is the common part of
+    /// the regex crate's, and like it matches case unless the pattern starts
+    /// with `(?i)`.
+    pub fn search_regex(&self, pattern: &str) -> Result<Vec<&Task>, TodoError> {
+        let pattern = Pattern::parse(pattern)?;
+        Ok(self.search_with(|text| pattern.is_match(text)))
+    }
+
+    fn search_with(&self, matches: impl Fn(&str) -> bool) -> Vec<&Task> {
+        self.sorted(self.tasks.iter()
+            .filter(|task| matches(&task.description) || task.notes.as_deref().is_some_and(&matches))
+            .collect())
+    }
+
+    fn sorted<'a>(&self, mut tasks: Vec<&'a Task>) -> Vec<&'a Task> {
+        sort_tasks(&mut tasks, self.sort_order, &self.urgency, self.today());
+        tasks
+    }
+
+    /// Formats `tasks`, taken from this list, a line each in the order
+    /// given, as [`render::task_list`] does.
+    pub fn format_task_list(&self, tasks: &[&Task]) -> String {
+        render::task_list(self, tasks)
+    }
+
+    /// Formats `tasks` grouped by project, as [`render::by_project`] does.
+    pub fn format_by_project(&self, tasks: &[&Task]) -> String {
+        render::by_project(self, tasks)
+    }
+
+    /// Formats `tasks` under a heading for each column of the board, as
+    /// [`render::board`] does.
+    pub fn format_board(&self, tasks: &[&Task]) -> String {
+        render::board(self, tasks)
+    }
+
+    /// The heading of `column` on the board, showing `count` tasks: its
+    /// name, the count and its WIP limit, if it has one, with a warning when
+    /// the whole list has more tasks in it than the limit.
+    pub fn board_heading(&self, column: Column, count: usize) -> String {
+        let name = match column {
+            Column::Todo => tr!("board-todo"),
+            Column::InProgress => tr!("board-in-progress"),
+            Column::Blocked => tr!("board-blocked"),
+            Column::Done => tr!("board-done"),
+        };
+        match (self.wip_limit(column), self.over_wip_limit(column)) {
+            (Some(limit), Some(_)) => tr!("board-heading-over-limit", column = name, count = count, limit = limit),
+            (Some(limit), None) => tr!("board-heading-limit", column = name, count = count, limit = limit),
+            (None, _) => tr!("board-heading", column = name, count = count),
+        }
+    }
+
+    /// Every tag used by at least one task, sorted and without duplicates.
+    pub fn list_tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self.tasks.iter()
+            .flat_map(|task| task.tags.iter().cloned())
+            .collect();
+        tags.sort();
+        tags.dedup();
+        tags
+    }
+
+    /// Every context named by at least one open task, sorted and without
+    /// duplicates.
+    pub fn list_contexts(&self) -> Vec<String> {
+        let mut contexts: Vec<String> = self.tasks.iter()
+            .filter(|task| !task.completed)
+            .flat_map(Task::contexts)
+            .collect();
+        contexts.sort();
+        contexts.dedup();
+        contexts
+    }
+
+    /// Attaches a tag to the task with the given ID. Tags are stored in
+    /// lowercase and cannot contain spaces.
+    pub fn add_tag(&mut self, id: usize, tag: &str) -> Result<(), TodoError> {
+        let tag = validate_tag(tag)?;
+        if self.tasks[self.position(id)?].has_tag(&tag) {
+            return Err(TodoError::DuplicateTag { id, tag });
+        }
+        self.update_task(id, tr!("change-tag", tag = tag.as_str(), id = id), |task| task.tags.push(tag))
+    }
+
+    /// Detaches a tag from the task with the given ID.
+    pub fn remove_tag(&mut self, id: usize, tag: &str) -> Result<(), TodoError> {
+        let tag = tag.trim().to_lowercase();
+        if !self.tasks[self.position(id)?].has_tag(&tag) {
+            return Err(TodoError::MissingTag { id, tag });
+        }
+        self.update_task(id, tr!("change-untag", tag = tag.as_str(), id = id), |task| {
+            task.tags.retain(|t| *t != tag)
+        })
+    }
+
+    /// Marks the task with the given ID as completed. Completing a recurring
+    /// task also adds its next occurrence, whose ID is returned.
+    /// A task that depends on open tasks cannot be completed.
+    pub fn complete_task(&mut self, id: usize) -> Result<Option<usize>, TodoError> {
+        let next_ids = self.complete_batch(&[id], tr!("change-complete", id = id), false)?;
+        Ok(next_ids[0])
+    }
+
+    /// Completes every task in `ids` like [`TodoList::complete_task`], as a
+    /// single change for undo. Returns the next occurrence's ID for each.
+    /// Tasks may depend on others in the same batch.
+    pub fn complete_tasks(&mut self, ids: &[usize]) -> Result<Vec<Option<usize>>, TodoError> {
+        self.complete_batch(ids, tr!("change-complete-many", count = ids.len()), false)
+    }
+
+    /// Completes tasks like [`TodoList::complete_tasks`], even those that
+    /// depend on open tasks.
+    pub fn force_complete_tasks(&mut self, ids: &[usize]) -> Result<Vec<Option<usize>>, TodoError> {
+        self.complete_batch(ids, tr!("change-complete-many", count = ids.len()), true)
+    }
+
+    fn complete_batch(&mut self, ids: &[usize], label: String, force: bool) -> Result<Vec<Option<usize>>, TodoError> {
+        // Nothing is changed unless every task can be completed
+        for &id in ids {
+            if self.tasks[self.position(id)?].completed {
+                return Err(TodoError::AlreadyCompleted(id));
+            }
+            let blockers: Vec<usize> = self.blockers(id).into_iter().filter(|blocker| !ids.contains(blocker)).collect();
+            if !force && !blockers.is_empty() {
+                return Err(TodoError::Blocked { id, by: blockers });
+            }
+        }
+
+        let mut changes = Vec::new();
+        let mut next_ids = Vec::new();
+        for &id in ids {
+            let index = self.position(id)?;
+            // An earlier task in the batch may have completed this one as its parent
+            if self.tasks[index].completed {
+                next_ids.push(None);
+                continue;
+            }
+            next_ids.push(self.complete_at(index, &mut changes));
+            self.complete_parents(index, &mut changes);
+        }
+
+        self.history.record(label, changes);
+        Ok(next_ids)
+    }
+
+    /// Marks the completed task with the given ID as open again, for a
+    /// completion made by mistake. The completion stays in the task's status
+    /// log, so statistics about the past are unchanged. A parent completed
+    /// along with its subtasks is reopened too; the next occurrence of a
+    /// recurring task, if one was added, is left in the list.
+    pub fn reopen_task(&mut self, id: usize) -> Result<(), TodoError> {
+        let index = self.position(id)?;
+        if !self.tasks[index].completed {
+            return Err(TodoError::NotCompleted(id));
+        }
+        let changes = self.reopen_at(index);
+        self.history.record(tr!("change-reopen", id = id), changes);
+        Ok(())
+    }
+
+    /// Moves the task with the given ID to a column of the board. Moving it
+    /// to [`Column::Done`] completes it, as [`TodoList::complete_task`]
+    /// does, returning the next occurrence's ID for a recurring task, and
+    /// moving a completed task anywhere else reopens it.
+    pub fn move_to_column(&mut self, id: usize, column: Column) -> Result<Option<usize>, TodoError> {
+        if column == Column::Done {
+            return self.complete_task(id);
+        }
+        let index = self.position(id)?;
+        let mut changes = if self.tasks[index].completed { self.reopen_at(index) } else { Vec::new() };
+        changes.push(self.change_task(index, |task| task.set_status(column)));
+        self.history.record(tr!("change-move-column", column = column.label(), id = id), changes);
+        Ok(None)
+    }
+
+    // Reopens the completed task at `index`, and the parents its completion
+    // completed, returning the changes for the history
+    fn reopen_at(&mut self, index: usize) -> Vec<Change> {
+        let now = self.now();
+        let reopen = |task: &mut Task| {
+            task.set_status(Column::Todo);
+            task.status_log.push(StatusChange { at: now, completed: false });
+        };
+        let mut changes = vec![self.change_task(index, reopen)];
+        let mut parent = self.tasks[index].parent;
+        while let Some(parent_id) = parent.filter(|_| self.auto_complete_parents) {
+            let Ok(parent_index) = self.position(parent_id) else {
+                break;
+            };
+            if !self.tasks[parent_index].completed {
+                break;
+            }
+            changes.push(self.change_task(parent_index, reopen));
+            parent = self.tasks[parent_index].parent;
+        }
+        changes
+    }
+
+    // Completing the last open subtask completes its parent as well
+    fn complete_parents(&mut self, index: usize, changes: &mut Vec<Change>) {
+        let mut parent = self.tasks[index].parent;
+        while let Some(parent_id) = parent.filter(|_| self.auto_complete_parents) {
+            let Ok(parent_index) = self.position(parent_id) else {
+                break;
+            };
+            let all_done = self.subtasks(parent_id).iter().all(|task| task.completed);
+            if !all_done || self.tasks[parent_index].completed || !self.blockers(parent_id).is_empty() {
+                break;
+            }
+            self.complete_at(parent_index, changes);
+            parent = self.tasks[parent_index].parent;
+        }
+    }
+
+    /// Makes the task with ID `id` wait on the task with ID `on`, so it
+    /// cannot be completed while `on` is open. A dependency that would make a
+    /// task wait on itself, directly or through others, is refused.
+    pub fn add_dependency(&mut self, id: usize, on: usize) -> Result<(), TodoError> {
+        let index = self.position(id)?;
+        self.position(on)?;
+        if self.tasks[index].depends_on.contains(&on) {
+            return Ok(());
+        }
+        if self.depends_on_transitively(on, id) {
+            return Err(TodoError::DependencyCycle { id, on });
+        }
+        self.update_task(id, tr!("change-depend", on = on, id = id), |task| task.depends_on.push(on))
+    }
+
+    /// Stops the task with ID `id` waiting on the task with ID `on`.
+    pub fn remove_dependency(&mut self, id: usize, on: usize) -> Result<(), TodoError> {
+        if !self.tasks[self.position(id)?].depends_on.contains(&on) {
+            return Err(TodoError::MissingDependency { id, on });
+        }
+        self.update_task(id, tr!("change-undepend", on = on, id = id), |task| {
+            task.depends_on.retain(|&dependency| dependency != on)
+        })
+    }
+
+    /// The IDs of the open tasks the task with the given ID depends on.
+    /// Dependencies on tasks no longer in the list, such as archived ones,
+    /// do not block.
+    pub fn blockers(&self, id: usize) -> Vec<usize> {
+        let Some(task) = self.task(id) else {
+            return Vec::new();
+        };
+        task.depends_on.iter()
+            .copied()
+            .filter(|&dependency| self.task(dependency).is_some_and(|task| !task.completed))
+            .collect()
+    }
+
+    // Whether `id` is `target` or waits on it, directly or through other tasks
+    fn depends_on_transitively(&self, id: usize, target: usize) -> bool {
+        let mut seen = HashSet::new();
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            if id == target {
+                return true;
+            }
+            if seen.insert(id) {
+                if let Some(task) = self.task(id) {
+                    stack.extend(task.depends_on.iter().copied());
+                }
+            }
+        }
+        false
+    }
+
+    /// Open tasks due on or before `last_day` that no reminder has been sent
+    /// for yet, soonest first.
+    pub fn pending_reminders(&self, last_day: Date) -> Vec<&Task> {
+        let mut tasks: Vec<&Task> = self.tasks.iter()
+            .filter(|task| !task.completed)
+            .filter(|task| task.due_date.is_some_and(|due| due <= last_day && task.reminded != Some(due)))
+            .collect();
+        tasks.sort_by_key(|task| (task.due_date, task.priority, task.id));
+        tasks
+    }
+
+    /// Records that a reminder was sent for the task's current due date.
+    /// This is bookkeeping, so it is not recorded for undo.
+    pub fn mark_reminded(&mut self, id: usize) -> Result<(), TodoError> {
+        let index = self.position(id)?;
+        let task = &mut self.tasks[index];
+        task.reminded = task.due_date;
+        Ok(())
+    }
+
+    /// Open tasks that were due before `today` and have not been announced
+    /// as overdue yet, longest overdue first.
+    pub fn newly_overdue(&self, today: Date) -> Vec<&Task> {
+        let mut tasks: Vec<&Task> = self.tasks.iter()
+            .filter(|task| task.is_overdue(today) && task.announced_overdue != task.due_date)
+            .collect();
+        tasks.sort_by_key(|task| (task.due_date, task.priority, task.id));
+        tasks
+    }
+
+    /// Records that the task was announced as overdue for its current due
+    /// date. Like [`TodoList::mark_reminded`], it is not recorded for undo.
+    pub fn mark_announced_overdue(&mut self, id: usize) -> Result<(), TodoError> {
+        let index = self.position(id)?;
+        let task = &mut self.tasks[index];
+        task.announced_overdue = task.due_date;
+        Ok(())
+    }
+
+    /// Replaces the notes of the task with the given ID. Notes that are only
+    /// whitespace are cleared.
+    pub fn set_notes(&mut self, id: usize, notes: Option<String>) -> Result<(), TodoError> {
+        let notes = notes
+            .map(|notes| notes.trim_end().to_string())
+            .filter(|notes| !notes.trim().is_empty());
+        self.update_task(id, tr!("change-notes", id = id), |task| task.notes = notes)
+    }
+
+    /// Adds a file path or URL to the end of the task's attachments, unless
+    /// it is there already.
+    pub fn attach(&mut self, id: usize, attachment: &str) -> Result<(), TodoError> {
+        let attachment = attachment.trim().to_string();
+        if attachment.is_empty() {
+            return Err(TodoError::Parse(tr!("empty-attachment")));
+        }
+        self.update_task(id, tr!("change-attach", id = id), |task| {
+            if !task.attachments.contains(&attachment) {
+                task.attachments.push(attachment);
+            }
+        })
+    }
+
+    /// Removes the task's attachment with this number, counting from 1 as
+    /// `show` lists them, and returns it.
+    pub fn detach(&mut self, id: usize, number: usize) -> Result<String, TodoError> {
+        let attachment = self.attachment(id, number)?.to_string();
+        self.update_task(id, tr!("change-detach", id = id), |task| {
+            task.attachments.remove(number - 1);
+        })?;
+        Ok(attachment)
+    }
+
+    /// The task's attachment with this number, counting from 1.
+    pub fn attachment(&self, id: usize, number: usize) -> Result<&str, TodoError> {
+        let task = self.task(id).ok_or(TodoError::NotFound(id))?;
+        number
+            .checked_sub(1)
+            .and_then(|index| task.attachments.get(index))
+            .map(String::as_str)
+            .ok_or(TodoError::MissingAttachment { id, number, count: task.attachments.len() })
+    }
+
+    /// Adds a comment made now to the end of the task's comments.
+    pub fn add_comment(&mut self, id: usize, text: &str) -> Result<(), TodoError> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(TodoError::EmptyComment);
+        }
+        let comment = Comment {
+            at: self.now(),
+            text: text.to_string(),
+        };
+        self.update_task(id, tr!("change-comment", id = id), |task| task.comments.push(comment))
+    }
+
+    /// Formats everything known about the task with the given ID, as
+    /// [`render::task_details`] does.
+    pub fn format_task(&self, id: usize) -> Result<String, TodoError> {
+        let task = self.task(id).ok_or(TodoError::NotFound(id))?;
+        Ok(render::task_details(self, task))
+    }
+
+    /// Sets or clears how often the task with the given ID repeats.
+    pub fn set_recurrence(&mut self, id: usize, recurrence: Option<Recurrence>) -> Result<(), TodoError> {
+        self.update_task(id, tr!("change-recurrence", id = id), |task| task.recurrence = recurrence)
+    }
+
+    /// Replaces the description of the task with the given ID, keeping its
+    /// ID. The same rules as [`TodoList::add_task`] apply.
+    pub fn edit_task(&mut self, id: usize, new_description: String) -> Result<(), TodoError> {
+        let description = validate_description(&new_description)?;
+        self.update_task(id, tr!("change-edit", id = id), |task| task.description = description)
+    }
+
+    /// Moves the due date of the task with the given ID `days` days later,
+    /// counting from today when the task has no due date or is overdue,
+    /// and returns the new due date. With `hide` the task is also left out
+    /// of listings until then.
+    pub fn snooze(&mut self, id: usize, days: i64, hide: bool) -> Result<Date, TodoError> {
+        let index = self.position(id)?;
+        let today = self.today();
+        let from = self.tasks[index].due_date.filter(|due| *due > today).unwrap_or(today);
+        let due = from.add_days(days);
+        self.update_task(id, tr!("change-snooze", id = id), |task| {
+            task.due_date = Some(due);
+            task.hidden_until = if hide { Some(due) } else { None };
+        })?;
+        Ok(due)
+    }
+
+    /// Sets or clears the effort the task with the given ID is expected to
+    /// take.
+    pub fn set_estimate(&mut self, id: usize, estimate: Option<Estimate>) -> Result<(), TodoError> {
+        self.update_task(id, tr!("change-estimate", id = id), |task| task.estimate = estimate)
+    }
+
+    /// Sets a custom field, like `ticket` to `JIRA-42`, on the task with the
+    /// given ID, replacing any value it had. Names are stored in lowercase.
+    pub fn set_field(&mut self, id: usize, name: &str, value: &str) -> Result<(), TodoError> {
+        let name = validate_field_name(name)?;
+        let value = value.trim().to_string();
+        if value.is_empty() {
+            return Err(TodoError::InvalidField(tr!("error-field-empty", name = name)));
+        }
+        self.update_task(id, tr!("change-set-field", name = name.as_str(), id = id), |task| {
+            task.fields.insert(name, value);
+        })
+    }
+
+    /// Removes a custom field from the task with the given ID.
+    pub fn remove_field(&mut self, id: usize, name: &str) -> Result<(), TodoError> {
+        let name = name.trim().to_lowercase();
+        if !self.tasks[self.position(id)?].fields.contains_key(&name) {
+            return Err(TodoError::MissingField { id, name });
+        }
+        self.update_task(id, tr!("change-unset-field", name = name.as_str(), id = id), |task| {
+            task.fields.remove(&name);
+        })
+    }
+
+    /// Assigns the task with the given ID to someone, or to no one. Names
+    /// cannot contain spaces.
+    pub fn assign(&mut self, id: usize, assignee: Option<&str>) -> Result<(), TodoError> {
+        let assignee = assignee.map(str::trim);
+        if assignee.is_some_and(|name| name.is_empty() || name.contains(char::is_whitespace)) {
+            return Err(TodoError::Parse(tr!("invalid-assignee")));
+        }
+        let assignee = assignee.map(str::to_string);
+        self.update_task(id, tr!("change-assign", id = id), |task| task.assignee = assignee)
+    }
+
+    /// Marks the open task with the given ID as waiting on `on`, or on no one
+    /// in particular, to be followed up on `follow_up`. A task already
+    /// waiting keeps the time it started waiting.
+    pub fn wait(&mut self, id: usize, on: Option<&str>, follow_up: Option<Date>) -> Result<(), TodoError> {
+        let task = &self.tasks[self.position(id)?];
+        if task.completed {
+            return Err(TodoError::AlreadyCompleted(id));
+        }
+        let since = task.waiting.as_ref().map_or_else(|| self.now(), |waiting| waiting.since);
+        let on = on.map(str::trim).filter(|on| !on.is_empty()).map(str::to_string);
+        self.update_task(id, tr!("change-wait", id = id), |task| task.waiting = Some(Waiting { on, since, follow_up }))
+    }
+
+    /// Stops the task with the given ID waiting on anyone.
+    pub fn stop_waiting(&mut self, id: usize) -> Result<(), TodoError> {
+        self.update_task(id, tr!("change-unwait", id = id), |task| task.waiting = None)
+    }
+
+    /// Moves the task with ID `id` to just before the task with ID `before`
+    /// in the manual order, which `--sort manual` lists tasks in.
+    pub fn move_before(&mut self, id: usize, before: usize) -> Result<(), TodoError> {
+        self.position(before)?;
+        if id == before {
+            return Err(TodoError::Parse(tr!("move-before-itself", id = id)));
+        }
+        self.reorder(id, Some(before))
+    }
+
+    /// Moves the task with the given ID to the top of the manual order.
+    pub fn move_to_top(&mut self, id: usize) -> Result<(), TodoError> {
+        self.reorder(id, None)
+    }
+
+    /// Puts the tasks with the given IDs first in the manual order, in the
+    /// order given, followed by the rest in the order they were in.
+    pub fn set_order(&mut self, ids: &[usize]) -> Result<(), TodoError> {
+        for &id in ids {
+            self.position(id)?;
+        }
+        let first: HashSet<usize> = ids.iter().copied().collect();
+        let mut rest: Vec<&Task> = self.tasks.iter().filter(|task| !first.contains(&task.id)).collect();
+        rest.sort_by_key(|task| manual_key(task));
+        let order = ids.iter().copied().chain(rest.iter().map(|task| task.id)).collect();
+        self.rank(order, tr!("change-reorder"))
+    }
+
+    fn reorder(&mut self, id: usize, before: Option<usize>) -> Result<(), TodoError> {
+        self.position(id)?;
+        let mut order: Vec<&Task> = self.tasks.iter().filter(|task| task.id != id).collect();
+        order.sort_by_key(|task| manual_key(task));
+        let mut ids: Vec<usize> = order.iter().map(|task| task.id).collect();
+        let at = before.and_then(|before| ids.iter().position(|&other| other == before)).unwrap_or(0);
+        ids.insert(at, id);
+        self.rank(ids, tr!("change-move", id = id))
+    }
+
+    // Ranks every task in the order of `ids`, so moved and new tasks never
+    // share a rank
+    fn rank(&mut self, ids: Vec<usize>, label: String) -> Result<(), TodoError> {
+        let mut changes = Vec::new();
+        for (rank, id) in ids.into_iter().enumerate() {
+            let index = self.position(id)?;
+            if self.tasks[index].rank != Some(rank + 1) {
+                changes.push(self.change_task(index, |task| task.rank = Some(rank + 1)));
+            }
+        }
+        self.history.record(label, changes);
+        Ok(())
+    }
+
+    /// Sets or clears the due date of the task with the given ID.
+    pub fn set_due_date(&mut self, id: usize, due_date: Option<Date>) -> Result<(), TodoError> {
+        self.update_task(id, tr!("change-due", id = id), |task| {
+            task.due_date = due_date;
+            task.recurs_on_day = None;
+        })
+    }
+
+    /// Sets or clears the start date of the task with the given ID. Before
+    /// it the task is left out of listings.
+    pub fn set_start_date(&mut self, id: usize, start_date: Option<Date>) -> Result<(), TodoError> {
+        self.update_task(id, tr!("change-start", id = id), |task| task.start_date = start_date)
+    }
+
+    /// Changes the priority of the task with the given ID.
+    pub fn set_priority(&mut self, id: usize, priority: Priority) -> Result<(), TodoError> {
+        self.update_task(id, tr!("change-priority", id = id), |task| task.priority = priority)
+    }
+
+    /// Removes the task with the given ID from the list, along with all of
+    /// its subtasks.
+    pub fn remove_task(&mut self, id: usize) -> Result<(), TodoError> {
+        self.take_task(id)?;
+        Ok(())
+    }
+
+    /// Removes the task with the given ID and its subtasks like
+    /// [`TodoList::remove_task`], returning what was removed.
+    pub fn take_task(&mut self, id: usize) -> Result<Vec<Task>, TodoError> {
+        self.take_batch(&[id], tr!("change-remove", id = id))
+    }
+
+    /// Removes every task in `ids` and their subtasks, as a single change
+    /// for undo, returning what was removed.
+    pub fn remove_tasks(&mut self, ids: &[usize]) -> Result<Vec<Task>, TodoError> {
+        self.take_batch(ids, tr!("change-remove-many", count = ids.len()))
+    }
+
+    fn take_batch(&mut self, ids: &[usize], label: String) -> Result<Vec<Task>, TodoError> {
+        // Nothing is removed unless every task exists
+        for &id in ids {
+            self.position(id)?;
+        }
+
+        // A task whose parent is also in the batch is only taken once
+        let mut taken = Vec::new();
+        let mut seen = HashSet::new();
+        for &id in ids {
+            taken.extend(self.with_descendants(id).into_iter().filter(|&id| seen.insert(id)));
+        }
+        let mut indices: Vec<usize> = taken.iter().map(|&id| self.position(id)).collect::<Result<_, _>>()?;
+        indices.sort_unstable();
+
+        // Each change's index is where the task is once those before it are
+        // gone, so the changes undo and redo one after another
+        let changes: Vec<Change> = indices.iter().enumerate().map(|(gone, &index)| Change {
+            index: index - gone,
+            before: Some(self.tasks[index].clone()),
+            after: None,
+        }).collect();
+        let mut tasks: HashMap<usize, Task> = HashMap::new();
+        for (index, task) in std::mem::take(&mut self.tasks).into_iter().enumerate() {
+            if indices.binary_search(&index).is_ok() {
+                tasks.insert(task.id, task);
+            } else {
+                self.tasks.push(task);
+            }
+        }
+        self.reindex();
+        self.history.record(label, changes);
+        Ok(taken.iter().filter_map(|id| tasks.remove(id)).collect())
+    }
+
+    /// Puts back tasks taken from another list, such as the archive, keeping
+    /// their IDs.
+    pub fn restore_tasks(&mut self, tasks: Vec<Task>) {
+        let label = match tasks.first() {
+            Some(task) => tr!("change-restore", id = task.id),
+            None => return,
+        };
+        let changes = tasks.into_iter().map(|task| self.push_task(task)).collect();
+        self.history.record(label, changes);
+    }
+
+    /// Moves every completed task whose subtasks are all completed out of
+    /// the list and returns them, ready to be added to an archive. This
+    /// clears the undo history.
+    pub fn archive_completed(&mut self) -> Vec<Task> {
+        let archived: HashSet<usize> = self.tasks.iter()
+            .filter(|task| self.with_descendants(task.id).iter()
+                .all(|id| self.task(*id).is_some_and(|task| task.completed)))
+            .map(|task| task.id)
+            .collect();
+
+        let (archived, kept) = std::mem::take(&mut self.tasks)
+            .into_iter()
+            .partition(|task| archived.contains(&task.id));
+        self.tasks = kept;
+        self.reindex();
+        self.history.clear();
+        archived
+    }
+
+    /// Gives the tasks the new IDs in `ids`, which maps old IDs to new ones,
+    /// and moves the parent, dependency and recurrence links along with
+    /// them. Tasks left out of `ids` keep their IDs, and links to IDs it
+    /// does not have are dropped, as the task they pointed at is gone and
+    /// its number may now be another's. UUIDs do not change. This clears
+    /// the undo history.
+    pub fn renumber(&mut self, ids: &HashMap<usize, usize>) {
+        for task in &mut self.tasks {
+            task.id = ids.get(&task.id).copied().unwrap_or(task.id);
+            task.parent = task.parent.and_then(|parent| ids.get(&parent).copied());
+            task.recurs_from = task.recurs_from.and_then(|from| ids.get(&from).copied());
+            task.depends_on = task.depends_on.iter().filter_map(|on| ids.get(on).copied()).collect();
+        }
+        self.reindex();
+        // The next ID can go down, but not onto one given out here
+        self.next_id = ids.values().max().map_or(1, |max| max + 1);
+        self.fix_next_id();
+        self.history.clear();
+    }
+
+    /// Reverts the most recent change and returns a short description of it.
+    pub fn undo(&mut self) -> Result<String, TodoError> {
+        let label = self.history.undo(&mut self.tasks).ok_or(TodoError::NothingToUndo)?;
+        self.reindex();
+        Ok(label)
+    }
+
+    /// Reapplies the most recently undone change and returns a short
+    /// description of it.
+    pub fn redo(&mut self) -> Result<String, TodoError> {
+        let label = self.history.redo(&mut self.tasks).ok_or(TodoError::NothingToRedo)?;
+        self.reindex();
+        Ok(label)
+    }
+
+    /// Short descriptions of the changes that can be undone, like
+    /// "complete task 3" in the language messages are shown in, oldest
+    /// first.
+    pub fn change_labels(&self) -> Vec<&str> {
+        self.history.labels().collect()
+    }
+
+    /// Sets how many changes can be undone. Older changes are forgotten.
+    pub fn set_history_limit(&mut self, limit: usize) {
+        self.history.set_limit(limit);
+    }
+
+    fn insert_new_task(
+        &mut self,
+        description: String,
+        due_date: Option<Date>,
+        priority: Priority,
+        parent: Option<usize>,
+    ) -> Result<usize, TodoError> {
+        let description = validate_description(&description)?;
+
+        let task = Task {
+            id: self.next_id,
+            uuid: Uuid::new_v4(),
+            created_at: Some(self.now()),
+            description,
+            completed: false,
+            due_date,
+            priority,
+            parent,
+            ..Task::default()
+        };
+        let task_id = task.id;
+        let change = self.push_task(task);
+        self.history.record(tr!("change-add", id = task_id), vec![change]);
+        Ok(task_id)
+    }
+
+    // Adds tasks read from another format, giving each the next free ID.
+    // All of them are undone together.
+    pub(crate) fn add_imported(&mut self, tasks: Vec<Task>) -> Vec<usize> {
+        let mut ids = Vec::new();
+        let mut changes = Vec::new();
+        let now = self.now();
+        for mut task in tasks {
+            task.id = self.next_id;
+            task.uuid = Uuid::new_v4();
+            task.created_at.get_or_insert(now);
+            task.normalize_status();
+            ids.push(task.id);
+            changes.push(self.push_task(task));
+        }
+        self.history.record(tr!("change-import", count = ids.len()), changes);
+        ids
+    }
+
+    // Puts each task in the list under its own ID, replacing the task
+    // already there or adding it at the end. All of them are undone together.
+    pub(crate) fn put_tasks(&mut self, label: String, tasks: Vec<Task>) {
+        let mut changes = Vec::new();
+        for task in tasks {
+            match self.index.get(&task.id) {
+                Some(&index) => changes.push(self.change_task(index, |old| *old = task)),
+                None => changes.push(self.push_task(task)),
+            }
+        }
+        self.history.record(label, changes);
+    }
+
+    // Marks the task at `index` completed, scheduling the next occurrence of
+    // a recurring task, and returns the next occurrence's ID
+    fn complete_at(&mut self, index: usize, changes: &mut Vec<Change>) -> Option<usize> {
+        let now = self.now();
+        changes.push(self.change_task(index, |task| {
+            task.set_status(Column::Done);
+            task.completed_at = Some(now);
+            task.status_log.push(StatusChange { at: now, completed: true });
+            task.stop_timer(now);
+            task.waiting = None;
+        }));
+        let completed = &self.tasks[index];
+        let next = completed.recurrence.map(|recurrence| {
+            // Without a due date the next occurrence is counted from today
+            let due = completed.due_date.unwrap_or_else(|| now.date());
+            let day = completed.recurs_on_day.unwrap_or(due.day);
+            let next_due = recurrence.next_date_on(due, day);
+            let start_date = completed.start_date.map(|start| start.add_days(next_due.to_days() - due.to_days()));
+            (next_due, start_date, (next_due.day != day).then_some(day))
+        });
+        let next = next.map(|(due_date, start_date, recurs_on_day)| Task {
+            id: self.next_id,
+            uuid: Uuid::new_v4(),
+            completed: false,
+            due_date: Some(due_date),
+            // The start date keeps its distance from the due date
+            start_date,
+            recurs_from: Some(completed.id),
+            recurs_on_day,
+            reminded: None,
+            announced_overdue: None,
+            hidden_until: None,
+            comments: Vec::new(),
+            last_reviewed: None,
+            time_log: Vec::new(),
+            pomodoros: 0,
+            created_at: Some(now),
+            completed_at: None,
+            status_log: Vec::new(),
+            // Only tasks that were moved have a place in the manual order
+            rank: None,
+            ..completed.clone()
+        });
+        next.map(|task| {
+            let next_id = task.id;
+            changes.push(self.push_task(task));
+            next_id
+        })
+    }
+
+    // The top-level task `id` is a subtask of, or `id` itself. A parent
+    // missing from the list counts as the top.
+    fn root(&self, mut id: usize) -> usize {
+        // Parents that loop would otherwise never end
+        for _ in 0..self.tasks.len() {
+            match self.task(id).and_then(|task| task.parent).filter(|parent| self.task(*parent).is_some()) {
+                Some(parent) => id = parent,
+                None => break,
+            }
+        }
+        id
+    }
+
+    // The ID followed by the IDs of all its subtasks, their subtasks and so on
+    fn with_descendants(&self, id: usize) -> Vec<usize> {
+        let mut ids = vec![id];
+        let mut next = 0;
+        while next < ids.len() {
+            ids.extend(self.subtasks(ids[next]).iter().map(|task| task.id));
+            next += 1;
+        }
+        ids
+    }
+
+    fn position(&self, id: usize) -> Result<usize, TodoError> {
+        self.index.get(&id).copied().ok_or(TodoError::NotFound(id))
+    }
+
+    fn reindex(&mut self) {
+        self.index = self.tasks.iter().enumerate().map(|(index, task)| (task.id, index)).collect();
+    }
+
+    // Applies `update` to one task and records the change so it can be undone
+    fn update_task(
+        &mut self,
+        id: usize,
+        label: String,
+        update: impl FnOnce(&mut Task),
+    ) -> Result<(), TodoError> {
+        let index = self.position(id)?;
+        let change = self.change_task(index, update);
+        self.history.record(label, vec![change]);
+        Ok(())
+    }
+
+    // Applies `update` to the task at `index`, returning the change for the history
+    fn change_task(&mut self, index: usize, update: impl FnOnce(&mut Task)) -> Change {
+        let before = self.tasks[index].clone();
+        update(&mut self.tasks[index]);
+        Change {
+            index,
+            before: Some(before),
+            after: Some(self.tasks[index].clone()),
+        }
+    }
+
+    // Appends a task that already has the next ID, returning the change for the history
+    fn push_task(&mut self, task: Task) -> Change {
+        self.next_id = self.next_id.max(task.id + 1);
+        self.index.insert(task.id, self.tasks.len());
+        self.tasks.push(task.clone());
+        Change {
+            index: self.tasks.len() - 1,
+            before: None,
+            after: Some(task),
+        }
+    }
+
+    /// Writes the list to `filename` as JSON. The file is replaced in one
+    /// step, so a crash while saving leaves the previous version intact.
+    #[cfg(feature = "native")]
+    pub fn save_tasks(&self, filename: &str) -> io::Result<()> {
+        self.save_tasks_with_backups(filename, 0)
+    }
+
+    /// Like [`TodoList::save_tasks`], but first keeps the previous `backups`
+    /// versions of the file as `filename.1` (newest), `filename.2`, ...
+    #[cfg(feature = "native")]
+    pub fn save_tasks_with_backups(&self, filename: &str, backups: usize) -> io::Result<()> {
+        atomic::write_atomic(Path::new(filename), backups, |writer| {
+            serde_json::to_writer_pretty(&mut *writer, self)?;
+            writeln!(writer)
+        })
+    }
+
+    /// Reads tasks saved by [`TodoList::save_tasks`]. A missing file gives an
+    /// empty list.
+    #[cfg(feature = "native")]
+    pub fn load_tasks(filename: &str) -> Result<Self, TodoError> {
+        if !Path::new(filename).exists() {
+            return Ok(TodoList::new());
+        }
+
+        let file = File::open(filename)?;
+        let mut todo_list: TodoList = serde_json::from_reader(BufReader::new(file))?;
+        todo_list.finish_loading();
+        Ok(todo_list)
+    }
+
+    /// Reads a list from JSON written by [`TodoList::save_tasks`], such as
+    /// an older copy of the data file.
+    pub fn from_json(json: &str) -> Result<Self, TodoError> {
+        let mut todo_list: TodoList = serde_json::from_str(json)?;
+        todo_list.finish_loading();
+        Ok(todo_list)
+    }
+
+    /// The list as [`TodoList::save_tasks`] writes it to the data file.
+    pub fn to_json(&self) -> Result<String, TodoError> {
+        let mut json = serde_json::to_string_pretty(self)?;
+        json.push('\n');
+        Ok(json)
+    }
+
+    /// Reads the comma separated format used before tasks were stored as
+    /// JSON, so existing lists can be migrated. A missing file gives an
+    /// empty list. Lines that cannot be read as tasks, such as ones without
+    /// the right number of fields or with a bad ID, are left out;
+    /// [`TodoList::load_legacy_tasks_with_report`] says which and why.
+    #[cfg(feature = "native")]
+    pub fn load_legacy_tasks(filename: &str) -> Result<Self, TodoError> {
+        Ok(TodoList::load_legacy_tasks_with_report(filename)?.0)
+    }
+
+    /// Reads the old comma separated format like
+    /// [`TodoList::load_legacy_tasks`], along with a report of the lines
+    /// that were left out.
+    #[cfg(feature = "native")]
+    pub fn load_legacy_tasks_with_report(filename: &str) -> Result<(Self, ParseReport), TodoError> {
+        let mut todo_list = TodoList::new();
+
+        if !Path::new(filename).exists() {
+            return Ok((todo_list, ParseReport::default()));
+        }
+
+        let file = File::open(filename)?;
+        let (tasks, rejected) = legacy::read_tasks(BufReader::new(file))?;
+        todo_list.tasks = tasks;
+        todo_list.finish_loading();
+        Ok((todo_list, ParseReport { rejected }))
+    }
+
+    // Adds an already built task, as read back from storage, without
+    // recording it in the history
+    #[cfg(any(feature = "native", feature = "memory"))]
+    pub(crate) fn insert_task(&mut self, mut task: Task) {
+        task.normalize_status();
+        self.next_id = self.next_id.max(task.id + 1);
+        match self.index.get(&task.id) {
+            Some(&index) => self.tasks[index] = task,
+            None => {
+                self.index.insert(task.id, self.tasks.len());
+                self.tasks.push(task);
+            }
+        }
+    }
+
+    // Adds a task from a CalDAV server or a todo.txt file with the next free
+    // ID, keeping its UUID, without recording it in the history
+    #[cfg(feature = "native")]
+    pub(crate) fn add_synced(&mut self, mut task: Task) -> usize {
+        task.id = self.next_id;
+        let id = task.id;
+        self.insert_task(task);
+        id
+    }
+
+    // Removes a task deleted on a CalDAV server or in a journal, leaving its
+    // subtasks, without recording it in the history
+    #[cfg(feature = "native")]
+    pub(crate) fn remove_synced(&mut self, id: usize) {
+        self.tasks.retain(|task| task.id != id);
+        self.reindex();
+    }
+
+    // For changes undo cannot revert, like ones shared with a server
+    #[cfg(feature = "native")]
+    pub(crate) fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    // Update next_id to be higher than existing task IDs
+    fn fix_next_id(&mut self) {
+        if let Some(max_id) = self.tasks.iter().map(|task| task.id).max() {
+            self.next_id = self.next_id.max(max_id + 1);
+        }
+    }
+
+    // Sets up what a list read from a file needs beyond its tasks
+    fn finish_loading(&mut self) {
+        for task in &mut self.tasks {
+            task.normalize_status();
+        }
+        self.reindex();
+        self.fix_next_id();
+    }
+}
@@ -0,0 +1,40 @@
+//! Keeps two instances from changing the same list at once, which would
+//! have the last one to save silently undo the other's changes.
+
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io;
+use std::path::Path;
+
+use todo_list::TodoError;
+
+use crate::paths;
+
+/// Holds the lock until dropped.
+pub struct Lock {
+    _file: File,
+}
+
+/// Takes the lock for `data_file`, failing straight away when another
+/// instance has it. The lock is on a separate file because saving replaces
+/// the data file.
+pub fn acquire(data_file: &Path) -> Result<Lock, TodoError> {
+    let path = paths::lock_file(data_file);
+    let file = open(&path)?;
+    match file.try_lock() {
+        Ok(()) => Ok(Lock { _file: file }),
+        Err(TryLockError::WouldBlock) => Err(TodoError::Locked(path)),
+        Err(TryLockError::Error(e)) => Err(e.into()),
+    }
+}
+
+/// Takes the lock for `data_file`, waiting for another instance to let go
+/// of it first.
+pub fn wait(data_file: &Path) -> Result<Lock, TodoError> {
+    let file = open(&paths::lock_file(data_file))?;
+    file.lock()?;
+    Ok(Lock { _file: file })
+}
+
+fn open(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).truncate(false).write(true).open(path)
+}
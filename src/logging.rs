@@ -0,0 +1,110 @@
+//! Logging what the program does, for working out what went wrong in a sync
+//! or with a damaged list. The engine reports loads, saves, changes and the
+//! files it parses as `tracing` events; with `-v` or `--log-file` they are
+//! written one per line, to stderr or appended to the file:
+//!
+//! ```text
+//! 2024-06-01T09:00:00Z INFO  todo_list::storage: loaded backend=json path=/home/sam/todo_list.json tasks=12
+//! 2024-06-01T09:00:00Z INFO  todo_list::history: changed change="complete task 3" tasks=1
+//! ```
+
+use std::fmt::{self, Write as _};
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+use todo_list::{tr, TodoError, Timestamp};
+use tracing::field::{Field, Visit};
+use tracing::level_filters::LevelFilter;
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+/// Starts logging events as detailed as `verbose` asks: loads, saves and
+/// changes at 1, the files parsed and sync requests too at 2, and every
+/// task read at 3 or more. A log file with `verbose` at 0 gets the first.
+/// Without either nothing is logged.
+pub fn init(verbose: u8, file: Option<&Path>) -> Result<(), TodoError> {
+    let level = match verbose {
+        0 if file.is_none() => return Ok(()),
+        0 | 1 => Level::INFO,
+        2 => Level::DEBUG,
+        _ => Level::TRACE,
+    };
+    let out: Box<dyn Write + Send> = match file {
+        Some(path) => Box::new(
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|e| TodoError::Parse(tr!("log-file-failed", path = path.display().to_string(), error = e.to_string())))?,
+        ),
+        None => Box::new(io::stderr()),
+    };
+    // Setting it only fails when one was set before, which it is not
+    let _ = tracing::subscriber::set_global_default(Logger { level, out: Mutex::new(out), spans: AtomicU64::new(1) });
+    Ok(())
+}
+
+struct Logger {
+    level: Level,
+    out: Mutex<Box<dyn Write + Send>>,
+    // The engine does not use spans, but each one made still needs an ID
+    spans: AtomicU64,
+}
+
+impl Subscriber for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        *metadata.level() <= self.level
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        Some(LevelFilter::from_level(self.level))
+    }
+
+    fn new_span(&self, _: &Attributes) -> Id {
+        Id::from_u64(self.spans.fetch_add(1, Ordering::Relaxed))
+    }
+
+    fn record(&self, _: &Id, _: &Record) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event) {
+        let mut line = Line::default();
+        event.record(&mut line);
+        let metadata = event.metadata();
+        let mut out = self.out.lock().unwrap_or_else(PoisonError::into_inner);
+        // A log that cannot be written to is not worth stopping for
+        let _ = writeln!(out, "{} {:<5} {}: {}{}", Timestamp::now(), metadata.level(), metadata.target(), line.message, line.fields);
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+// An event's message and its other fields as ` name=value`, quoted where
+// the value has spaces so the line still splits into fields
+#[derive(Default)]
+struct Line {
+    message: String,
+    fields: String,
+}
+
+impl Visit for Line {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else if value.is_empty() || value.contains(|c: char| c.is_whitespace() || c == '"') {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.record_str(field, &format!("{:?}", value));
+    }
+}
@@ -0,0 +1,99 @@
+//! Email, as RFC 5322 has it, and sending it through a sendmail-compatible
+//! program, such as sendmail itself, or msmtp or ssmtp to go through an
+//! SMTP server.
+
+#[cfg(feature = "native")]
+use std::io::{self, Write};
+#[cfg(feature = "native")]
+use std::process::{Command, Stdio};
+
+use crate::{tr, DateFormat, Timestamp, TodoError};
+
+/// The program email goes to when the config file does not name one. With
+/// `-t` it reads the recipients from the message's headers.
+pub const DEFAULT_SENDMAIL: &str = "sendmail -t -i";
+
+/// A plain text email from `from` to `to`, sent at `now`, with the lines
+/// ending in CRLF. Either address can be left out for the program sending it
+/// to fill in. An address with a line break in it, which would let it add
+/// headers of its own, is an error.
+pub fn message(
+    from: Option<&str>,
+    to: Option<&str>,
+    subject: &str,
+    body: &str,
+    now: Timestamp,
+) -> Result<String, TodoError> {
+    let mut headers = Vec::new();
+    for (name, address) in [("From", from), ("To", to)] {
+        let Some(address) = address.map(str::trim) else {
+            continue;
+        };
+        if address.is_empty() || address.contains(['\r', '\n']) {
+            return Err(TodoError::Parse(tr!("invalid-email", value = address.escape_debug().to_string())));
+        }
+        headers.push(format!("{}: {}", name, address));
+    }
+    headers.push(format!("Subject: {}", encode_header(subject)));
+    headers.push(format!("Date: {}", email_date(now)));
+    headers.push("MIME-Version: 1.0".to_string());
+    headers.push("Content-Type: text/plain; charset=utf-8".to_string());
+    headers.push("Content-Transfer-Encoding: 8bit".to_string());
+
+    let mut message = headers.join("\r\n");
+    message.push_str("\r\n\r\n");
+    for line in body.lines() {
+        message.push_str(line);
+        message.push_str("\r\n");
+    }
+    Ok(message)
+}
+
+/// Pipes `message`, with its headers, to `command`, a program and its
+/// arguments separated by spaces, and waits for it to take it.
+#[cfg(feature = "native")]
+pub fn send(command: &str, message: &str) -> io::Result<()> {
+    let mut words = command.split_whitespace();
+    let program = words.next().ok_or_else(|| io::Error::other("no sendmail command given"))?;
+    let mut child = Command::new(program).args(words).stdin(Stdio::piped()).spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(message.as_bytes())?;
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(io::Error::other(format!("{} exited with {}", program, status)));
+    }
+    Ok(())
+}
+
+// Like `Wed, 14 Oct 2026 08:00:00 +0000`, always in UTC
+fn email_date(now: Timestamp) -> String {
+    let day = DateFormat::parse("%a, %d %b %Y").map(|format| format.format(now.date())).unwrap_or_default();
+    let time = now.seconds().rem_euclid(86_400);
+    format!("{} {:02}:{:02}:{:02} +0000", day, time / 3600, time % 3600 / 60, time % 60)
+}
+
+// Header values are ASCII, so any other text is written as RFC 2047
+// encoded words, each short enough for a line of its own, and a character
+// is never split between two of them
+fn encode_header(text: &str) -> String {
+    if text.is_ascii() {
+        return text.to_string();
+    }
+    let mut words = vec![String::new()];
+    for c in text.chars() {
+        let mut bytes = [0; 4];
+        let encoded: String = c.encode_utf8(&mut bytes).bytes().map(|byte| match byte {
+            b' ' => "_".to_string(),
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'.' | b',' | b':' | b'-' => (byte as char).to_string(),
+            _ => format!("={:02X}", byte),
+        }).collect();
+        if words.last().is_some_and(|word| word.len() + encoded.len() > 60) {
+            words.push(String::new());
+        }
+        if let Some(word) = words.last_mut() {
+            word.push_str(&encoded);
+        }
+    }
+    words.iter().map(|word| format!("=?UTF-8?Q?{}?=", word)).collect::<Vec<_>>().join("\r\n ")
+}
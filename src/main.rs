@@ -0,0 +1,1257 @@
+mod chat;
+mod cli;
+mod config;
+mod confirm;
+mod editor;
+mod git;
+mod hooks;
+mod kanban;
+mod lock;
+mod logging;
+mod menu;
+mod opener;
+mod output;
+mod pager;
+mod paths;
+mod picker;
+#[cfg(feature = "plugins")]
+mod plugins;
+mod pomodoro;
+mod readline;
+mod review;
+mod serve;
+mod signals;
+mod snapshots;
+
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::Parser;
+use todo_list::storage::{
+    self, archive_completed, empty_trash, renumber, restore_archived, restore_trashed, trash_tasks, JsonStorage, Storage,
+};
+#[cfg(feature = "sqlite")]
+use todo_list::storage::SqliteStorage;
+#[cfg(feature = "text")]
+use todo_list::storage::TextStorage;
+use todo_list::agenda::Agenda;
+use todo_list::caldav::{self, SyncState};
+use todo_list::capture::{self, Capture};
+use todo_list::diff::Diff;
+use todo_list::digest::Digest;
+use todo_list::filter::{Filter, Status};
+use todo_list::import::{self, ImportSummary};
+use todo_list::journal::{self, Journal};
+use todo_list::notify::Event;
+use todo_list::stats::{self, Report};
+use todo_list::{batch, csv, doctor, html, i18n, tr, ical, mail, markdown, merge, todotxt, Column, Date, Estimate, Recurrence, Task, Timestamp, TodoError, TodoList};
+
+use cli::{AtCommand, Autosave, Backend, Cli, Command, ExportFormat, ImportFormat, OutputFormat, PickAction, ReportFormat, SyncSide, TrashCommand};
+use config::Config;
+use confirm::Duplicate;
+use git::GitStorage;
+use chat::ChatStorage;
+use hooks::HookStorage;
+use snapshots::{SnapshotStorage, DEFAULT_SNAPSHOTS};
+use plugins::Plugins;
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = logging::init(cli.verbose, cli.log_file.as_deref()).and_then(|()| run(cli));
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", tr!("error", message = e.to_string()));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn open_storage(cli: &Cli, path: &Path) -> Box<dyn Storage> {
+    match cli.backend {
+        Backend::Json => Box::new(JsonStorage::new(path).with_backups(cli.backups)),
+        #[cfg(feature = "sqlite")]
+        Backend::Sqlite if cli.read_only => Box::new(SqliteStorage::new(path).read_only()),
+        #[cfg(feature = "sqlite")]
+        Backend::Sqlite => Box::new(SqliteStorage::new(path)),
+        #[cfg(feature = "text")]
+        Backend::Text => Box::new(TextStorage::new(path)),
+    }
+}
+
+// Lists from before the data file moved out of the working directory, or
+// from before the switch to JSON, are read once and then saved to `path`.
+// This only happens for the default data file, not one the user picked.
+// Lines of an old list that cannot be read are set aside in the rejects
+// file next to `path` and warned about, or with `strict` stop the import.
+fn load_or_import(storage: &dyn Storage, path: &Path, picked: bool, strict: bool) -> Result<TodoList, TodoError> {
+    let old_json = PathBuf::from("todo_list.json");
+    let legacy = PathBuf::from("todo_list.txt");
+
+    if path.exists() || picked {
+        storage.load()
+    } else if old_json.exists() {
+        println!("{}", tr!("importing-old-list", from = old_json.display().to_string(), to = path.display().to_string()));
+        JsonStorage::new(old_json).load()
+    } else if legacy.exists() {
+        println!("{}", tr!("importing-old-list", from = legacy.display().to_string(), to = path.display().to_string()));
+        let (todo_list, report) = TodoList::load_legacy_tasks_with_report(&legacy.to_string_lossy())?;
+        if !report.is_clean() && strict {
+            return Err(TodoError::Parse(tr!("legacy-unreadable", file = legacy.display().to_string(), lines = report.to_string())));
+        }
+        if !report.is_clean() {
+            let rejects = paths::rejects_file(path);
+            // Until the list is first saved the old one is imported again
+            // each run, so the file is written afresh rather than added to
+            fs::write(&rejects, report.rejects())?;
+            let message = tr!(
+                "rejected-lines",
+                lines = report.to_string(),
+                file = legacy.display().to_string(),
+                rejects = rejects.display().to_string()
+            );
+            eprintln!("{}", tr!("warning", message = message));
+        }
+        Ok(todo_list)
+    } else {
+        storage.load()
+    }
+}
+
+// Another copy of a list, of the kind its extension says. Numbered backups
+// like todo_list.json.2 are of the kind before the number.
+fn load_file(input: &Path) -> Result<TodoList, TodoError> {
+    // A missing file would load as an empty list
+    if !input.exists() {
+        return Err(io::Error::new(io::ErrorKind::NotFound, tr!("file-missing", path = input.display().to_string())).into());
+    }
+    let mut name = input.to_path_buf();
+    while name.extension().is_some_and(|extension| extension.to_string_lossy().bytes().all(|b| b.is_ascii_digit())) {
+        name.set_extension("");
+    }
+    match name.extension().and_then(|extension| extension.to_str()) {
+        Some("json") => JsonStorage::new(input).load(),
+        #[cfg(feature = "text")]
+        Some("txt") => TextStorage::new(input).load(),
+        #[cfg(feature = "sqlite")]
+        Some("db") => SqliteStorage::new(input).read_only().load(),
+        _ => Err(TodoError::Parse(tr!("unknown-list-kind", path = input.display().to_string()))),
+    }
+}
+
+// Prints what changed going from one copy of a list to another
+fn diff(old: &Path, new: &Path) -> Result<(), TodoError> {
+    let diff = Diff::new(&load_file(old)?, &load_file(new)?);
+    if diff.is_empty() {
+        println!("{}", tr!("diff-none"));
+        return Ok(());
+    }
+    print_diff(&diff);
+    Ok(())
+}
+
+// What a command run with --dry-run would have done to a list, from a copy
+// taken before to the list after, which is not saved
+fn dry_run(heading: &str, before: &TodoList, after: &TodoList) -> Result<(), TodoError> {
+    let diff = Diff::new(before, after);
+    if diff.is_empty() {
+        println!("{}", tr!("dry-run-none"));
+        return Ok(());
+    }
+    println!("{}", todo_list::i18n::message(heading, &[]));
+    print_diff(&diff);
+    Ok(())
+}
+
+fn print_diff(diff: &Diff) {
+    let line = |mark: &str, task: &Task| format!("  {} {} {}", mark, task.id, task.description);
+    for (heading, mark, tasks) in [
+        (tr!("diff-added"), "+", &diff.added),
+        (tr!("diff-removed"), "-", &diff.removed),
+        (tr!("diff-completed"), "x", &diff.completed),
+        (tr!("diff-reopened"), "o", &diff.reopened),
+    ] {
+        if !tasks.is_empty() {
+            println!("{}:", heading);
+            for task in tasks {
+                println!("{}", line(mark, task));
+            }
+        }
+    }
+    if !diff.edited.is_empty() {
+        println!("{}:", tr!("diff-edited"));
+        for (task, fields) in &diff.edited {
+            println!("{} ({})", line("~", task), fields.join(", "));
+        }
+    }
+}
+
+// The words of a filter given on the command line, unquoted or not
+fn parse_filter(words: &[String]) -> Result<Option<Filter>, TodoError> {
+    let filter = words.join(" ");
+    if filter.trim().is_empty() {
+        return Ok(None);
+    }
+    Filter::parse(&filter).map(Some)
+}
+
+// Who --mine and a bare `todo assign` mean, from --me or the login name
+fn me(name: Option<&str>) -> Result<String, TodoError> {
+    name.map(str::to_string)
+        .or_else(|| env::var("USER").ok())
+        .or_else(|| env::var("USERNAME").ok())
+        .filter(|name| !name.trim().is_empty())
+        .ok_or_else(|| TodoError::Parse(tr!("unknown-me")))
+}
+
+// A list's filter narrowed to the tasks assigned to you with --mine and to
+// those waiting on someone with --waiting
+fn list_filter(words: &[String], mine: bool, waiting: bool, name: Option<&str>) -> Result<Option<Filter>, TodoError> {
+    let mut filter = parse_filter(words)?;
+    let narrow = |filter: Option<Filter>, by: Filter| match filter {
+        Some(filter) => Filter::And(Box::new(filter), Box::new(by)),
+        None => by,
+    };
+    if mine {
+        filter = Some(narrow(filter, Filter::Assignee(Some(me(name)?))));
+    }
+    if waiting {
+        filter = Some(narrow(filter, Filter::Status(Status::Waiting)));
+    }
+    Ok(filter)
+}
+
+// How many tasks come before page `page` of `limit` tasks each
+fn page_offset(page: Option<usize>, limit: usize) -> usize {
+    page.map_or(0, |page| (page - 1).saturating_mul(limit))
+}
+
+// The date format, colors and WIP limits from the config file, and the
+// width of the terminal
+fn show_as_configured(list: &mut TodoList, config: &Config) {
+    if let Some(format) = &config.date_format {
+        list.set_date_format(format.clone());
+    }
+    if let Some(relative) = config.relative_dates {
+        list.set_relative_dates(relative);
+    }
+    list.set_color_scheme(config.colors);
+    for (&column, &limit) in &config.wip_limits {
+        list.set_wip_limit(column, limit);
+    }
+    // Listings fit the terminal, and go out whole anywhere else
+    if let Some((_, columns)) = console::Term::stdout().size_checked() {
+        list.set_width(columns as usize);
+    }
+}
+
+
+// Text too long for the terminal goes through the pager
+fn print_listing(list: &TodoList, tasks: &[&Task], output: OutputFormat, no_pager: bool) -> Result<(), TodoError> {
+    match output {
+        OutputFormat::Text => print_text(&list.format_task_list(tasks), no_pager)?,
+        format => output::print_tasks(tasks, format)?,
+    }
+    Ok(())
+}
+
+fn print_text(text: &str, no_pager: bool) -> Result<(), TodoError> {
+    if no_pager {
+        print!("{}", text);
+    } else {
+        pager::show(text)?;
+    }
+    Ok(())
+}
+
+// Plugins take a moment to compile, so they are only loaded by the commands
+// that use them
+fn load_plugins(dir: Option<&Path>) -> Result<Plugins, TodoError> {
+    let dir = dir.map(Path::to_path_buf).or_else(|| paths::config_dir().map(|dir| dir.join("plugins")));
+    Plugins::load(&dir.unwrap_or_default())
+}
+
+// Built without the plugins feature, any use of plugins says so
+#[cfg(not(feature = "plugins"))]
+mod plugins {
+    use std::path::Path;
+
+    use todo_list::{Task, TodoError, TodoList};
+
+    pub enum Plugins {}
+
+    impl Plugins {
+        pub fn load(_dir: &Path) -> Result<Self, TodoError> {
+            Err(TodoError::Plugin(tr!("plugins-unavailable")))
+        }
+
+        pub fn commands(&self) -> Vec<(String, String)> {
+            match *self {}
+        }
+
+        pub fn filters(&self) -> Vec<String> {
+            match *self {}
+        }
+
+        pub fn formats(&self) -> Vec<String> {
+            match *self {}
+        }
+
+        pub fn run_command(&self, _todo_list: &mut TodoList, _name: &str, _args: &[String]) -> Result<bool, TodoError> {
+            match *self {}
+        }
+
+        pub fn filter<'a>(&self, _todo_list: &TodoList, _name: &str, _tasks: Vec<&'a Task>) -> Result<Vec<&'a Task>, TodoError> {
+            match *self {}
+        }
+
+        pub fn format(&self, _todo_list: &TodoList, _name: &str, _tasks: &[&Task]) -> Result<String, TodoError> {
+            match *self {}
+        }
+    }
+}
+
+// Asks before acting on more than one task at once, or before removing any,
+// listing every task affected; removing a task takes its subtasks with it.
+// Returns false when there is nothing to do or the user said no.
+fn confirm_batch(todo_list: &TodoList, question: &str, ids: &[usize], yes: bool, removing: bool) -> Result<bool, TodoError> {
+    // A mistyped ID is reported before asking anything
+    if let Some(&id) = ids.iter().find(|&&id| todo_list.task(id).is_none()) {
+        return Err(TodoError::NotFound(id));
+    }
+    if ids.is_empty() {
+        println!("{}", tr!("no-tasks"));
+        return Ok(false);
+    }
+    if yes || (ids.len() == 1 && !removing) {
+        return Ok(true);
+    }
+
+    let tasks = if removing {
+        todo_list.with_subtasks(ids)
+    } else {
+        ids.iter().filter_map(|&id| todo_list.task(id)).collect()
+    };
+    Ok(confirm::ask(&i18n::message(question, &[("count", tasks.len().into())]), &tasks)?)
+}
+
+// A JSON file is read task by task, so damaged tasks can be set aside
+// rather than keep the rest from loading; other backends are checked once
+// loaded
+fn doctor(cli: &Cli, storage: &dyn Storage, path: &Path) -> Result<(), TodoError> {
+    let checkup = match cli.backend {
+        Backend::Json if path.exists() => doctor::check_json(&fs::read_to_string(path)?)?,
+        _ => doctor::check(&storage.load()?),
+    };
+    if checkup.is_healthy() {
+        println!("{}", tr!("doctor-healthy", path = path.display().to_string()));
+        return Ok(());
+    }
+    for problem in &checkup.problems {
+        println!("{}", problem);
+    }
+    if !cli.yes && !confirm::question(&tr!("confirm-repair", count = checkup.problems.len()))? {
+        return Ok(());
+    }
+
+    if !checkup.quarantined.is_empty() {
+        let quarantine = paths::quarantine_file(path);
+        let mut file = OpenOptions::new().create(true).append(true).open(&quarantine)?;
+        let now = Timestamp::now();
+        for record in &checkup.quarantined {
+            let entry = serde_json::json!({ "quarantined_at": now, "task": record });
+            writeln!(file, "{}", entry)?;
+        }
+        let count = checkup.quarantined.len();
+        println!("{}", tr!("doctor-quarantined", count = count, path = quarantine.display().to_string()));
+    }
+    storage.save(&checkup.into_repaired())?;
+    println!("{}", tr!("doctor-repaired", path = path.display().to_string()));
+    Ok(())
+}
+
+fn run(mut cli: Cli) -> Result<(), TodoError> {
+    // Colors are only useful on a terminal, not when piped or redirected
+    todo_list::set_color_enabled(!cli.no_color && io::stdout().is_terminal());
+
+    let extension = match cli.backend {
+        Backend::Json => "json",
+        #[cfg(feature = "sqlite")]
+        Backend::Sqlite => "db",
+        #[cfg(feature = "text")]
+        Backend::Text => "txt",
+    };
+    let config = match cli.config.clone().or_else(|| paths::config_dir().map(|dir| dir.join("config.toml"))) {
+        Some(file) => Config::load(&file)?,
+        None => Config::default(),
+    };
+    let path = paths::data_file(cli.file.as_deref().or(config.file.as_deref()), extension)?;
+    // The snapshot is looked at like any other file, read-only
+    if let Some(Command::At { date, command }) = &cli.command {
+        let command = AtCommand::parse_from(command).command;
+        if !command.reads_only() {
+            return Err(TodoError::Parse(tr!("at-read-only")));
+        }
+        let Some((day, snapshot)) = snapshots::find(&paths::snapshot_dir(&path), *date) else {
+            return Err(TodoError::Parse(tr!("no-snapshot", date = date.to_string())));
+        };
+        if day != *date {
+            eprintln!("{}", tr!("snapshot-from", date = day.to_string()));
+        }
+        cli.command = Some(command);
+        cli.file = Some(snapshot);
+        cli.backend = Backend::Json;
+        cli.read_only = true;
+        return run(cli);
+    }
+    if cli.read_only {
+        match &cli.command {
+            Some(command) if command.reads_only() => {}
+            _ => return Err(TodoError::Parse(tr!("read-only-refused"))),
+        }
+        // Not even an old list is brought over, as that would save it
+        if !path.exists() {
+            return Err(TodoError::Parse(tr!("read-only-missing", path = path.display().to_string())));
+        }
+    }
+    let mut storage = open_storage(&cli, &path);
+    // Only the list itself is committed and watched by hooks; the archive
+    // and trash stay local. Nothing is saved read-only, so neither is needed.
+    if cli.git && !cli.read_only {
+        storage = Box::new(GitStorage::new(storage, &path));
+    }
+    let hooks = cli.hooks.clone().or_else(|| paths::config_dir().map(|dir| dir.join("hooks")));
+    if let Some(hooks) = hooks.filter(|_| !cli.read_only) {
+        storage = HookStorage::wrap(storage, &path, &hooks);
+    }
+    if !cli.read_only {
+        storage = ChatStorage::wrap(storage, config.chat_notifiers());
+    }
+    if !cli.read_only {
+        let keep = config.snapshots.unwrap_or(DEFAULT_SNAPSHOTS);
+        storage = SnapshotStorage::wrap(storage, &paths::snapshot_dir(&path), keep);
+    }
+    let archive_storage = open_storage(&cli, &paths::archive_file(&path));
+    let trash_storage = open_storage(&cli, &paths::trash_file(&path));
+
+    // The interactive menu can change the list too
+    let _lock = match &cli.command {
+        _ if cli.read_only => None,
+        Some(command) if !command.changes_list() => None,
+        _ => Some(lock::acquire(&path)?),
+    };
+
+    if let Some(Command::Doctor) = &cli.command {
+        return doctor(&cli, storage.as_ref(), &path);
+    }
+    // Neither copy is the list itself, which is not even loaded
+    if let Some(Command::Diff { old, new }) = &cli.command {
+        return diff(old, new);
+    }
+
+    // A journal left behind is from a menu session that was killed before
+    // it could save, and its changes are only seen by loading everything.
+    // Otherwise one page of a big list is read on its own, where the
+    // backend can do that.
+    let journal_path = paths::journal_file(&path);
+    if let Some(Command::List { filter, tag, mine, waiting, archived, all, sort, output, limit: Some(limit), page: number, plugin_filter: None, plugin_format: None, by_project: false }) = &cli.command {
+        let filter = list_filter(if filter.is_empty() { &config.list } else { filter }, *mine, *waiting, cli.me.as_deref())?;
+        let (storage, file) = if *archived {
+            (archive_storage.as_ref(), paths::archive_file(&path))
+        } else {
+            (storage.as_ref(), path.clone())
+        };
+        let today = Date::today();
+        let keep = |task: &Task| {
+            (*all || !task.is_hidden(today))
+                && tag.as_ref().is_none_or(|tag| task.has_tag(tag))
+                && filter.as_ref().is_none_or(|filter| filter.matches(task, today))
+        };
+        if file.exists() && (cli.read_only || !journal_path.exists()) {
+            let sort = sort.or(config.sort).unwrap_or_default();
+            if let Some(mut page) = storage.load_page(sort, page_offset(*number, *limit), *limit, &keep)? {
+                show_as_configured(&mut page.list, &config);
+                print_listing(&page.list, &page.tasks(), *output, cli.no_pager)?;
+                return Ok(());
+            }
+        }
+    }
+
+    let unsaved = !path.exists();
+    let mut todo_list = match load_or_import(storage.as_ref(), &path, cli.file.is_some(), cli.strict) {
+        Err(TodoError::Json(e)) if path.exists() => {
+            return Err(TodoError::Parse(tr!("list-unreadable", path = path.display().to_string(), error = e.to_string())))
+        }
+        result => result?,
+    };
+
+    // Commands that only read the list still see a journal's changes, but
+    // read-only shows the file as it is
+    let recovered = if cli.read_only { 0 } else { journal::replay(&journal_path, &mut todo_list)? };
+    if recovered > 0 {
+        eprintln!("{}", tr!("journal-recovered", count = recovered));
+        if _lock.is_some() {
+            storage.save(&todo_list)?;
+            fs::remove_file(&journal_path)?;
+        }
+    }
+
+    todo_list.set_auto_complete_parents(!cli.no_auto_complete);
+    todo_list.set_urgency_weights(cli.urgency.clone().unwrap_or_default());
+    show_as_configured(&mut todo_list, &config);
+
+    let command = match cli.command {
+        Some(command) => command,
+        None => {
+            let journal = Journal::open(journal_path, &todo_list)?;
+            let (archive, trash) = (archive_storage.as_ref(), trash_storage.as_ref());
+            let autosave = cli.autosave.or(config.autosave).unwrap_or(Autosave::OnExit);
+            return menu::run(&mut todo_list, storage.as_ref(), archive, trash, journal, autosave);
+        }
+    };
+
+    match command {
+        Command::Add {
+            description,
+            stdin,
+            literal,
+            due,
+            start,
+            priority,
+            tag,
+            repeat,
+            estimate,
+            assign,
+            field,
+            parent,
+            depends_on,
+        } => {
+            // Blank lines in piped input are skipped rather than rejected
+            let descriptions = if stdin {
+                let lines: Vec<String> = io::stdin().lines().collect::<io::Result<_>>()?;
+                lines.into_iter().filter(|line| !line.trim().is_empty()).collect()
+            } else {
+                vec![description.join(" ")]
+            };
+            // Options given as flags win over those in the description
+            let mut captures = Vec::new();
+            for description in descriptions {
+                let mut capture = if literal {
+                    Capture { description, ..Capture::default() }
+                } else {
+                    capture::parse(&description, Date::today())?
+                };
+                capture.due_date = due.or(capture.due_date);
+                capture.start_date = start.or(capture.start_date);
+                capture.priority = priority.or(capture.priority);
+                for tag in &tag {
+                    if !capture.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                        capture.tags.push(tag.clone());
+                    }
+                }
+                captures.push(capture);
+            }
+            // Piped tasks are added as they are, without asking about each
+            let similar = match captures.as_slice() {
+                [capture] if !stdin && !cli.yes => todo_list.find_similar(&capture.description),
+                _ => None,
+            };
+            if let Some(existing) = similar.cloned() {
+                let id = existing.id;
+                let capture = &captures[0];
+                match confirm::duplicate(&existing)? {
+                    Duplicate::Add => {}
+                    Duplicate::Cancel => return Ok(()),
+                    Duplicate::Merge => {
+                        for tag in capture.tags.iter().filter(|tag| !existing.has_tag(tag)) {
+                            todo_list.add_tag(id, tag)?;
+                        }
+                        if capture.due_date.is_some() {
+                            todo_list.set_due_date(id, capture.due_date)?;
+                        }
+                        if capture.start_date.is_some() {
+                            todo_list.set_start_date(id, capture.start_date)?;
+                        }
+                        if repeat.is_some() {
+                            todo_list.set_recurrence(id, repeat)?;
+                        }
+                        if estimate.is_some() {
+                            todo_list.set_estimate(id, estimate)?;
+                        }
+                        if assign.is_some() {
+                            todo_list.assign(id, assign.as_deref())?;
+                        }
+                        for (name, value) in &field {
+                            todo_list.set_field(id, name, value)?;
+                        }
+                        for &on in &depends_on {
+                            todo_list.add_dependency(id, on)?;
+                        }
+                        println!("{}", tr!("merged-into", id = id));
+                        storage.save(&todo_list)?;
+                        return Ok(());
+                    }
+                }
+            }
+
+            let mut task_ids = Vec::new();
+            for capture in captures {
+                let priority = capture.priority.unwrap_or_default();
+                let task_id = match parent {
+                    Some(parent_id) => todo_list.add_subtask(parent_id, capture.description, capture.due_date, priority)?,
+                    None => todo_list.add_task(capture.description, capture.due_date, priority)?,
+                };
+                for tag in &capture.tags {
+                    todo_list.add_tag(task_id, tag)?;
+                }
+                if repeat.is_some() {
+                    todo_list.set_recurrence(task_id, repeat)?;
+                }
+                if estimate.is_some() {
+                    todo_list.set_estimate(task_id, estimate)?;
+                }
+                if assign.is_some() {
+                    todo_list.assign(task_id, assign.as_deref())?;
+                }
+                if capture.start_date.is_some() {
+                    todo_list.set_start_date(task_id, capture.start_date)?;
+                }
+                for (name, value) in &field {
+                    todo_list.set_field(task_id, name, value)?;
+                }
+                for &on in &depends_on {
+                    todo_list.add_dependency(task_id, on)?;
+                }
+                println!("{}", tr!("task-added", id = task_id));
+                task_ids.push(task_id);
+            }
+            if task_ids.is_empty() {
+                println!("{}", tr!("no-tasks"));
+                return Ok(());
+            }
+
+            // A new or imported list has not been written yet, so it needs a
+            // full save, as do several tasks at once
+            if let (false, [task_id]) = (unsaved, task_ids.as_slice()) {
+                if let Some(task) = todo_list.task(*task_id) {
+                    storage.append(task)?;
+                }
+                return Ok(());
+            }
+        }
+        Command::List { filter, tag, mine, waiting, archived, all, sort, output, limit, page, plugin_filter, plugin_format, by_project } => {
+            let filter = list_filter(if filter.is_empty() { &config.list } else { &filter }, mine, waiting, cli.me.as_deref())?;
+            let plugins = match plugin_filter.is_some() || plugin_format.is_some() {
+                true => Some(load_plugins(cli.plugins.as_deref())?),
+                false => None,
+            };
+            let mut list = if archived { archive_storage.load()? } else { todo_list };
+            show_as_configured(&mut list, &config);
+            list.set_sort_order(sort.or(config.sort).unwrap_or_default());
+            list.set_urgency_weights(cli.urgency.unwrap_or_default());
+            list.set_show_snoozed(all);
+            let mut tasks = match &tag {
+                Some(tag) => list.tasks_with_tag(tag),
+                None => list.sorted_tasks(),
+            };
+            if let Some(filter) = filter {
+                let today = Date::today();
+                tasks.retain(|task| filter.matches(task, today));
+            }
+            if let (Some(plugins), Some(name)) = (&plugins, &plugin_filter) {
+                tasks = plugins.filter(&list, name, tasks)?;
+            }
+            let limit = limit.unwrap_or(usize::MAX);
+            let tasks: Vec<&Task> = tasks.into_iter().skip(page_offset(page, limit)).take(limit).collect();
+            match (&plugins, &plugin_format) {
+                (Some(plugins), Some(name)) => println!("{}", plugins.format(&list, name, &tasks)?.trim_end_matches('\n')),
+                _ if by_project => print_text(&list.format_by_project(&tasks), cli.no_pager)?,
+                _ => print_listing(&list, &tasks, output, cli.no_pager)?,
+            }
+            return Ok(());
+        }
+        Command::Search { query, exact, regex, sort, output } => {
+            let query = query.join(" ");
+            todo_list.set_sort_order(sort.or(config.sort).unwrap_or_default());
+            let found = match (exact, regex) {
+                (_, true) => todo_list.search_regex(&query)?,
+                (true, false) => todo_list.search_exact(&query),
+                (false, false) => todo_list.search(&query),
+            };
+            print_listing(&todo_list, &found, output, cli.no_pager)?;
+            return Ok(());
+        }
+        Command::Archive { dry_run: true } => {
+            let before = todo_list.clone();
+            todo_list.archive_completed();
+            return dry_run("dry-run-list", &before, &todo_list);
+        }
+        Command::Archive { dry_run: false } => {
+            let count = archive_completed(&mut todo_list, archive_storage.as_ref())?;
+            println!("{}", tr!("archived", count = count));
+        }
+        Command::Restore { id } => {
+            restore_archived(&mut todo_list, storage.as_ref(), archive_storage.as_ref(), id)?;
+            println!("{}", tr!("task-restored", id = id));
+            return Ok(());
+        }
+        Command::Done { ids, tag, force, dry_run: dry } => {
+            let ids = match tag {
+                Some(tag) => todo_list.tasks().iter()
+                    .filter(|task| !task.completed && task.has_tag(&tag))
+                    .map(|task| task.id)
+                    .collect(),
+                None => ids,
+            };
+            let before = dry.then(|| todo_list.clone());
+            if before.is_none() && !confirm_batch(&todo_list, "confirm-complete", &ids, cli.yes, false)? {
+                return Ok(());
+            }
+            let next_ids = if force {
+                todo_list.force_complete_tasks(&ids)?
+            } else {
+                todo_list.complete_tasks(&ids)?
+            };
+            if let Some(before) = before {
+                return dry_run("dry-run-list", &before, &todo_list);
+            }
+            for (id, next_id) in ids.iter().zip(next_ids) {
+                match next_id {
+                    Some(next_id) => println!("{}", tr!("task-completed-next", id = *id, next = next_id)),
+                    None => println!("{}", tr!("task-completed", id = *id)),
+                }
+            }
+        }
+        Command::Start { id } => {
+            if let Some(stopped) = todo_list.start_timer(id)? {
+                println!("{}", tr!("timer-stopped", id = stopped));
+            }
+            println!("{}", tr!("timer-started", id = id));
+        }
+        Command::Stop => {
+            let (id, seconds) = todo_list.stop_timer()?;
+            println!("{}", tr!("timer-stopped-after", id = id, time = stats::format_time(seconds)));
+        }
+        Command::Pomodoro { id, work, rest, cycles } => {
+            // The list is saved as each period starts and ends
+            return pomodoro::run(&mut todo_list, storage.as_ref(), id, work, rest, cycles);
+        }
+        Command::Review => {
+            review::run(&mut todo_list, trash_storage.as_ref())?;
+        }
+        Command::Snooze { id, duration, hide } => {
+            let due = todo_list.snooze(id, duration, hide)?;
+            if hide {
+                println!("{}", tr!("task-snoozed-hidden", id = id, date = due.to_string()));
+            } else {
+                println!("{}", tr!("task-snoozed", id = id, date = due.to_string()));
+            }
+        }
+        Command::Defer { id, start } => {
+            let start = match start.trim().to_lowercase().as_str() {
+                "none" => None,
+                _ => Some(Date::parse_natural(&start, Date::today())?),
+            };
+            todo_list.set_start_date(id, start)?;
+            match start {
+                Some(start) => println!("{}", tr!("task-deferred", id = id, date = start.to_string())),
+                None => println!("{}", tr!("start-cleared", id = id)),
+            }
+        }
+        Command::Repeat { id, recurrence } => {
+            todo_list.set_recurrence(id, Recurrence::parse(&recurrence)?)?;
+            println!("{}", tr!("recurrence-updated", id = id));
+        }
+        Command::Estimate { id, estimate } => {
+            todo_list.set_estimate(id, Estimate::parse(&estimate)?)?;
+            println!("{}", tr!("estimate-updated", id = id));
+        }
+        Command::Rm { ids, completed, dry_run: dry } => {
+            let ids = if completed {
+                todo_list.iter_completed().map(|task| task.id).collect()
+            } else {
+                ids
+            };
+            if dry {
+                let before = todo_list.clone();
+                todo_list.remove_tasks(&ids)?;
+                return dry_run("dry-run-list", &before, &todo_list);
+            }
+            if !confirm_batch(&todo_list, "confirm-remove", &ids, cli.yes, true)? {
+                return Ok(());
+            }
+            trash_tasks(&mut todo_list, trash_storage.as_ref(), &ids)?;
+            for id in ids {
+                println!("{}", tr!("task-trashed", id = id));
+            }
+        }
+        Command::Trash { command } => match command {
+            TrashCommand::List => {
+                let mut trash_list = trash_storage.load()?;
+                trash_list.set_show_snoozed(true);
+                print!("{}", trash_list.format_task_list(&trash_list.sorted_tasks()));
+                return Ok(());
+            }
+            TrashCommand::Restore { id } => {
+                restore_trashed(&mut todo_list, storage.as_ref(), trash_storage.as_ref(), id)?;
+                println!("{}", tr!("task-restored", id = id));
+                return Ok(());
+            }
+            TrashCommand::Empty { older_than, dry_run: dry } => {
+                let trash_list = trash_storage.load()?;
+                let ids = storage::expired(&trash_list, older_than);
+                if dry {
+                    let mut after = trash_list.clone();
+                    after.remove_tasks(&ids)?;
+                    return dry_run("dry-run-trash", &trash_list, &after);
+                }
+                if ids.is_empty() {
+                    println!("{}", tr!("no-tasks"));
+                    return Ok(());
+                }
+                let tasks: Vec<_> = ids.iter().filter_map(|&id| trash_list.task(id)).collect();
+                if !cli.yes && !confirm::ask(&tr!("confirm-delete", count = tasks.len()), &tasks)? {
+                    return Ok(());
+                }
+                let count = empty_trash(trash_storage.as_ref(), older_than)?;
+                println!("{}", tr!("trash-emptied", count = count));
+                return Ok(());
+            }
+        },
+        Command::Export { format, output } => {
+            let text = match format {
+                ExportFormat::Todotxt => todotxt::export(&todo_list),
+                ExportFormat::Ics => ical::export(&todo_list),
+                ExportFormat::Md => markdown::export(&todo_list),
+                ExportFormat::Csv => csv::export(&todo_list)?,
+            };
+            match output {
+                Some(path) => fs::write(path, text)?,
+                None => print!("{}", text),
+            }
+            return Ok(());
+        }
+        Command::Import { format, input, dry_run: dry } => {
+            let Some(format) = format.or_else(|| ImportFormat::from_path(&input)) else {
+                return Err(TodoError::Parse(tr!("unknown-import-format", path = input.display().to_string())));
+            };
+            let text = fs::read_to_string(input)?;
+            let before = dry.then(|| todo_list.clone());
+            let summary = match format {
+                ImportFormat::Todotxt => ImportSummary {
+                    added: todotxt::import(&mut todo_list, &text)?,
+                    skipped: 0,
+                },
+                ImportFormat::Todoist => import::todoist(&mut todo_list, text.as_bytes())?,
+                ImportFormat::Trello => import::trello(&mut todo_list, text.as_bytes())?,
+                ImportFormat::Taskwarrior => import::taskwarrior(&mut todo_list, text.as_bytes())?,
+                ImportFormat::Md => ImportSummary {
+                    added: markdown::import(&mut todo_list, &text)?,
+                    skipped: 0,
+                },
+                ImportFormat::Csv => ImportSummary {
+                    added: csv::import(&mut todo_list, &text)?,
+                    skipped: 0,
+                },
+            };
+            if let Some(before) = before {
+                return dry_run("dry-run-list", &before, &todo_list);
+            }
+            println!("{}", tr!("imported", count = summary.added.len()));
+            if summary.skipped > 0 {
+                println!("{}", tr!("import-skipped", count = summary.skipped));
+            }
+        }
+        Command::Merge { input, dry_run: dry } => {
+            let other = load_file(&input)?;
+            let before = dry.then(|| todo_list.clone());
+            let summary = merge::merge(&mut todo_list, &other);
+            if let Some(before) = before {
+                return dry_run("dry-run-list", &before, &todo_list);
+            }
+            let (added, updated) = (summary.added.len(), summary.updated.len());
+            println!("{}", tr!("merged", added = added, updated = updated, skipped = summary.skipped));
+        }
+        Command::Show { id } => {
+            print!("{}", todo_list.format_task(id)?);
+            return Ok(());
+        }
+        Command::Comment { id, text } => {
+            todo_list.add_comment(id, &text.join(" "))?;
+            println!("{}", tr!("comment-added", id = id));
+        }
+        Command::Attach { id, attachments } => {
+            for attachment in attachments {
+                // A path that is not absolute would point elsewhere when run
+                // from another directory
+                let attachment = if attachment.contains("://") {
+                    attachment
+                } else {
+                    fs::canonicalize(&attachment)
+                        .map_err(|e| TodoError::Parse(tr!("attach-failed", path = attachment.as_str(), error = e.to_string())))?
+                        .display()
+                        .to_string()
+                };
+                todo_list.attach(id, &attachment)?;
+                println!("{}", tr!("attached", id = id, attachment = attachment));
+            }
+        }
+        Command::Detach { id, number } => {
+            let attachment = todo_list.detach(id, number)?;
+            println!("{}", tr!("detached", id = id, attachment = attachment));
+        }
+        Command::Open { id, number } => {
+            let attachment = todo_list.attachment(id, number)?;
+            println!("{}", tr!("opening", attachment = attachment));
+            opener::open(attachment)?;
+            return Ok(());
+        }
+        Command::Notes { id, clear } => {
+            let notes = if clear { None } else { Some(editor::edit_notes(&todo_list, id)?) };
+            todo_list.set_notes(id, notes)?;
+            println!("{}", tr!("notes-updated", id = id));
+        }
+        Command::Tag { id, tag } => {
+            todo_list.add_tag(id, &tag)?;
+            println!("{}", tr!("tagged", id = id));
+        }
+        Command::Untag { id, tag } => {
+            todo_list.remove_tag(id, &tag)?;
+            println!("{}", tr!("untagged", id = id));
+        }
+        Command::Move { id, column, before, to_top } => {
+            match (column, before) {
+                (Some(column), _) => match todo_list.move_to_column(id, column)? {
+                    Some(next_id) => println!("{}", tr!("task-completed-next", id = id, next = next_id)),
+                    None if column == Column::Done => println!("{}", tr!("task-completed", id = id)),
+                    None => {
+                        println!("{}", tr!("task-moved-to", id = id, column = column.label()));
+                        kanban::warn_over_wip_limit(&todo_list, column);
+                    }
+                },
+                (None, Some(before)) => {
+                    todo_list.move_before(id, before)?;
+                    println!("{}", tr!("task-moved-before", id = id, before = before));
+                }
+                (None, None) if to_top => {
+                    todo_list.move_to_top(id)?;
+                    println!("{}", tr!("task-moved-to-top", id = id));
+                }
+                (None, None) => unreachable!("clap asks for a column, --before or --to-top"),
+            }
+        }
+        Command::Assign { id, name } => {
+            let name = match name {
+                Some(name) => name,
+                None => me(cli.me.as_deref())?,
+            };
+            todo_list.assign(id, Some(&name))?;
+            println!("{}", tr!("task-assigned", id = id, name = name));
+        }
+        Command::Unassign { id } => {
+            todo_list.assign(id, None)?;
+            println!("{}", tr!("task-unassigned", id = id));
+        }
+        Command::Wait { id, on, follow_up } => {
+            let on = on.join(" ");
+            todo_list.wait(id, Some(&on), follow_up)?;
+            match on.trim() {
+                "" => println!("{}", tr!("task-waiting", id = id)),
+                on => println!("{}", tr!("task-waiting-on", id = id, who = on)),
+            }
+        }
+        Command::Unwait { id } => {
+            todo_list.stop_waiting(id)?;
+            println!("{}", tr!("task-stopped-waiting", id = id));
+        }
+        Command::Set { id, fields } => {
+            for (name, value) in &fields {
+                todo_list.set_field(id, name, value)?;
+            }
+            println!("{}", tr!("fields-updated", id = id));
+        }
+        Command::Unset { id, names } => {
+            for name in &names {
+                todo_list.remove_field(id, name)?;
+            }
+            println!("{}", tr!("fields-removed", id = id));
+        }
+        Command::Depend { id, on } => {
+            todo_list.add_dependency(id, on)?;
+            println!("{}", tr!("dependency-added", id = id, on = on));
+        }
+        Command::Undepend { id, on } => {
+            todo_list.remove_dependency(id, on)?;
+            println!("{}", tr!("dependency-removed", id = id, on = on));
+        }
+        Command::Tags => {
+            for tag in todo_list.list_tags() {
+                println!("{}", tag);
+            }
+            return Ok(());
+        }
+        Command::Contexts => {
+            for context in todo_list.list_contexts() {
+                println!("@{}", context);
+            }
+            return Ok(());
+        }
+        Command::Board { filter, mine } => {
+            let filter = list_filter(&filter, mine, false, cli.me.as_deref())?;
+            todo_list.set_sort_order(config.sort.unwrap_or_default());
+            let mut tasks = todo_list.sorted_tasks();
+            if let Some(filter) = filter {
+                let today = Date::today();
+                tasks.retain(|task| filter.matches(task, today));
+            }
+            print_text(&todo_list.format_board(&tasks), cli.no_pager)?;
+            return Ok(());
+        }
+        Command::Sync { merge, .. } if cli.git => {
+            if !matches!(cli.backend, Backend::Json) {
+                return Err(TodoError::Sync(tr!("git-needs-json")));
+            }
+            let report = git::sync(&mut todo_list, storage.as_ref(), &path, merge)?;
+            if !report.conflicts.is_empty() {
+                for conflict in &report.conflicts {
+                    println!("{}", conflict);
+                }
+                if report.merged.is_none() {
+                    println!("{}", tr!("git-nothing-pulled"));
+                    return Ok(());
+                }
+            }
+            if let Some(summary) = report.merged {
+                println!("{}", tr!("git-merged", added = summary.added.len(), updated = summary.updated.len()));
+            }
+            println!("{}", tr!("git-synced", pulled = report.pulled, pushed = report.pushed));
+            // The data file may have changed under the loaded list
+            return Ok(());
+        }
+        Command::Sync { url, user, password, prefer, .. } => {
+            let url = url.ok_or_else(|| TodoError::Sync(tr!("caldav-no-url")))?;
+            let mut client = caldav::Client::new(&url)?;
+            if let Some(user) = user {
+                client = client.with_login(&user, password.as_deref().unwrap_or(""));
+            }
+            let prefer = prefer.map(|side| match side {
+                SyncSide::Local => caldav::Side::Local,
+                SyncSide::Remote => caldav::Side::Remote,
+            });
+
+            let state_path = paths::sync_state_file(&path);
+            let mut state = SyncState::load(&state_path)?;
+            let result = caldav::sync(&mut todo_list, &mut state, &client, prefer);
+            // Whatever got done before an error is remembered
+            storage.save(&todo_list)?;
+            state.save(&state_path)?;
+            let report = result?;
+
+            println!(
+                "{}",
+                tr!(
+                    "caldav-synced",
+                    pushed = report.pushed,
+                    pulled = report.pulled,
+                    deleted_remote = report.deleted_remote,
+                    deleted_local = report.deleted_local
+                )
+            );
+            for id in report.conflicts {
+                println!("{}", tr!("caldav-conflict", id = id));
+            }
+            return Ok(());
+        }
+        Command::Serve { port, host } => {
+            let address = format!("{}:{}", host, port);
+            return serve::run(
+                &address,
+                storage.as_ref(),
+                trash_storage.as_ref(),
+                &path,
+                !cli.no_auto_complete,
+            );
+        }
+        Command::Agenda { week } => {
+            print!("{}", Agenda::new(&todo_list, Date::today(), if week { 7 } else { 2 }));
+            return Ok(());
+        }
+        Command::Digest { email, output, days } => {
+            let archived = archive_storage.load()?;
+            let digest = Digest::new(todo_list.tasks().iter().chain(archived.tasks()), Date::today(), days);
+            if email.is_none() && output.is_none() {
+                print!("{}", digest);
+                return Ok(());
+            }
+            let message = digest.to_email(config.email.from.as_deref(), email.as_deref(), Timestamp::now())?;
+            if let Some(path) = &output {
+                fs::write(path, &message)?;
+            }
+            if let Some(to) = &email {
+                mail::send(config.email.sendmail.as_deref().unwrap_or(mail::DEFAULT_SENDMAIL), &message)?;
+                println!("{}", tr!("digest-sent", to = to.as_str()));
+            }
+            return Ok(());
+        }
+        Command::Stats { output } => {
+            output::print_report(&Report::new(&todo_list, Date::today()), output)?;
+            return Ok(());
+        }
+        Command::Report { format, output } => {
+            let text = match format {
+                ReportFormat::Html => html::report(&todo_list, Date::today()),
+            };
+            match output {
+                Some(path) => fs::write(path, text)?,
+                None => print!("{}", text),
+            }
+            return Ok(());
+        }
+        Command::Notify { within } => {
+            let notifiers = config.notifiers();
+            let chats = config.chat_notifiers();
+            if notifiers.is_empty() && chats.is_empty() {
+                return Err(TodoError::Notify(tr!("notify-no-notifiers")));
+            }
+            let last_day = Date::today().add_days(within.into());
+            let due: Vec<Task> = todo_list.pending_reminders(last_day).into_iter().cloned().collect();
+            for task in due {
+                // A reminder none of them could deliver is tried again next run
+                let mut delivered = false;
+                for notifier in &notifiers {
+                    match notifier.notify(Event::Due, &task) {
+                        Ok(()) => delivered = true,
+                        Err(e) => {
+                            let message = tr!("notify-failed", id = task.id, notifier = notifier.name(), error = e.to_string());
+                            eprintln!("{}", tr!("error", message = message));
+                        }
+                    }
+                }
+                if delivered {
+                    todo_list.mark_reminded(task.id)?;
+                }
+            }
+            if !chats.is_empty() {
+                let overdue: Vec<Task> = todo_list.newly_overdue(Date::today()).into_iter().cloned().collect();
+                for task in overdue {
+                    if chat::post(&chats, Event::Overdue, &task) {
+                        todo_list.mark_announced_overdue(task.id)?;
+                    }
+                }
+            }
+        }
+        Command::Pick { action, all } => {
+            let Some(id) = picker::pick(&todo_list, all)? else {
+                return Ok(());
+            };
+            match action {
+                None => {
+                    println!("{}", id);
+                    return Ok(());
+                }
+                Some(PickAction::Show) => {
+                    print!("{}", todo_list.format_task(id)?);
+                    return Ok(());
+                }
+                Some(PickAction::Done) => match todo_list.complete_task(id)? {
+                    Some(next_id) => println!("{}", tr!("task-completed-next", id = id, next = next_id)),
+                    None => println!("{}", tr!("task-completed", id = id)),
+                },
+                Some(PickAction::Rm) => {
+                    trash_tasks(&mut todo_list, trash_storage.as_ref(), &[id])?;
+                    println!("{}", tr!("task-trashed", id = id));
+                }
+                Some(PickAction::Edit) => {
+                    let current = todo_list.task(id).map(|task| task.description.clone()).unwrap_or_default();
+                    todo_list.edit_task(id, picker::read_description(&current)?)?;
+                    println!("{}", tr!("task-updated", id = id));
+                }
+            }
+        }
+        Command::Reopen { id } => {
+            todo_list.reopen_task(id)?;
+            println!("{}", tr!("task-reopened", id = id));
+        }
+        Command::Renumber => {
+            if !cli.yes {
+                eprintln!("{}", tr!("warning", message = tr!("renumber-warning")));
+                if !confirm::question(&tr!("confirm-renumber"))? {
+                    return Ok(());
+                }
+            }
+            let count = renumber(&mut todo_list, archive_storage.as_ref(), trash_storage.as_ref())?;
+            if count == 0 {
+                println!("{}", tr!("renumber-nothing"));
+                return Ok(());
+            }
+            println!("{}", tr!("renumbered", count = count));
+        }
+        // Checked before the list is loaded, which a damaged file would fail
+        Command::Doctor => unreachable!("todo doctor runs before the list is loaded"),
+        Command::Diff { .. } => unreachable!("todo diff runs before the list is loaded"),
+        Command::At { .. } => unreachable!("todo at runs before the list is loaded"),
+        Command::EditAll => {
+            let text = editor::edit_text(&batch::write(&todo_list), ".txt")?;
+            let edit = batch::apply(&mut todo_list, &text)?;
+            if edit.is_empty() {
+                println!("{}", tr!("no-changes"));
+                return Ok(());
+            }
+            if !edit.removed.is_empty() {
+                trash_tasks(&mut todo_list, trash_storage.as_ref(), &edit.removed)?;
+            }
+            for id in &edit.added {
+                println!("{}", tr!("task-added", id = id));
+            }
+            for id in &edit.changed {
+                println!("{}", tr!("task-updated", id = id));
+            }
+            for id in &edit.completed {
+                println!("{}", tr!("task-completed", id = id));
+            }
+            for id in &edit.removed {
+                println!("{}", tr!("task-trashed", id = id));
+            }
+            if edit.reordered {
+                println!("{}", tr!("tasks-reordered"));
+            }
+        }
+        Command::Edit { id, description } => {
+            todo_list.edit_task(id, description.join(" "))?;
+            println!("{}", tr!("task-updated", id = id));
+        }
+        Command::Plugins => {
+            let plugins = load_plugins(cli.plugins.as_deref())?;
+            let (commands, filters, formats) = (plugins.commands(), plugins.filters(), plugins.formats());
+            if commands.is_empty() && filters.is_empty() && formats.is_empty() {
+                println!("{}", tr!("no-plugins"));
+            }
+            if !commands.is_empty() {
+                println!("{}:", tr!("plugins-commands"));
+                let width = commands.iter().map(|(name, _)| name.len()).max().unwrap_or_default();
+                for (name, about) in &commands {
+                    println!("  {}", format!("{:<width$}  {}", name, about).trim_end());
+                }
+            }
+            if !filters.is_empty() {
+                println!("{}: {}", tr!("plugins-filters"), filters.join(", "));
+            }
+            if !formats.is_empty() {
+                println!("{}: {}", tr!("plugins-formats"), formats.join(", "));
+            }
+            return Ok(());
+        }
+        Command::Plugin(args) => {
+            let (name, args) = args.split_first().expect("clap gives the command name first");
+            if !load_plugins(cli.plugins.as_deref())?.run_command(&mut todo_list, name, args)? {
+                return Err(TodoError::Parse(tr!("unknown-command", name = name.as_str())));
+            }
+        }
+    }
+
+    storage.save(&todo_list)?;
+    Ok(())
+}
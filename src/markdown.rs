@@ -0,0 +1,104 @@
+//! GitHub-style Markdown checklists, one task per item with subtasks
+//! indented under their parent:
+//!
+//! ```text
+//! - [ ] plan the party #home due:2024-06-01
+//!   - [x] book the venue
+//!   - [ ] send invites
+//! ```
+//!
+//! Tags are written as `#tag` and due dates as `due:YYYY-MM-DD`. Comments
+//! are quoted under their task, `> 2024-05-20T09:30:00Z called the venue`.
+//! Priorities, notes and recurrence have no checklist equivalent and are
+//! left out. Anything in the file that is not a checklist item, such as
+//! headings, quotes or paragraphs, is skipped on import.
+
+use crate::{tr, Date, Task, TodoError, TodoList};
+
+/// Writes every task as a checklist item, subtasks indented two spaces
+/// under their parent.
+pub fn export(todo_list: &TodoList) -> String {
+    let mut text = String::new();
+    // Subtasks whose parent is not in the list are written at the top level
+    let roots = todo_list.tasks().iter().filter(|task| task.parent.is_none_or(|parent| todo_list.task(parent).is_none()));
+    for task in roots {
+        write_tree(&mut text, todo_list, task, 0);
+    }
+    text
+}
+
+fn write_tree(text: &mut String, todo_list: &TodoList, task: &Task, depth: usize) {
+    let check = if task.completed { "x" } else { " " };
+    let tags: String = task.tags.iter().map(|tag| format!(" #{}", tag)).collect();
+    let due = task.due_date.map(|due| format!(" due:{}", due)).unwrap_or_default();
+    text.push_str(&format!("{}- [{}] {}{}{}\n", "  ".repeat(depth), check, task.description, tags, due));
+    for comment in &task.comments {
+        text.push_str(&format!("{}> {} {}\n", "  ".repeat(depth + 1), comment.at, comment.text));
+    }
+    for child in todo_list.subtasks(task.id) {
+        write_tree(text, todo_list, child, depth + 1);
+    }
+}
+
+/// Adds the checklist items in Markdown `text` to `todo_list` with new IDs
+/// and returns those IDs. An item indented under another becomes its
+/// subtask.
+pub fn import(todo_list: &mut TodoList, text: &str) -> Result<Vec<usize>, TodoError> {
+    let mut tasks: Vec<Task> = Vec::new();
+    // The indent and position in `tasks` of the items enclosing the current one
+    let mut open: Vec<(usize, usize)> = Vec::new();
+
+    for (line_num, line) in text.lines().enumerate() {
+        let indent: usize = line.chars()
+            .take_while(|c| c.is_whitespace())
+            .map(|c| if c == '\t' { 4 } else { 1 })
+            .sum();
+        let Some((completed, item)) = parse_item(line.trim()) else {
+            continue;
+        };
+        let mut task = parse_words(item).map_err(|e| TodoError::Parse(tr!("in-line", error = e.to_string(), line = line_num + 1)))?;
+        task.completed = completed;
+
+        while open.last().is_some_and(|&(open_indent, _)| open_indent >= indent) {
+            open.pop();
+        }
+        // Imported tasks are numbered in order from the next free ID
+        task.parent = open.last().map(|&(_, position)| todo_list.next_id + position);
+        open.push((indent, tasks.len()));
+        tasks.push(task);
+    }
+    Ok(todo_list.add_imported(tasks))
+}
+
+// "- [x] text" gives the completion and the text; other lines give nothing
+fn parse_item(line: &str) -> Option<(bool, &str)> {
+    let rest = line.strip_prefix(['-', '*', '+'])?.trim_start();
+    let completed = match rest.get(..3)? {
+        "[ ]" => false,
+        "[x]" | "[X]" => true,
+        _ => return None,
+    };
+    Some((completed, rest[3..].trim()))
+}
+
+fn parse_words(item: &str) -> Result<Task, TodoError> {
+    let mut task = Task::default();
+    let mut words = Vec::new();
+    for word in item.split_whitespace() {
+        if let Some(tag) = word.strip_prefix('#').filter(|tag| !tag.is_empty()) {
+            let tag = tag.to_lowercase();
+            if !task.tags.contains(&tag) {
+                task.tags.push(tag);
+            }
+        } else if let Some(due) = word.strip_prefix("due:") {
+            task.due_date = Some(Date::parse(due)?);
+        } else {
+            words.push(word);
+        }
+    }
+    task.description = words.join(" ");
+    if task.description.is_empty() {
+        return Err(TodoError::EmptyDescription);
+    }
+    Ok(task)
+}
@@ -0,0 +1,397 @@
+//! The interactive numbered menu used when no subcommand is given.
+
+use std::io;
+use std::time::Instant;
+
+use todo_list::journal::Journal;
+use todo_list::storage::{self, Storage};
+use todo_list::{tr, Date, Priority, Recurrence, TodoError, TodoList, Uuid};
+
+use crate::cli::Autosave;
+use crate::confirm::{self, Duplicate};
+use crate::editor;
+use crate::kanban;
+use crate::readline::{Complete, Readline};
+use crate::signals::{self, Stop};
+
+// The IDs of the menu's messages, in the order they are numbered
+const MENU: [&str; 21] = [
+    "menu-add",
+    "menu-list",
+    "menu-complete",
+    "menu-remove",
+    "menu-set-due",
+    "menu-set-priority",
+    "menu-edit",
+    "menu-undo",
+    "menu-redo",
+    "menu-filter-tag",
+    "menu-add-tag",
+    "menu-remove-tag",
+    "menu-set-recurrence",
+    "menu-add-subtask",
+    "menu-archive",
+    "menu-list-archived",
+    "menu-restore",
+    "menu-show",
+    "menu-edit-notes",
+    "menu-board",
+    "menu-save-exit",
+];
+
+// Prints `message` and reads one line of input
+fn prompt(readline: &mut Readline, message: &str) -> io::Result<String> {
+    readline.line(message, &Complete::Nothing)
+}
+
+// Prompts for a menu choice, by number or by name, ignoring case, with Tab
+// completing names
+fn read_choice(readline: &mut Readline, names: &[String]) -> io::Result<Option<usize>> {
+    let input = readline.line(&tr!("menu-enter-choice"), &Complete::Names(names))?;
+    let input = input.trim();
+    if let Ok(number) = input.parse() {
+        return Ok(Some(number));
+    }
+    match names.iter().position(|name| name.to_lowercase() == input.to_lowercase()) {
+        Some(index) => Ok(Some(index + 1)),
+        None => {
+            println!("{}", tr!("menu-not-a-number"));
+            Ok(None)
+        }
+    }
+}
+
+// Prompts for a task ID, telling the user when it is not a number, with Tab
+// completing the IDs of the tasks in `todo_list`
+fn read_id(readline: &mut Readline, todo_list: &TodoList, message: &str) -> io::Result<Option<usize>> {
+    let input = readline.line(message, &Complete::Ids(todo_list))?;
+    match input.trim().parse() {
+        Ok(id) => Ok(Some(id)),
+        Err(_) => {
+            println!("{}", tr!("menu-invalid-id"));
+            Ok(None)
+        }
+    }
+}
+
+// Prompts for an optional due date; a blank line means no due date
+fn read_due_date(readline: &mut Readline) -> io::Result<Result<Option<Date>, TodoError>> {
+    let input = prompt(readline, &tr!("menu-enter-due"))?;
+
+    if input.trim().is_empty() {
+        return Ok(Ok(None));
+    }
+    Ok(Date::parse_natural(&input, Date::today()).map(Some))
+}
+
+// Prompts for a priority; a blank line means medium
+fn read_priority(readline: &mut Readline) -> io::Result<Result<Priority, TodoError>> {
+    let input = prompt(readline, &tr!("menu-enter-priority"))?;
+    Ok(Priority::parse(&input))
+}
+
+// Prints the outcome of a menu action
+fn report(result: Result<(), TodoError>, success: String) {
+    match result {
+        Ok(_) => println!("{}", success),
+        Err(e) => println!("{}", tr!("error", message = e.to_string())),
+    }
+}
+
+pub fn run(
+    todo_list: &mut TodoList,
+    storage: &dyn Storage,
+    archive: &dyn Storage,
+    trash: &dyn Storage,
+    mut journal: Journal,
+    autosave: Autosave,
+) -> Result<(), TodoError> {
+    signals::catch();
+    let mut readline = Readline::new();
+    let names: Vec<String> = MENU.iter().map(|item| todo_list::i18n::message(item, &[])).collect();
+    let mut unsaved = false;
+    let mut last_save = Instant::now();
+    // Tasks restored from the archive, which stay in it until the list is saved
+    let mut restored = Vec::new();
+    loop {
+        // Each change is journaled as it is made in case the session is
+        // killed before the list is next saved
+        unsaved |= journal.record(todo_list)?;
+        if unsaved && autosave.is_due(last_save.elapsed()) {
+            match storage.save(todo_list).and_then(|()| forget_restored(todo_list, archive, &mut restored)) {
+                Ok(()) => {
+                    journal.clear()?;
+                    unsaved = false;
+                    last_save = Instant::now();
+                }
+                Err(e) => println!("{}", tr!("menu-autosave-failed", message = e.to_string())),
+            }
+        }
+
+        println!("\n{}", tr!("menu-title"));
+        for (number, name) in names.iter().enumerate() {
+            println!("{}. {}", number + 1, name);
+        }
+
+        let result = match read_choice(&mut readline, &names) {
+            Ok(Some(choice)) if choice == MENU.len() => break,
+            Ok(Some(choice)) => act(choice, &mut readline, todo_list, archive, trash, &mut restored),
+            Ok(None) => Ok(()),
+            Err(e) => Err(e.into()),
+        };
+        match result {
+            Ok(()) => {}
+            // At the end of input, as from a script, the session ends as if
+            // saved and exited
+            Err(TodoError::Io(e)) if e.kind() == io::ErrorKind::UnexpectedEof => break,
+            // Ctrl-C at any prompt ends the session, first asking whether to
+            // keep what was done since the list was last saved. A kill, the
+            // terminal closing or Ctrl-C with a script typing leave nobody
+            // to ask, and the changes are saved.
+            Err(TodoError::Io(e)) if e.kind() == io::ErrorKind::Interrupted => {
+                unsaved |= journal.record(todo_list)?;
+                let stop = signals::take();
+                if !unsaved || stop == Some(Stop::Terminate) || !readline.is_interactive() {
+                    break;
+                }
+                match ask_to_save(&mut readline)? {
+                    Some(true) => break,
+                    Some(false) => {
+                        journal.compact()?;
+                        println!("{}", tr!("menu-changes-discarded"));
+                    }
+                    None => println!("{}", tr!("menu-changes-kept")),
+                }
+                return Ok(());
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    storage.save(todo_list)?;
+    forget_restored(todo_list, archive, &mut restored)?;
+    journal.compact()?;
+    println!("{}", tr!("menu-goodbye"));
+    Ok(())
+}
+
+// Takes the tasks restored during the session out of the archive, once the
+// list holding them is saved
+fn forget_restored(todo_list: &TodoList, archive: &dyn Storage, restored: &mut Vec<Uuid>) -> Result<(), TodoError> {
+    storage::forget_restored(todo_list, archive, restored)?;
+    restored.clear();
+    Ok(())
+}
+
+// Asks whether to save the changes made before Ctrl-C was pressed. Pressing
+// it again, or the input ending, answers nothing, and the changes are left
+// in the journal to be recovered the next time the list is opened. Being
+// killed while asking saves them.
+fn ask_to_save(readline: &mut Readline) -> io::Result<Option<bool>> {
+    let question = format!("{} {}", tr!("menu-save-before-quit"), tr!("confirm-choices"));
+    match prompt(readline, &question) {
+        Ok(answer) => Ok(Some(confirm::is_yes(&answer))),
+        Err(e) if e.kind() == io::ErrorKind::Interrupted && signals::take() == Some(Stop::Terminate) => Ok(Some(true)),
+        Err(e) if matches!(e.kind(), io::ErrorKind::Interrupted | io::ErrorKind::UnexpectedEof) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+// Does what menu choice `choice` says, other than saving and exiting
+fn act(
+    choice: usize,
+    readline: &mut Readline,
+    todo_list: &mut TodoList,
+    archive: &dyn Storage,
+    trash: &dyn Storage,
+    restored: &mut Vec<Uuid>,
+) -> Result<(), TodoError> {
+    match choice {
+        1 => {
+            let description = prompt(readline, &tr!("menu-enter-description"))?;
+
+            let due_date = match read_due_date(readline)? {
+                Ok(due_date) => due_date,
+                Err(e) => {
+                    println!("{}", tr!("error", message = e.to_string()));
+                    return Ok(());
+                }
+            };
+
+            let priority = match read_priority(readline)? {
+                Ok(priority) => priority,
+                Err(e) => {
+                    println!("{}", tr!("error", message = e.to_string()));
+                    return Ok(());
+                }
+            };
+
+            if let Some(existing) = todo_list.find_similar(&description) {
+                let id = existing.id;
+                match confirm::duplicate(existing)? {
+                    Duplicate::Add => {}
+                    Duplicate::Cancel => return Ok(()),
+                    Duplicate::Merge => {
+                        if due_date.is_some() {
+                            report(todo_list.set_due_date(id, due_date), tr!("due-updated", id = id));
+                        }
+                        report(todo_list.set_priority(id, priority), tr!("merged-into", id = id));
+                        return Ok(());
+                    }
+                }
+            }
+
+            match todo_list.add_task(description, due_date, priority) {
+                Ok(task_id) => println!("{}", tr!("task-added", id = task_id)),
+                Err(e) => println!("{}", tr!("error", message = e.to_string())),
+            }
+        }
+        2 => print!("{}", todo_list.format_task_list(&todo_list.sorted_tasks())),
+        3 => {
+            if let Some(id) = read_id(readline, todo_list, &tr!("menu-id-to-complete"))? {
+                match todo_list.complete_task(id) {
+                    Ok(Some(next_id)) => println!("{}", tr!("task-completed-next", id = id, next = next_id)),
+                    Ok(None) => println!("{}", tr!("task-completed", id = id)),
+                    Err(e) => println!("{}", tr!("error", message = e.to_string())),
+                }
+            }
+        }
+        4 => {
+            if let Some(id) = read_id(readline, todo_list, &tr!("menu-id-to-remove"))? {
+                let tasks = todo_list.with_subtasks(&[id]);
+                if tasks.is_empty() {
+                    println!("{}", tr!("error", message = TodoError::NotFound(id).to_string()));
+                    return Ok(());
+                }
+                if !confirm::ask(&tr!("confirm-remove", count = tasks.len()), &tasks)? {
+                    return Ok(());
+                }
+                match storage::trash_tasks(todo_list, trash, &[id]) {
+                    Ok(_) => println!("{}", tr!("task-trashed", id = id)),
+                    Err(e) => println!("{}", tr!("error", message = e.to_string())),
+                }
+            }
+        }
+        5 => {
+            if let Some(id) = read_id(readline, todo_list, &tr!("menu-id-to-set-due"))? {
+                match read_due_date(readline)? {
+                    Ok(due_date) => report(
+                        todo_list.set_due_date(id, due_date),
+                        tr!("due-updated", id = id),
+                    ),
+                    Err(e) => println!("{}", tr!("error", message = e.to_string())),
+                }
+            }
+        }
+        6 => {
+            if let Some(id) = read_id(readline, todo_list, &tr!("menu-id-to-set-priority"))? {
+                match read_priority(readline)? {
+                    Ok(priority) => report(
+                        todo_list.set_priority(id, priority),
+                        tr!("priority-updated", id = id),
+                    ),
+                    Err(e) => println!("{}", tr!("error", message = e.to_string())),
+                }
+            }
+        }
+        7 => {
+            if let Some(id) = read_id(readline, todo_list, &tr!("menu-id-to-edit"))? {
+                let description = prompt(readline, &tr!("menu-enter-new-description"))?;
+                report(todo_list.edit_task(id, description), tr!("task-updated", id = id));
+            }
+        }
+        8 => match todo_list.undo() {
+            Ok(label) => println!("{}", tr!("undid", change = label)),
+            Err(e) => println!("{}", tr!("error", message = e.to_string())),
+        },
+        9 => match todo_list.redo() {
+            Ok(label) => println!("{}", tr!("redid", change = label)),
+            Err(e) => println!("{}", tr!("error", message = e.to_string())),
+        },
+        10 => {
+            let tags = todo_list.list_tags();
+            if tags.is_empty() {
+                println!("{}", tr!("no-tags"));
+                return Ok(());
+            }
+            println!("{}", tr!("tags", tags = tags.join(", ")));
+            let tag = prompt(readline, &tr!("menu-enter-tag-filter"))?;
+            print!("{}", todo_list.format_task_list(&todo_list.tasks_with_tag(&tag)));
+        }
+        11 => {
+            if let Some(id) = read_id(readline, todo_list, &tr!("menu-id-to-tag"))? {
+                let tag = prompt(readline, &tr!("menu-enter-tag"))?;
+                report(todo_list.add_tag(id, &tag), tr!("tagged", id = id));
+            }
+        }
+        12 => {
+            if let Some(id) = read_id(readline, todo_list, &tr!("menu-id-to-untag"))? {
+                let tag = prompt(readline, &tr!("menu-enter-tag-remove"))?;
+                report(todo_list.remove_tag(id, &tag), tr!("untagged", id = id));
+            }
+        }
+        13 => {
+            if let Some(id) = read_id(readline, todo_list, &tr!("menu-id-to-set-recurrence"))? {
+                let input = prompt(readline, &tr!("menu-enter-recurrence"))?;
+                match Recurrence::parse(&input) {
+                    Ok(recurrence) => report(
+                        todo_list.set_recurrence(id, recurrence),
+                        tr!("recurrence-updated", id = id),
+                    ),
+                    Err(e) => println!("{}", tr!("error", message = e.to_string())),
+                }
+            }
+        }
+        14 => {
+            if let Some(parent_id) = read_id(readline, todo_list, &tr!("menu-enter-parent"))? {
+                let description = prompt(readline, &tr!("menu-enter-subtask"))?;
+                match todo_list.add_subtask(parent_id, description, None, Priority::Medium) {
+                    Ok(task_id) => println!("{}", tr!("subtask-added", id = task_id)),
+                    Err(e) => println!("{}", tr!("error", message = e.to_string())),
+                }
+            }
+        }
+        15 => match storage::archive_completed(todo_list, archive) {
+            Ok(count) => println!("{}", tr!("archived", count = count)),
+            Err(e) => println!("{}", tr!("error", message = e.to_string())),
+        },
+        16 => match archive.load() {
+            Ok(archived_list) => print!("{}", archived_list.format_task_list(&archived_list.sorted_tasks())),
+            Err(e) => println!("{}", tr!("error", message = e.to_string())),
+        },
+        17 => {
+            if let Some(id) = read_id(readline, todo_list, &tr!("menu-id-to-restore"))? {
+                report(
+                    storage::restore_archived_unsaved(todo_list, archive, id).map(|uuids| restored.extend(uuids)),
+                    tr!("task-restored", id = id),
+                );
+            }
+        }
+        18 => {
+            if let Some(id) = read_id(readline, todo_list, &tr!("menu-id-to-show"))? {
+                match todo_list.format_task(id) {
+                    Ok(details) => print!("{}", details),
+                    Err(e) => println!("{}", tr!("error", message = e.to_string())),
+                }
+            }
+        }
+        19 => {
+            if let Some(id) = read_id(readline, todo_list, &tr!("menu-id-to-edit-notes"))? {
+                match editor::edit_notes(todo_list, id) {
+                    Ok(notes) => report(
+                        todo_list.set_notes(id, Some(notes)),
+                        tr!("notes-updated", id = id),
+                    ),
+                    Err(e) => println!("{}", tr!("error", message = e.to_string())),
+                }
+            }
+        }
+        20 => {
+            if let Err(e) = kanban::run(todo_list) {
+                println!("{}", tr!("error", message = e.to_string()));
+            }
+        }
+        _ => println!("{}", tr!("menu-invalid-choice")),
+    }
+    Ok(())
+}
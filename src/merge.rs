@@ -0,0 +1,136 @@
+//! Combining another copy of a list, such as one kept on another machine,
+//! into this one. Tasks are matched by UUID rather than ID, as the same ID
+//! can belong to different tasks in the two copies.
+//!
+//! A task found in both copies is merged field by field: it is completed if
+//! it was completed in either and not reopened since, its tags, comments,
+//! custom fields and tracked time are combined, and fields only one copy set
+//! are taken from it. Where both set a field differently, this list's copy wins. A task
+//! only in the other copy is added with a new ID, unless an open or
+//! completed task with the same description and due date is already here.
+
+use std::collections::HashMap;
+
+use crate::{tr, Column, Task, TodoList};
+
+/// What a merge changed.
+#[derive(Debug, Clone, Default)]
+pub struct MergeSummary {
+    /// The IDs given to tasks that were only in the other copy.
+    pub added: Vec<usize>,
+    /// The IDs of tasks in both copies that took something from the other.
+    pub updated: Vec<usize>,
+    /// How many tasks were already here as they are in the other copy.
+    pub skipped: usize,
+}
+
+/// Merges the tasks of `other` into `todo_list`. The whole merge is undone
+/// as one change.
+pub fn merge(todo_list: &mut TodoList, other: &TodoList) -> MergeSummary {
+    let mut summary = MergeSummary::default();
+    let mut changed = Vec::new();
+    // The ID each task in `other` has here
+    let mut ids: HashMap<usize, usize> = HashMap::new();
+    let mut next_id = todo_list.next_id;
+
+    for theirs in other.tasks() {
+        if let Some(ours) = todo_list.task_by_uuid(theirs.uuid) {
+            ids.insert(theirs.id, ours.id);
+            let merged = merge_task(ours, theirs);
+            if merged == *ours {
+                summary.skipped += 1;
+            } else {
+                summary.updated.push(ours.id);
+                changed.push(merged);
+            }
+        } else if let Some(ours) = todo_list.tasks().iter().find(|ours| same_task(ours, theirs)) {
+            ids.insert(theirs.id, ours.id);
+            summary.skipped += 1;
+        } else {
+            ids.insert(theirs.id, next_id);
+            summary.added.push(next_id);
+            changed.push(Task { id: next_id, ..theirs.clone() });
+            next_id += 1;
+        }
+    }
+
+    // Subtasks and dependencies of added tasks point at the other copy's IDs
+    for task in changed.iter_mut().filter(|task| summary.added.contains(&task.id)) {
+        task.parent = task.parent.and_then(|parent| ids.get(&parent).copied());
+        task.recurs_from = task.recurs_from.and_then(|from| ids.get(&from).copied());
+        task.depends_on = task.depends_on.iter().filter_map(|on| ids.get(on).copied()).collect();
+    }
+
+    if !changed.is_empty() {
+        todo_list.put_tasks(tr!("change-merge", count = changed.len()), changed);
+    }
+    summary
+}
+
+// Written as one task on both sides, though with different UUIDs
+fn same_task(ours: &Task, theirs: &Task) -> bool {
+    ours.description.trim().eq_ignore_ascii_case(theirs.description.trim()) && ours.due_date == theirs.due_date
+}
+
+fn merge_task(ours: &Task, theirs: &Task) -> Task {
+    let mut task = ours.clone();
+    if theirs.completed && !task.completed {
+        task.set_status(Column::Done);
+        task.completed_at = theirs.completed_at;
+    }
+    if !task.completed && task.column == Column::Todo {
+        task.set_status(theirs.column);
+    }
+    task.due_date = task.due_date.or(theirs.due_date);
+    task.start_date = task.start_date.or(theirs.start_date);
+    // Completed on either side, the task is no longer waiting
+    task.waiting = task.waiting.or_else(|| theirs.waiting.clone()).filter(|_| !task.completed);
+    task.recurrence = task.recurrence.or(theirs.recurrence);
+    task.estimate = task.estimate.or(theirs.estimate);
+    task.notes = task.notes.or_else(|| theirs.notes.clone());
+    task.assignee = task.assignee.or_else(|| theirs.assignee.clone());
+    task.rank = task.rank.or(theirs.rank);
+    task.created_at = task.created_at.or(theirs.created_at);
+    task.last_reviewed = task.last_reviewed.max(theirs.last_reviewed);
+    task.pomodoros = task.pomodoros.max(theirs.pomodoros);
+
+    for tag in &theirs.tags {
+        if !task.has_tag(tag) {
+            task.tags.push(tag.clone());
+        }
+    }
+    for (name, value) in &theirs.fields {
+        task.fields.entry(name.clone()).or_insert_with(|| value.clone());
+    }
+    for attachment in &theirs.attachments {
+        if !task.attachments.contains(attachment) {
+            task.attachments.push(attachment.clone());
+        }
+    }
+    for comment in &theirs.comments {
+        if !task.comments.contains(comment) {
+            task.comments.push(comment.clone());
+        }
+    }
+    task.comments.sort_by_key(|comment| comment.at);
+    for interval in &theirs.time_log {
+        if !task.time_log.contains(interval) {
+            task.time_log.push(*interval);
+        }
+    }
+    task.time_log.sort_by_key(|interval| interval.start);
+    for change in &theirs.status_log {
+        if !task.status_log.contains(change) {
+            task.status_log.push(*change);
+        }
+    }
+    task.status_log.sort_by_key(|change| change.at);
+    // The copy that was completed or reopened last says which it is
+    if let Some(last) = task.status_log.last().copied() {
+        task.set_status(if last.completed { Column::Done } else { task.column });
+        if last.completed {
+            task.completed_at = Some(last.at);
+        }
+    }
+    task
+}
@@ -0,0 +1,204 @@
+//! Ways of telling about a task, such as the reminder `todo notify` sends
+//! when it is nearly due. Each is a [`Notifier`]: a desktop notification, a
+//! JSON POST to a webhook, an email or a message in a Slack or Discord
+//! channel, and programs using the library can bring their own. Those that
+//! come with it need the `native` feature. A webhook is sent the event and
+//! the whole task:
+//!
+//! ```text
+//! {"event": "due", "task": {"id": 4, "description": "pay rent", ...}}
+//! ```
+//!
+//! while a chat gets a line for people to read:
+//!
+//! ```text
+//! *Task completed:* pay rent @sam (ID 4, due 2026-10-12)
+//! ```
+
+#[cfg(feature = "native")]
+use notify_rust::Notification;
+use serde::Serialize;
+#[cfg(feature = "native")]
+use serde_json::json;
+
+#[cfg(feature = "native")]
+use crate::{mail, Timestamp};
+use crate::{tr, Task, TodoError};
+
+/// What happened to the task a notification is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Event {
+    /// The task is due soon, or already overdue.
+    Due,
+    /// The task was completed.
+    Completed,
+    /// The task's due date has passed.
+    Overdue,
+}
+
+impl Event {
+    /// A few words saying what happened, as the title of a notification.
+    pub fn summary(self) -> String {
+        match self {
+            Event::Due => tr!("notify-task-due"),
+            Event::Completed => tr!("notify-task-completed"),
+            Event::Overdue => tr!("notify-task-overdue"),
+        }
+    }
+}
+
+/// Something that can deliver a notification about a task.
+pub trait Notifier {
+    /// A short name for where notifications go, for messages about them.
+    fn name(&self) -> &str;
+
+    /// Tells about `event` for `task`, giving an error if it could not be
+    /// delivered.
+    fn notify(&self, event: Event, task: &Task) -> Result<(), TodoError>;
+}
+
+/// Shows a notification on the desktop.
+#[cfg(feature = "native")]
+#[derive(Debug, Clone, Default)]
+pub struct DesktopNotifier;
+
+#[cfg(feature = "native")]
+impl Notifier for DesktopNotifier {
+    fn name(&self) -> &str {
+        "desktop"
+    }
+
+    fn notify(&self, event: Event, task: &Task) -> Result<(), TodoError> {
+        Notification::new()
+            .summary(&event.summary())
+            .body(&describe(task))
+            .show()
+            .map_err(|e| TodoError::Notify(e.to_string()))?;
+        Ok(())
+    }
+}
+
+/// POSTs the event and the task, as JSON, to a URL.
+#[cfg(feature = "native")]
+#[derive(Debug, Clone)]
+pub struct WebhookNotifier {
+    /// The URL to POST to.
+    pub url: String,
+}
+
+#[cfg(feature = "native")]
+impl Notifier for WebhookNotifier {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    fn notify(&self, event: Event, task: &Task) -> Result<(), TodoError> {
+        post(self.name(), &self.url, &json!({ "event": event, "task": task }))
+    }
+}
+
+/// Emails the notification through a sendmail-compatible program.
+#[cfg(feature = "native")]
+#[derive(Debug, Clone)]
+pub struct EmailNotifier {
+    /// The address to send to.
+    pub to: String,
+    /// The address to send from, or `None` for the program to pick one.
+    pub from: Option<String>,
+    /// The program to send with and its arguments, like
+    /// [`mail::DEFAULT_SENDMAIL`].
+    pub sendmail: String,
+}
+
+#[cfg(feature = "native")]
+impl Notifier for EmailNotifier {
+    fn name(&self) -> &str {
+        "email"
+    }
+
+    fn notify(&self, event: Event, task: &Task) -> Result<(), TodoError> {
+        let subject = format!("{}: {}", event.summary(), task.description);
+        let message = mail::message(self.from.as_deref(), Some(&self.to), &subject, &describe(task), Timestamp::now())?;
+        mail::send(&self.sendmail, &message).map_err(|e| TodoError::Notify(e.to_string()))
+    }
+}
+
+/// A chat service that takes messages through incoming webhooks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Chat {
+    Slack,
+    Discord,
+}
+
+/// Posts a message about the task to a Slack or Discord channel, through
+/// the webhook URL the service gave for it.
+#[cfg(feature = "native")]
+#[derive(Debug, Clone)]
+pub struct ChatNotifier {
+    pub chat: Chat,
+    /// The channel's webhook URL.
+    pub url: String,
+}
+
+#[cfg(feature = "native")]
+impl Notifier for ChatNotifier {
+    fn name(&self) -> &str {
+        match self.chat {
+            Chat::Slack => "Slack",
+            Chat::Discord => "Discord",
+        }
+    }
+
+    fn notify(&self, event: Event, task: &Task) -> Result<(), TodoError> {
+        let mut details = vec![format!("ID {}", task.id)];
+        if let Some(due) = task.due_date {
+            details.push(tr!("list-due", date = due.to_string()));
+        }
+        let assignee = task.assignee.as_ref().map(|assignee| format!(" @{}", assignee)).unwrap_or_default();
+        let text = format!("{}{}", task.description, assignee);
+        let body = match self.chat {
+            // Slack only wants its three control characters escaped
+            Chat::Slack => {
+                let text = text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+                json!({ "text": format!("*{}:* {} ({})", event.summary(), text, details.join(", ")) })
+            }
+            // Discord reads Markdown, so anything that could start some is
+            Chat::Discord => {
+                let text = text.chars().fold(String::new(), |mut escaped, c| {
+                    if "\\*_~`|<>[]#-".contains(c) {
+                        escaped.push('\\');
+                    }
+                    escaped.push(c);
+                    escaped
+                });
+                json!({ "content": format!("**{}:** {} ({})", event.summary(), text, details.join(", ")) })
+            }
+        };
+        post(self.name(), &self.url, &body)
+    }
+}
+
+// POSTs `body` to `url`, saying what the `name` webhook answered if it was
+// not a success
+#[cfg(feature = "native")]
+fn post(name: &str, url: &str, body: &serde_json::Value) -> Result<(), TodoError> {
+    match ureq::post(url).set("Content-Type", "application/json").send_string(&body.to_string()) {
+        Ok(_) => Ok(()),
+        Err(ureq::Error::Status(code, response)) => {
+            Err(TodoError::Notify(tr!("webhook-answered", name = name, code = code, status = response.status_text())))
+        }
+        Err(ureq::Error::Transport(transport)) => {
+            Err(TodoError::Notify(tr!("webhook-unreachable", name = name, error = transport.to_string())))
+        }
+    }
+}
+
+// The line telling about the task, like `pay rent (due 2026-10-14)`
+#[cfg(feature = "native")]
+fn describe(task: &Task) -> String {
+    match task.due_date {
+        Some(due) => format!("{} ({})", task.description, tr!("list-due", date = due.to_string())),
+        None => task.description.clone(),
+    }
+}
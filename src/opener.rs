@@ -0,0 +1,25 @@
+//! Opening files and URLs with whatever the system opens them with.
+
+use std::io;
+use std::process::Command;
+
+/// Opens `target`, a path or URL, with `open` on macOS, `start` on Windows
+/// and `xdg-open` elsewhere, returning once the handler has been started.
+pub fn open(target: &str) -> io::Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        // The empty argument is the window title, which start otherwise
+        // takes from a quoted target
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+    let status = command.arg(target).status()?;
+    if !status.success() {
+        return Err(io::Error::other(format!("could not open {}: the handler exited with {}", target, status)));
+    }
+    Ok(())
+}
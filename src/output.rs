@@ -0,0 +1,78 @@
+//! Machine readable output for scripts, as JSON, tab separated values or
+//! plain lines.
+
+use todo_list::stats::Report;
+use todo_list::{Task, TodoError};
+
+use crate::cli::OutputFormat;
+
+/// Prints `tasks` in `format`, one row or array element per task. Text
+/// output is printed by the library instead, as it needs the whole list to
+/// draw subtasks under their parents.
+pub fn print_tasks(tasks: &[&Task], format: OutputFormat) -> Result<(), TodoError> {
+    match format {
+        OutputFormat::Text | OutputFormat::Json => println!("{}", serde_json::to_string_pretty(tasks)?),
+        OutputFormat::Tsv => {
+            println!("id\tstatus\tpriority\tdue\ttags\tparent\tdescription");
+            for task in tasks {
+                println!(
+                    "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                    task.id,
+                    if task.completed { "done" } else { "open" },
+                    task.priority,
+                    task.due_date.map(|due| due.to_string()).unwrap_or_default(),
+                    task.tags.join(","),
+                    task.parent.map(|parent| parent.to_string()).unwrap_or_default(),
+                    field(&task.description),
+                );
+            }
+        }
+        OutputFormat::Plain => {
+            for task in tasks {
+                println!("{}", field(&task.description));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Prints `report` in `format`. As TSV it is one `name<TAB>value` row per
+/// figure, with `completed:<date>` and `seconds_on:<date>` rows for each day,
+/// `seconds_on_task:<id>` and `pomodoros_on_task:<id>` rows for each task
+/// with time tracked, and `minutes_left:<date>` and `points_left:<date>`
+/// rows for each week of the burndown.
+pub fn print_report(report: &Report, format: OutputFormat) -> Result<(), TodoError> {
+    match format {
+        OutputFormat::Text | OutputFormat::Plain => print!("{}", report),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(report)?),
+        OutputFormat::Tsv => {
+            println!("pending\t{}", report.pending);
+            println!("completed\t{}", report.completed);
+            for (day, count) in &report.completed_per_day {
+                println!("completed:{}\t{}", day, count);
+            }
+            if let Some(seconds) = report.average_seconds_to_complete {
+                println!("average_seconds_to_complete\t{}", seconds);
+            }
+            for time in &report.time_per_task {
+                println!("seconds_on_task:{}\t{}", time.id, time.seconds);
+                println!("pomodoros_on_task:{}\t{}", time.id, time.pomodoros);
+            }
+            for (day, seconds) in &report.time_per_day {
+                println!("seconds_on:{}\t{}", day, seconds);
+            }
+            println!("minutes_left\t{}", report.remaining.minutes);
+            println!("points_left\t{}", report.remaining.points);
+            for (day, effort) in &report.burndown {
+                println!("minutes_left:{}\t{}", day, effort.minutes);
+                println!("points_left:{}\t{}", day, effort.points);
+            }
+        }
+    }
+    Ok(())
+}
+
+// Tabs and line breaks would split a value across columns or rows
+fn field(text: &str) -> String {
+    text.replace(['\t', '\n', '\r'], " ")
+}
@@ -0,0 +1,41 @@
+//! Showing output longer than the terminal a screen at a time, through
+//! `$PAGER`.
+
+use std::env;
+use std::io::{self, IsTerminal, Write};
+use std::process::{Command, Stdio};
+
+use dialoguer::console::Term;
+
+/// Prints `text`, through `$PAGER`, or `less` when that is not set, if
+/// stdout is a terminal and the text has more lines than fit on it. Falls
+/// back to printing it straight out if the pager cannot be started.
+pub fn show(text: &str) -> io::Result<()> {
+    let (rows, _) = Term::stdout().size();
+    if !io::stdout().is_terminal() || text.lines().count() < usize::from(rows) {
+        print!("{}", text);
+        return Ok(());
+    }
+
+    let pager = env::var("PAGER")
+        .ok()
+        .filter(|pager| !pager.trim().is_empty())
+        .unwrap_or_else(|| "less".to_string());
+    let mut words = pager.split_whitespace();
+    let mut command = Command::new(words.next().unwrap_or("less"));
+    command.args(words).stdin(Stdio::piped());
+    // Lets colors through less and leaves the list on screen after quitting
+    if env::var_os("LESS").is_none() {
+        command.env("LESS", "FRX");
+    }
+    let Ok(mut child) = command.spawn() else {
+        print!("{}", text);
+        return Ok(());
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        // Quitting the pager before the end closes the pipe, which is fine
+        let _ = stdin.write_all(text.as_bytes());
+    }
+    child.wait()?;
+    Ok(())
+}
@@ -0,0 +1,109 @@
+//! Working out where the data files live.
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The directory tasks are kept in when no file is given:
+/// `$XDG_DATA_HOME/todo`, or `~/.local/share/todo` when that is not set.
+pub fn data_dir() -> Option<PathBuf> {
+    let base = env::var_os("XDG_DATA_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")))?;
+    Some(base.join("todo"))
+}
+
+/// Where settings and hooks are kept: `$XDG_CONFIG_HOME/todo`, or
+/// `~/.config/todo` when that is not set.
+pub fn config_dir() -> Option<PathBuf> {
+    let base = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+    Some(base.join("todo"))
+}
+
+/// The data file to use: `file` when one was given with `--file` or
+/// `$TODO_FILE`, otherwise `todo_list.<extension>` in the data directory,
+/// falling back to the working directory. Missing parent directories are
+/// created.
+pub fn data_file(file: Option<&Path>, extension: &str) -> io::Result<PathBuf> {
+    let path = match file {
+        Some(file) => file.to_path_buf(),
+        None => {
+            let name = format!("todo_list.{}", extension);
+            data_dir().map(|dir| dir.join(&name)).unwrap_or_else(|| PathBuf::from(name))
+        }
+    };
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    Ok(path)
+}
+
+/// The archive kept next to a data file: `todo_list.json` is archived to
+/// `todo_list_archive.json`.
+pub fn archive_file(data_file: &Path) -> PathBuf {
+    companion_file(data_file, "archive")
+}
+
+/// The trash kept next to a data file, `todo_list_trash.json` for
+/// `todo_list.json`.
+pub fn trash_file(data_file: &Path) -> PathBuf {
+    companion_file(data_file, "trash")
+}
+
+/// Where what was last synced with a CalDAV server is remembered,
+/// `todo_list_caldav.json` for `todo_list.json` or `todo_list.db`.
+pub fn sync_state_file(data_file: &Path) -> PathBuf {
+    let stem = data_file.file_stem().unwrap_or_default().to_string_lossy();
+    data_file.with_file_name(format!("{}_caldav.json", stem))
+}
+
+/// The file locked while a data file is being changed, `todo_list.json.lock`
+/// for `todo_list.json`.
+pub fn lock_file(data_file: &Path) -> PathBuf {
+    let mut name = data_file.as_os_str().to_owned();
+    name.push(".lock");
+    PathBuf::from(name)
+}
+
+/// Where the interactive menu journals its changes until they are saved,
+/// `todo_list.json.journal` for `todo_list.json`.
+pub fn journal_file(data_file: &Path) -> PathBuf {
+    let mut name = data_file.as_os_str().to_owned();
+    name.push(".journal");
+    PathBuf::from(name)
+}
+
+/// The directory daily snapshots of a data file are kept in,
+/// `todo_list_snapshots` for `todo_list.json` or `todo_list.db`.
+pub fn snapshot_dir(data_file: &Path) -> PathBuf {
+    let stem = data_file.file_stem().unwrap_or_default().to_string_lossy();
+    data_file.with_file_name(format!("{}_snapshots", stem))
+}
+
+/// Where `todo doctor` sets aside tasks it cannot read,
+/// `todo_list_quarantine.jsonl` for `todo_list.json`, one per line.
+pub fn quarantine_file(data_file: &Path) -> PathBuf {
+    let stem = data_file.file_stem().unwrap_or_default().to_string_lossy();
+    data_file.with_file_name(format!("{}_quarantine.jsonl", stem))
+}
+
+/// Where the lines of an old list that could not be read are set aside,
+/// `todo_list.rejects` for `todo_list.json`.
+pub fn rejects_file(data_file: &Path) -> PathBuf {
+    data_file.with_extension("rejects")
+}
+
+// `data_file` with `_<suffix>` added to its name, before the extension
+fn companion_file(data_file: &Path, suffix: &str) -> PathBuf {
+    let stem = data_file.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match data_file.extension() {
+        Some(extension) => format!("{}_{}.{}", stem, suffix, extension.to_string_lossy()),
+        None => format!("{}_{}", stem, suffix),
+    };
+    data_file.with_file_name(name)
+}
@@ -0,0 +1,687 @@
+//! Regular expressions for `search --regex`, in the syntax of the regex
+//! crate for the parts of it people search with:
+//!
+//! - `.` for any character but a newline, `^` and `$` for the start and
+//!   end of the text, `\b` and `\B` for a word boundary and anywhere else;
+//! - `[abc]`, `[a-z]` and `[^0-9]` for a character from a set, and `\d`,
+//!   `\w` and `\s` for a digit, word character and space, with `\D`, `\W`
+//!   and `\S` for anything else;
+//! - `*`, `+`, `?`, `{n}`, `{n,}` and `{n,m}` for repeating what comes
+//!   before, with a `?` after them accepted and making no difference to
+//!   whether the text matches;
+//! - `|` for either side and `(...)` or `(?:...)` for grouping;
+//! - `\n`, `\t` and `\r`, and a backslash before any punctuation for the
+//!   character itself;
+//! - `(?i)` at the start to ignore case.
+//!
+//! Patterns run as a Thompson NFA, taking time in proportion to the length
+//! of the text times that of the pattern however the pattern is written, so
+//! no pattern can hang a search the way some do a backtracking matcher.
+
+use crate::{i18n, text, tr, TodoError};
+
+// How many times `{n}` and the like may ask for something to be repeated,
+// and how long a program a pattern may compile to
+const MAX_REPEAT: usize = 1000;
+const MAX_PROGRAM: usize = 100_000;
+
+/// A regular expression ready to match text with.
+#[derive(Debug, Clone)]
+pub(crate) struct Pattern {
+    program: Vec<Inst>,
+    ignore_case: bool,
+}
+
+impl Pattern {
+    /// Compiles `pattern`, saying what is wrong with it and where if it is
+    /// not a regular expression this module understands.
+    pub(crate) fn parse(pattern: &str) -> Result<Self, TodoError> {
+        let mut parser = Parser { pattern, chars: pattern.chars().collect(), position: 0 };
+        let ignore_case = pattern.starts_with("(?i)");
+        if ignore_case {
+            parser.position = 4;
+        }
+        let node = parser.alternation()?;
+        if parser.position < parser.chars.len() {
+            return Err(parser.error_at(tr!("regex-unmatched-close"), parser.position));
+        }
+        let mut program = Vec::new();
+        compile(&node, &mut program).map_err(|what| parser.error_at(i18n::message(what, &[]), 0))?;
+        program.push(Inst::Match);
+        Ok(Pattern { program, ignore_case })
+    }
+
+    /// Whether the pattern matches anywhere in `text`.
+    pub(crate) fn is_match(&self, text: &str) -> bool {
+        let chars: Vec<char> = text.chars().collect();
+        let mut current = Threads::new(self.program.len());
+        let mut next = Threads::new(self.program.len());
+        for position in 0..=chars.len() {
+            // A match can start anywhere, so each step also starts afresh
+            if self.add(&mut current, 0, &chars, position) {
+                return true;
+            }
+            let Some(&c) = chars.get(position) else {
+                break;
+            };
+            for &pc in &current.list {
+                let matched = match &self.program[pc] {
+                    Inst::Char(expected) => self.same(c, *expected),
+                    Inst::Any => c != '\n',
+                    Inst::Class(class) => class.matches(c, self.ignore_case),
+                    _ => false,
+                };
+                if matched && self.add(&mut next, pc + 1, &chars, position + 1) {
+                    return true;
+                }
+            }
+            std::mem::swap(&mut current, &mut next);
+            next.clear();
+        }
+        false
+    }
+
+    // Adds a thread at `start` to `threads`, following jumps, splits and
+    // assertions as far as the instructions that take a character, and
+    // says whether it got to the end of the pattern
+    fn add(&self, threads: &mut Threads, start: usize, chars: &[char], position: usize) -> bool {
+        let mut stack = vec![start];
+        while let Some(pc) = stack.pop() {
+            if !threads.insert(pc) {
+                continue;
+            }
+            match &self.program[pc] {
+                Inst::Jump(to) => stack.push(*to),
+                Inst::Split(first, second) => {
+                    stack.push(*second);
+                    stack.push(*first);
+                }
+                Inst::Start if position == 0 => stack.push(pc + 1),
+                Inst::End if position == chars.len() => stack.push(pc + 1),
+                Inst::WordBoundary(wanted) => {
+                    let before = position > 0 && is_word(chars[position - 1]);
+                    let after = chars.get(position).is_some_and(|&c| is_word(c));
+                    if (before != after) == *wanted {
+                        stack.push(pc + 1);
+                    }
+                }
+                Inst::Match => return true,
+                _ => {}
+            }
+        }
+        false
+    }
+
+    fn same(&self, c: char, expected: char) -> bool {
+        c == expected || (self.ignore_case && c.to_lowercase().eq(expected.to_lowercase()))
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Inst {
+    Char(char),
+    Any,
+    Class(Class),
+    Start,
+    End,
+    // `\b` when true, `\B` when false
+    WordBoundary(bool),
+    Split(usize, usize),
+    Jump(usize),
+    Match,
+}
+
+#[derive(Debug, Clone)]
+struct Class {
+    items: Vec<ClassItem>,
+    negated: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ClassItem {
+    Range(char, char),
+    // `\d`, `\w` or `\s`, or when negated `\D`, `\W` or `\S`
+    Named(Named, bool),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Named {
+    Digit,
+    Word,
+    Space,
+}
+
+impl Named {
+    fn matches(self, c: char) -> bool {
+        match self {
+            Named::Digit => c.is_numeric(),
+            Named::Word => is_word(c),
+            Named::Space => c.is_whitespace(),
+        }
+    }
+}
+
+impl Class {
+    fn named(named: Named, negated: bool) -> Self {
+        Class { items: vec![ClassItem::Named(named, false)], negated }
+    }
+
+    fn matches(&self, c: char, ignore_case: bool) -> bool {
+        let within = |c: char| {
+            self.items.iter().any(|item| match *item {
+                ClassItem::Range(low, high) => low <= c && c <= high,
+                ClassItem::Named(named, negated) => named.matches(c) != negated,
+            })
+        };
+        let found = within(c) || (ignore_case && (c.to_lowercase().any(within) || c.to_uppercase().any(within)));
+        found != self.negated
+    }
+}
+
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+// The pattern parsed, before it is compiled
+enum Node {
+    Empty,
+    Char(char),
+    Any,
+    Class(Class),
+    Start,
+    End,
+    WordBoundary(bool),
+    Concat(Vec<Node>),
+    Alternation(Vec<Node>),
+    Repeat { node: Box<Node>, min: usize, max: Option<usize> },
+}
+
+// A recursive descent parser, one function per level of precedence
+struct Parser<'a> {
+    pattern: &'a str,
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += 1;
+        Some(c)
+    }
+
+    // What is wrong, pointing at the character just read
+    fn error(&self, what: String) -> TodoError {
+        self.error_at(what, self.position.saturating_sub(1))
+    }
+
+    fn error_at(&self, what: String, position: usize) -> TodoError {
+        let before: String = self.chars[..position.min(self.chars.len())].iter().collect();
+        TodoError::Parse(format!(
+            "{}\n  {}\n  {}^",
+            tr!("regex-error", problem = what),
+            self.pattern,
+            " ".repeat(text::width(&before))
+        ))
+    }
+
+    fn alternation(&mut self) -> Result<Node, TodoError> {
+        let mut branches = vec![self.concat()?];
+        while self.peek() == Some('|') {
+            self.next();
+            branches.push(self.concat()?);
+        }
+        Ok(if branches.len() == 1 { branches.remove(0) } else { Node::Alternation(branches) })
+    }
+
+    fn concat(&mut self) -> Result<Node, TodoError> {
+        let mut nodes = Vec::new();
+        while let Some(c) = self.peek().filter(|&c| c != '|' && c != ')') {
+            let atom = self.atom(c)?;
+            nodes.push(self.repeat(atom)?);
+        }
+        Ok(match nodes.len() {
+            0 => Node::Empty,
+            1 => nodes.remove(0),
+            _ => Node::Concat(nodes),
+        })
+    }
+
+    fn atom(&mut self, c: char) -> Result<Node, TodoError> {
+        self.next();
+        Ok(match c {
+            '(' => {
+                let open = self.position - 1;
+                if self.peek() == Some('?') {
+                    self.next();
+                    if self.next() != Some(':') {
+                        return Err(self.error(tr!("regex-unsupported-group")));
+                    }
+                }
+                let node = self.alternation()?;
+                if self.next() != Some(')') {
+                    return Err(self.error_at(tr!("regex-unclosed-group"), open));
+                }
+                node
+            }
+            '[' => Node::Class(self.class()?),
+            '\\' => self.escape()?,
+            '.' => Node::Any,
+            '^' => Node::Start,
+            '$' => Node::End,
+            '*' | '+' | '?' | '{' => return Err(self.error(tr!("regex-nothing-to-repeat"))),
+            c => Node::Char(c),
+        })
+    }
+
+    fn repeat(&mut self, node: Node) -> Result<Node, TodoError> {
+        let (min, max) = match self.peek() {
+            Some(c @ ('*' | '+' | '?')) => {
+                self.next();
+                match c {
+                    '*' => (0, None),
+                    '+' => (1, None),
+                    _ => (0, Some(1)),
+                }
+            }
+            Some('{') => {
+                self.next();
+                self.counts()?
+            }
+            _ => return Ok(node),
+        };
+        if matches!(node, Node::Empty | Node::Start | Node::End | Node::WordBoundary(_)) {
+            return Err(self.error(tr!("regex-nothing-to-repeat")));
+        }
+        // A lazy repetition matches the same texts as a greedy one
+        if self.peek() == Some('?') {
+            self.next();
+        }
+        if matches!(self.peek(), Some('*' | '+' | '?' | '{')) {
+            self.next();
+            return Err(self.error(tr!("regex-nothing-to-repeat")));
+        }
+        Ok(Node::Repeat { node: Box::new(node), min, max })
+    }
+
+    // The `n}`, `n,}` or `n,m}` after a `{`
+    fn counts(&mut self) -> Result<(usize, Option<usize>), TodoError> {
+        let number = |parser: &mut Self| {
+            let mut digits = String::new();
+            while let Some(c) = parser.peek().filter(char::is_ascii_digit) {
+                digits.push(c);
+                parser.next();
+            }
+            digits.parse::<usize>().ok()
+        };
+        let Some(min) = number(self) else {
+            self.next();
+            return Err(self.error(tr!("regex-expected-count")));
+        };
+        let max = match self.next() {
+            Some('}') => Some(min),
+            Some(',') if self.peek() == Some('}') => {
+                self.next();
+                None
+            }
+            Some(',') => {
+                let max = number(self);
+                if self.next() != Some('}') || max.is_none() {
+                    return Err(self.error(tr!("regex-expected-count-end")));
+                }
+                max
+            }
+            _ => return Err(self.error(tr!("regex-expected-comma"))),
+        };
+        if min.max(max.unwrap_or(0)) > MAX_REPEAT {
+            return Err(self.error(tr!("regex-repeat-too-big", max = MAX_REPEAT)));
+        }
+        if max.is_some_and(|max| max < min) {
+            return Err(self.error(tr!("regex-backwards-repeat")));
+        }
+        Ok((min, max))
+    }
+
+    // What comes after a `\`, outside a set
+    fn escape(&mut self) -> Result<Node, TodoError> {
+        Ok(match self.escaped()? {
+            Escaped::Char(c) => Node::Char(c),
+            Escaped::Named(named, negated) => Node::Class(Class::named(named, negated)),
+            Escaped::WordBoundary(wanted) => Node::WordBoundary(wanted),
+        })
+    }
+
+    fn escaped(&mut self) -> Result<Escaped, TodoError> {
+        let Some(c) = self.next() else {
+            return Err(self.error(tr!("regex-trailing-backslash")));
+        };
+        Ok(match c {
+            'd' => Escaped::Named(Named::Digit, false),
+            'D' => Escaped::Named(Named::Digit, true),
+            'w' => Escaped::Named(Named::Word, false),
+            'W' => Escaped::Named(Named::Word, true),
+            's' => Escaped::Named(Named::Space, false),
+            'S' => Escaped::Named(Named::Space, true),
+            'b' => Escaped::WordBoundary(true),
+            'B' => Escaped::WordBoundary(false),
+            'n' => Escaped::Char('\n'),
+            't' => Escaped::Char('\t'),
+            'r' => Escaped::Char('\r'),
+            c if c.is_ascii_punctuation() || c == ' ' => Escaped::Char(c),
+            c => return Err(self.error(tr!("regex-unknown-escape", char = c.to_string()))),
+        })
+    }
+
+    // A set, after its `[`
+    fn class(&mut self) -> Result<Class, TodoError> {
+        let open = self.position - 1;
+        let negated = self.peek() == Some('^');
+        if negated {
+            self.next();
+        }
+        let mut items = Vec::new();
+        loop {
+            let low = match self.next() {
+                None => return Err(self.error_at(tr!("regex-unclosed-set"), open)),
+                // A `]` first in the set is the character itself
+                Some(']') if !items.is_empty() => break,
+                Some('\\') => match self.escaped()? {
+                    Escaped::Char(c) => c,
+                    Escaped::Named(named, negated) => {
+                        items.push(ClassItem::Named(named, negated));
+                        continue;
+                    }
+                    Escaped::WordBoundary(_) => return Err(self.error(tr!("regex-boundary-in-set"))),
+                },
+                Some(c) => c,
+            };
+            // A `-` last in the set is the character itself
+            if self.peek() == Some('-') && self.chars.get(self.position + 1).is_some_and(|&c| c != ']') {
+                self.next();
+                let high = match self.next() {
+                    Some('\\') => match self.escaped()? {
+                        Escaped::Char(c) => c,
+                        _ => return Err(self.error(tr!("regex-range-end"))),
+                    },
+                    Some(c) => c,
+                    None => return Err(self.error_at(tr!("regex-unclosed-set"), open)),
+                };
+                if high < low {
+                    return Err(self.error(tr!("regex-backwards-range")));
+                }
+                items.push(ClassItem::Range(low, high));
+            } else {
+                items.push(ClassItem::Range(low, low));
+            }
+        }
+        Ok(Class { items, negated })
+    }
+}
+
+enum Escaped {
+    Char(char),
+    Named(Named, bool),
+    WordBoundary(bool),
+}
+
+// Appends the instructions for `node` to `program`, or gives the ID of the
+// message saying why it cannot
+fn compile(node: &Node, program: &mut Vec<Inst>) -> Result<(), &'static str> {
+    if program.len() > MAX_PROGRAM {
+        return Err("regex-too-big");
+    }
+    match node {
+        Node::Empty => {}
+        Node::Char(c) => program.push(Inst::Char(*c)),
+        Node::Any => program.push(Inst::Any),
+        Node::Class(class) => program.push(Inst::Class(class.clone())),
+        Node::Start => program.push(Inst::Start),
+        Node::End => program.push(Inst::End),
+        Node::WordBoundary(wanted) => program.push(Inst::WordBoundary(*wanted)),
+        Node::Concat(nodes) => {
+            for node in nodes {
+                compile(node, program)?;
+            }
+        }
+        Node::Alternation(branches) => {
+            let mut jumps = Vec::new();
+            for (n, branch) in branches.iter().enumerate() {
+                if n + 1 == branches.len() {
+                    compile(branch, program)?;
+                    break;
+                }
+                let split = program.len();
+                program.push(Inst::Split(split + 1, 0));
+                compile(branch, program)?;
+                jumps.push(program.len());
+                program.push(Inst::Jump(0));
+                program[split] = Inst::Split(split + 1, program.len());
+            }
+            let end = program.len();
+            for jump in jumps {
+                program[jump] = Inst::Jump(end);
+            }
+        }
+        Node::Repeat { node, min, max } => {
+            for _ in 0..*min {
+                compile(node, program)?;
+            }
+            match max {
+                None => {
+                    let split = program.len();
+                    program.push(Inst::Split(split + 1, 0));
+                    compile(node, program)?;
+                    program.push(Inst::Jump(split));
+                    program[split] = Inst::Split(split + 1, program.len());
+                }
+                Some(max) => {
+                    let mut splits = Vec::new();
+                    for _ in *min..*max {
+                        splits.push(program.len());
+                        program.push(Inst::Split(program.len() + 1, 0));
+                        compile(node, program)?;
+                    }
+                    let end = program.len();
+                    for split in splits {
+                        program[split] = Inst::Split(split + 1, end);
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+// The threads at one step of the match: the instructions they are at, each
+// once, in the order they were added
+struct Threads {
+    list: Vec<usize>,
+    seen: Vec<bool>,
+}
+
+impl Threads {
+    fn new(size: usize) -> Self {
+        Threads { list: Vec::new(), seen: vec![false; size] }
+    }
+
+    // Whether `pc` was not already among the threads
+    fn insert(&mut self, pc: usize) -> bool {
+        if self.seen[pc] {
+            return false;
+        }
+        self.seen[pc] = true;
+        self.list.push(pc);
+        true
+    }
+
+    fn clear(&mut self) {
+        for &pc in &self.list {
+            self.seen[pc] = false;
+        }
+        self.list.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, text: &str) -> bool {
+        Pattern::parse(pattern).unwrap_or_else(|e| panic!("{}: {}", pattern, e)).is_match(text)
+    }
+
+    // The column the error's caret points at, under the pattern
+    fn caret(pattern: &str) -> usize {
+        let error = Pattern::parse(pattern).err().unwrap_or_else(|| panic!("{} should not parse", pattern)).to_string();
+        let lines: Vec<&str> = error.lines().collect();
+        assert_eq!(lines[1], format!("  {}", pattern), "{}", error);
+        lines[2].len() - "  ^".len()
+    }
+
+    #[test]
+    fn literals_match_anywhere() {
+        assert!(matches("milk", "buy milk today"));
+        assert!(!matches("milk", "buy bread"));
+        assert!(matches("", "anything"));
+        assert!(matches("café", "un café noir"));
+    }
+
+    #[test]
+    fn dot_matches_any_character_but_a_newline() {
+        assert!(matches("b.y", "buy"));
+        assert!(matches("b.y", "bäy"));
+        assert!(!matches("b.y", "b\ny"));
+        assert!(!matches("b.y", "by"));
+    }
+
+    #[test]
+    fn anchors() {
+        assert!(matches("^buy", "buy milk"));
+        assert!(!matches("^milk", "buy milk"));
+        assert!(matches("milk$", "buy milk"));
+        assert!(!matches("buy$", "buy milk"));
+        assert!(matches("^$", ""));
+        assert!(!matches("^$", "x"));
+    }
+
+    #[test]
+    fn word_boundaries() {
+        assert!(matches(r"\bcat\b", "feed the cat"));
+        assert!(!matches(r"\bcat\b", "concatenate"));
+        assert!(matches(r"\Bcat\B", "concatenate"));
+        assert!(!matches(r"\Bcat", "cat"));
+    }
+
+    #[test]
+    fn character_sets() {
+        assert!(matches("[abc]x", "bx"));
+        assert!(!matches("[abc]x", "dx"));
+        assert!(matches("^[a-z]+$", "milk"));
+        assert!(!matches("^[a-z]+$", "Milk"));
+        assert!(matches("[^0-9]", "a1"));
+        assert!(!matches("^[^0-9]+$", "a1"));
+        assert!(matches("[-a]", "-"));
+        assert!(matches(r"[\d.]+", "3.5"));
+        assert!(matches("[]x]", "]"));
+    }
+
+    #[test]
+    fn class_escapes() {
+        assert!(matches(r"^\d\d\d\d-\d\d$", "2024-06"));
+        assert!(!matches(r"\d", "no digits"));
+        assert!(matches(r"^\w+$", "snake_case9"));
+        assert!(!matches(r"^\w+$", "two words"));
+        assert!(matches(r"\s", "two words"));
+        assert!(matches(r"^\D+$", "abc"));
+        assert!(matches(r"\W", "a-b"));
+        assert!(!matches(r"\S", " \t"));
+        assert!(matches(r"a\tb", "a\tb"));
+        assert!(matches(r"\(\)\.\*", "().*"));
+        assert!(!matches(r"\.", "x"));
+    }
+
+    #[test]
+    fn repeats() {
+        assert!(matches("^ab*c$", "ac"));
+        assert!(matches("^ab*c$", "abbbc"));
+        assert!(!matches("^ab+c$", "ac"));
+        assert!(matches("^ab+c$", "abc"));
+        assert!(matches("^colou?r$", "color"));
+        assert!(matches("^colou?r$", "colour"));
+        assert!(!matches("^colou?r$", "colouur"));
+        assert!(matches("^a*?$", "aaa"));
+        assert!(matches("^a+?b$", "aab"));
+    }
+
+    #[test]
+    fn counted_repeats() {
+        assert!(matches("^a{3}$", "aaa"));
+        assert!(!matches("^a{3}$", "aa"));
+        assert!(!matches("^a{3}$", "aaaa"));
+        assert!(matches("^a{2,}$", "aaaaa"));
+        assert!(!matches("^a{2,}$", "a"));
+        assert!(matches("^a{2,3}$", "aa"));
+        assert!(matches("^a{2,3}$", "aaa"));
+        assert!(!matches("^a{2,3}$", "aaaa"));
+        assert!(matches("^(ab){2}$", "abab"));
+        assert!(matches("^a{0}b$", "b"));
+        assert!(matches("^a{1000}$", &"a".repeat(1000)));
+    }
+
+    #[test]
+    fn alternation_and_groups() {
+        assert!(matches("^(cat|dog)$", "dog"));
+        assert!(!matches("^(cat|dog)$", "cow"));
+        assert!(matches("^(?:cat|dog)s?$", "cats"));
+        assert!(matches("cat|dog", "hotdog"));
+        assert!(matches("^(a|b)*$", "abba"));
+        assert!(matches("^(|x)y$", "y"));
+        assert!(matches("^((a|b)(c|d))+$", "acbd"));
+    }
+
+    #[test]
+    fn ignoring_case() {
+        assert!(matches("(?i)^milk$", "MILK"));
+        assert!(matches("(?i)[a-c]", "B"));
+        assert!(!matches("^milk$", "MILK"));
+        assert!(matches("(?i)^ÄPFEL$", "äpfel"));
+    }
+
+    #[test]
+    fn bad_patterns_point_at_the_problem() {
+        assert_eq!(caret("ab)"), 2);
+        assert_eq!(caret("x(ab"), 1);
+        assert_eq!(caret("x[ab"), 1);
+        assert_eq!(caret("*a"), 0);
+        assert_eq!(caret("a|+"), 2);
+        assert_eq!(caret("a{x}"), 2);
+        assert_eq!(caret("a{2,1}"), 5);
+        assert_eq!(caret("a{1001}"), 6);
+        assert_eq!(caret(r"ab\"), 2);
+        assert_eq!(caret(r"a\q"), 2);
+        assert_eq!(caret("[z-a]"), 3);
+        assert_eq!(caret(r"[\b]"), 2);
+        assert_eq!(caret("(?=a)"), 2);
+        // Wide characters before the problem take two columns each
+        assert_eq!(caret("日本)"), 4);
+    }
+
+    #[test]
+    fn huge_programs_are_refused() {
+        assert!(Pattern::parse("(a{1000}){1000}").is_err());
+    }
+
+    #[test]
+    fn nested_repeats_do_not_blow_up() {
+        let text = "a".repeat(20_000);
+        assert!(!matches("(a*)*b", &text));
+        assert!(!matches("(a+)+b", &text));
+        assert!(!matches("(a|a)*b", &text));
+        assert!(!matches("^(a|aa)+$", &format!("{}b", text)));
+        assert!(matches("(a*)*$", &text));
+    }
+}
@@ -0,0 +1,48 @@
+//! Choosing a task by typing part of its description instead of its ID.
+
+use std::io::{self, IsTerminal};
+
+use dialoguer::{FuzzySelect, Input};
+use todo_list::{tr, TodoError, TodoList};
+
+/// Opens a fuzzy finder over the open tasks, or every task with `all`, and
+/// returns the ID of the one picked. Pressing Esc picks nothing. The finder
+/// is drawn on stderr so the ID can be captured from stdout.
+pub fn pick(todo_list: &TodoList, all: bool) -> Result<Option<usize>, TodoError> {
+    if !io::stdin().is_terminal() || !io::stderr().is_terminal() {
+        return Err(io::Error::other(tr!("pick-needs-terminal")).into());
+    }
+    let tasks: Vec<_> = todo_list.sorted_tasks().into_iter().filter(|task| all || !task.completed).collect();
+    if tasks.is_empty() {
+        println!("{}", tr!("no-tasks"));
+        return Ok(None);
+    }
+
+    let items: Vec<String> = tasks.iter()
+        .map(|task| {
+            let status = if task.completed { "[x]" } else { "[ ]" };
+            let tags: String = task.tags.iter().map(|tag| format!(" #{}", tag)).collect();
+            format!("{} {}: {}{}", status, task.id, task.description, tags)
+        })
+        .collect();
+    let choice = FuzzySelect::new()
+        .with_prompt(tr!("pick-prompt"))
+        .items(&items)
+        .interact_opt()
+        .map_err(into_io)?;
+    Ok(choice.map(|index| tasks[index].id))
+}
+
+/// Asks for a new description on the terminal, starting from `current`.
+pub fn read_description(current: &str) -> Result<String, TodoError> {
+    Input::new()
+        .with_prompt(tr!("pick-new-description"))
+        .with_initial_text(current)
+        .interact_text()
+        .map_err(into_io)
+}
+
+fn into_io(e: dialoguer::Error) -> TodoError {
+    let dialoguer::Error::IO(e) = e;
+    TodoError::Io(e)
+}
@@ -0,0 +1,223 @@
+//! Plugins written in Rhai (<https://rhai.rs>), loaded from the `*.rhai`
+//! files in the plugins directory. When a script is loaded it registers
+//! what it adds:
+//!
+//! ```text
+//! register_command("standup", "List what was done yesterday", |args| {
+//!     for task in tasks() {
+//!         if task.completed { print(task.description); }
+//!     }
+//! });
+//! register_filter("quick", |task| task.estimate == () || task.tags.contains("quick"));
+//! register_format("csv", |tasks| tasks.map(|task| `${task.id},${task.description}`).reduce(|a, b| a + "\n" + b, ""));
+//! ```
+//!
+//! A command runs as `todo <name> [args...]` and gets its arguments as an
+//! array of strings. Filters and formats are used with
+//! `list --plugin-filter <name>` and `list --plugin-format <name>`. Tasks are given to scripts as maps with the
+//! same fields as the JSON data file. Scripts can call `tasks()`,
+//! `add_task(description)`, which gives the new ID, `complete_task(id)`,
+//! `add_tag(id, tag)` and `today()`; changes they make are saved once the
+//! command is done.
+
+use std::cell::RefCell;
+use std::fs;
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use rhai::{Array, Dynamic, Engine, EvalAltResult, FnPtr, FuncArgs, AST};
+use todo_list::{tr, Date, Task, TodoError, TodoList};
+
+// Something a plugin added: its name, what a command does, the script it
+// came from and what to call in it
+struct Entry {
+    name: String,
+    about: String,
+    script: usize,
+    function: FnPtr,
+}
+
+#[derive(Default)]
+struct Registry {
+    commands: Vec<Entry>,
+    filters: Vec<Entry>,
+    formats: Vec<Entry>,
+    // The script being loaded; things can only be registered then
+    loading: Option<usize>,
+}
+
+/// The plugins in a directory, loaded and ready to run.
+pub struct Plugins {
+    engine: Engine,
+    scripts: Vec<(PathBuf, AST)>,
+    registry: Rc<RefCell<Registry>>,
+    // The list scripts see while one of their functions runs
+    todo_list: Rc<RefCell<TodoList>>,
+}
+
+impl Plugins {
+    /// Loads every `*.rhai` file in `dir`, in name order. A directory that
+    /// cannot be read gives no plugins; a script that does not compile or
+    /// fails while it is loaded is an error naming it.
+    pub fn load(dir: &Path) -> Result<Self, TodoError> {
+        let registry = Rc::new(RefCell::new(Registry::default()));
+        let todo_list = Rc::new(RefCell::new(TodoList::new()));
+        let engine = engine(&registry, &todo_list);
+
+        let mut paths: Vec<PathBuf> = match fs::read_dir(dir) {
+            Ok(entries) => entries.filter_map(|entry| Some(entry.ok()?.path())).collect(),
+            Err(_) => Vec::new(),
+        };
+        paths.retain(|path| path.extension().is_some_and(|extension| extension == "rhai"));
+        paths.sort();
+
+        let mut scripts = Vec::new();
+        for path in paths {
+            let failed = |e: Box<EvalAltResult>| TodoError::Plugin(format!("{}: {}", path.display(), e));
+            registry.borrow_mut().loading = Some(scripts.len());
+            let ast = engine.compile_file(path.clone()).map_err(failed)?;
+            engine.run_ast(&ast).map_err(failed)?;
+            scripts.push((path, ast));
+        }
+        registry.borrow_mut().loading = None;
+        Ok(Plugins { engine, scripts, registry, todo_list })
+    }
+
+    /// The names of the commands the plugins added, with what they do.
+    pub fn commands(&self) -> Vec<(String, String)> {
+        let registry = self.registry.borrow();
+        registry.commands.iter().map(|entry| (entry.name.clone(), entry.about.clone())).collect()
+    }
+
+    /// The names of the filters the plugins added.
+    pub fn filters(&self) -> Vec<String> {
+        self.registry.borrow().filters.iter().map(|entry| entry.name.clone()).collect()
+    }
+
+    /// The names of the formats the plugins added.
+    pub fn formats(&self) -> Vec<String> {
+        self.registry.borrow().formats.iter().map(|entry| entry.name.clone()).collect()
+    }
+
+    /// Runs the command called `name` on `todo_list`. Gives false if no
+    /// plugin added a command by that name.
+    pub fn run_command(&self, todo_list: &mut TodoList, name: &str, args: &[String]) -> Result<bool, TodoError> {
+        let Some(function) = self.find(|registry| &registry.commands, name) else {
+            return Ok(false);
+        };
+        let args: Array = args.iter().map(|arg| Dynamic::from(arg.clone())).collect();
+        // The list is lent to the scripts and taken back even if they fail
+        *self.todo_list.borrow_mut() = mem::take(todo_list);
+        let result = self.call(name, function, (args,));
+        *todo_list = mem::take(&mut *self.todo_list.borrow_mut());
+        result.map(|_| true)
+    }
+
+    /// The tasks of `tasks` that the filter called `name` accepts.
+    pub fn filter<'a>(&self, todo_list: &TodoList, name: &str, tasks: Vec<&'a Task>) -> Result<Vec<&'a Task>, TodoError> {
+        let function = self.find(|registry| &registry.filters, name).ok_or_else(|| missing("filter", name))?;
+        self.show_list(todo_list);
+        let mut kept = Vec::new();
+        for task in tasks {
+            let accepted = self.call(name, function.clone(), (to_dynamic(task)?,))?;
+            if accepted.as_bool().map_err(|kind| TodoError::Plugin(tr!("plugin-filter-not-bool", name = name, kind = kind)))? {
+                kept.push(task);
+            }
+        }
+        Ok(kept)
+    }
+
+    /// Writes `tasks` in the format called `name`.
+    pub fn format(&self, todo_list: &TodoList, name: &str, tasks: &[&Task]) -> Result<String, TodoError> {
+        let function = self.find(|registry| &registry.formats, name).ok_or_else(|| missing("format", name))?;
+        self.show_list(todo_list);
+        let tasks: Array = tasks.iter().map(|task| to_dynamic(task)).collect::<Result<_, _>>()?;
+        Ok(self.call(name, function, (tasks,))?.to_string())
+    }
+
+    // The script and function of what is called `name`, copied out so the
+    // registry is not borrowed while the script runs
+    fn find(&self, entries: impl Fn(&Registry) -> &Vec<Entry>, name: &str) -> Option<(usize, FnPtr)> {
+        let registry = self.registry.borrow();
+        let entry = entries(&registry).iter().find(|entry| entry.name == name)?;
+        Some((entry.script, entry.function.clone()))
+    }
+
+    fn call(&self, name: &str, (script, function): (usize, FnPtr), args: impl FuncArgs) -> Result<Dynamic, TodoError> {
+        let (path, ast) = &self.scripts[script];
+        function.call::<Dynamic>(&self.engine, ast, args).map_err(|e| {
+            TodoError::Plugin(format!("{} in {}: {}", name, path.display(), e))
+        })
+    }
+
+    // Filters and formats are given a copy of the list, as changes they make
+    // would not be saved
+    fn show_list(&self, todo_list: &TodoList) {
+        let mut copy = TodoList::new();
+        copy.restore_tasks(todo_list.tasks().to_vec());
+        *self.todo_list.borrow_mut() = copy;
+    }
+}
+
+fn missing(kind: &str, name: &str) -> TodoError {
+    TodoError::Plugin(tr!("plugin-missing", kind = kind, name = name))
+}
+
+fn to_dynamic(task: &Task) -> Result<Dynamic, TodoError> {
+    rhai::serde::to_dynamic(task).map_err(|e| TodoError::Plugin(e.to_string()))
+}
+
+// The engine with the functions scripts can call
+fn engine(registry: &Rc<RefCell<Registry>>, todo_list: &Rc<RefCell<TodoList>>) -> Engine {
+    let mut engine = Engine::new();
+
+    let add = |registry: &Rc<RefCell<Registry>>, kind: &'static str, entries: fn(&mut Registry) -> &mut Vec<Entry>| {
+        let registry = Rc::clone(registry);
+        move |name: &str, about: &str, function: FnPtr| -> Result<(), Box<EvalAltResult>> {
+            let mut registry = registry.borrow_mut();
+            let Some(script) = registry.loading else {
+                return Err(tr!("plugin-registered-late", kind = kind).into());
+            };
+            let entry = Entry { name: name.to_string(), about: about.to_string(), script, function };
+            entries(&mut registry).push(entry);
+            Ok(())
+        }
+    };
+    let register_command = add(registry, "command", |registry| &mut registry.commands);
+    engine.register_fn("register_command", register_command.clone());
+    engine.register_fn("register_command", move |name: &str, function: FnPtr| register_command(name, "", function));
+    let register_filter = add(registry, "filter", |registry| &mut registry.filters);
+    engine.register_fn("register_filter", move |name: &str, function: FnPtr| register_filter(name, "", function));
+    let register_format = add(registry, "format", |registry| &mut registry.formats);
+    engine.register_fn("register_format", move |name: &str, function: FnPtr| register_format(name, "", function));
+
+    let list = Rc::clone(todo_list);
+    engine.register_fn("tasks", move || -> Result<Array, Box<EvalAltResult>> {
+        list.borrow().tasks().iter().map(rhai::serde::to_dynamic).collect()
+    });
+    let list = Rc::clone(todo_list);
+    engine.register_fn("add_task", move |description: &str| -> Result<i64, Box<EvalAltResult>> {
+        let id = list.borrow_mut().add_task(description.to_string(), None, Default::default()).map_err(script_error)?;
+        Ok(id as i64)
+    });
+    let list = Rc::clone(todo_list);
+    engine.register_fn("complete_task", move |id: i64| -> Result<(), Box<EvalAltResult>> {
+        list.borrow_mut().complete_task(task_id(id)?).map_err(script_error)?;
+        Ok(())
+    });
+    let list = Rc::clone(todo_list);
+    engine.register_fn("add_tag", move |id: i64, tag: &str| -> Result<(), Box<EvalAltResult>> {
+        list.borrow_mut().add_tag(task_id(id)?, tag).map_err(script_error)
+    });
+    engine.register_fn("today", || Date::today().to_string());
+    engine
+}
+
+fn task_id(id: i64) -> Result<usize, Box<EvalAltResult>> {
+    usize::try_from(id).map_err(|_| tr!("plugin-invalid-id", id = id).into())
+}
+
+fn script_error(e: TodoError) -> Box<EvalAltResult> {
+    e.to_string().into()
+}
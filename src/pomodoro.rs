@@ -0,0 +1,84 @@
+//! Pomodoros: working on a task in timed periods with short breaks between,
+//! tracking the time on the task and counting each period finished.
+
+use std::io::{self, IsTerminal, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use notify_rust::Notification;
+use todo_list::storage::Storage;
+use todo_list::{tr, TodoError, TodoList};
+
+/// Runs `cycles` work periods of `work` on the task with the given ID, with
+/// a break of `rest` after each but the last. The task's timer runs during
+/// work periods, and the list is saved as each one starts and ends so an
+/// interrupted pomodoro keeps the time already spent.
+pub fn run(
+    todo_list: &mut TodoList,
+    storage: &dyn Storage,
+    id: usize,
+    work: Duration,
+    rest: Duration,
+    cycles: u32,
+) -> Result<(), TodoError> {
+    for cycle in 1..=cycles {
+        match todo_list.start_timer(id) {
+            Ok(Some(stopped)) => println!("{}", tr!("timer-stopped", id = stopped)),
+            // Already timing this task is as good as starting to
+            Ok(None) | Err(TodoError::TimerRunning(_)) => {}
+            Err(e) => return Err(e),
+        }
+        storage.save(todo_list)?;
+
+        countdown(&tr!("pomodoro-cycle", cycle = cycle, cycles = cycles, id = id), work)?;
+        todo_list.stop_timer()?;
+        todo_list.add_pomodoro(id)?;
+        storage.save(todo_list)?;
+
+        if cycle < cycles {
+            alert(&tr!("pomodoro-finished"), &tr!("pomodoro-take-break", length = clock(rest)));
+            countdown(&tr!("pomodoro-break"), rest)?;
+            alert(&tr!("pomodoro-break-over"), &tr!("pomodoro-back-to-work"));
+        } else {
+            alert(&tr!("pomodoro-finished"), &tr!("pomodoro-all-done", count = cycles, id = id));
+        }
+    }
+    println!("{}", tr!("pomodoro-summary", count = cycles, id = id));
+    Ok(())
+}
+
+// Waits out `length`, counting down on the same line when on a terminal
+fn countdown(label: &str, length: Duration) -> io::Result<()> {
+    let end = Instant::now() + length;
+    let terminal = io::stdout().is_terminal();
+    if !terminal {
+        println!("{} ({})", label, clock(length));
+    }
+    while let Some(left) = end.checked_duration_since(Instant::now()).filter(|left| !left.is_zero()) {
+        if terminal {
+            print!("\r{} {} ", label, tr!("pomodoro-left", length = clock(left)));
+            io::stdout().flush()?;
+        }
+        thread::sleep(left.min(Duration::from_secs(1)));
+    }
+    if terminal {
+        println!("\r{} {}        ", label, tr!("pomodoro-done"));
+    }
+    Ok(())
+}
+
+// Rings the terminal bell, when there is one, and shows a desktop
+// notification where there is a desktop to show it on
+fn alert(summary: &str, body: &str) {
+    if io::stdout().is_terminal() {
+        print!("\x07");
+    }
+    println!("{}. {}", summary, body);
+    let _ = Notification::new().summary(summary).body(body).show();
+}
+
+// Minutes and seconds, like 24:59, rounding part seconds up
+fn clock(length: Duration) -> String {
+    let seconds = length.as_secs() + u64::from(length.subsec_nanos() > 0);
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
@@ -0,0 +1,166 @@
+//! Reading the interactive menu's input. On a terminal each line can be
+//! edited with the arrow keys, Home, End and Backspace, Up and Down go back
+//! through what was typed earlier in the session, and Tab completes menu
+//! choices by name and task IDs, from the start of an ID or from a word of
+//! the task's description. Input from anything else, such as a script, is
+//! read a plain line at a time, and its end gives an error of kind
+//! [`io::ErrorKind::UnexpectedEof`]. Either way, Ctrl-C or another signal
+//! [`signals`](crate::signals) catches gives an error of kind
+//! [`io::ErrorKind::Interrupted`].
+
+use std::collections::VecDeque;
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use dialoguer::console::Term;
+use dialoguer::{Completion, History, Input};
+use todo_list::TodoList;
+
+use crate::signals;
+
+// How many lines Up goes back through at most
+const HISTORY: usize = 500;
+
+/// What Tab completes at a prompt.
+pub enum Complete<'a> {
+    Nothing,
+    /// One of these names, ignoring case.
+    Names(&'a [String]),
+    /// The ID of a task on the list.
+    Ids(&'a TodoList),
+}
+
+impl Completion for Complete<'_> {
+    fn get(&self, input: &str) -> Option<String> {
+        match self {
+            Complete::Nothing => None,
+            Complete::Names(names) => {
+                let typed = input.to_lowercase();
+                let found: Vec<&String> = names.iter().filter(|name| name.to_lowercase().starts_with(&typed)).collect();
+                common_prefix(&found).filter(|prefix| prefix.chars().count() > input.chars().count())
+            }
+            Complete::Ids(todo_list) if input.chars().all(|c| c.is_ascii_digit()) => {
+                let ids: Vec<String> = todo_list.iter().map(|task| task.id.to_string()).filter(|id| id.starts_with(input)).collect();
+                common_prefix(&ids.iter().collect::<Vec<_>>()).filter(|prefix| prefix.len() > input.len())
+            }
+            // Text that is not a number picks out the one open task whose
+            // description has it
+            Complete::Ids(todo_list) => match todo_list.search(input).as_slice() {
+                [task] => Some(task.id.to_string()),
+                tasks => match tasks.iter().filter(|task| !task.completed).collect::<Vec<_>>().as_slice() {
+                    [task] => Some(task.id.to_string()),
+                    _ => None,
+                },
+            },
+        }
+    }
+}
+
+// The longest start all of `texts` share, ignoring case, as the first has
+// it, or None when there are none
+fn common_prefix(texts: &[&String]) -> Option<String> {
+    let (first, rest) = texts.split_first()?;
+    let mut prefix: Vec<char> = first.chars().collect();
+    for text in rest {
+        let shared = prefix.iter().zip(text.chars()).take_while(|(a, b)| a.to_lowercase().eq(b.to_lowercase())).count();
+        prefix.truncate(shared);
+    }
+    Some(prefix.into_iter().collect())
+}
+
+// The lines read so far, the latest first, each once; blank ones are not
+// worth going back to
+#[derive(Default)]
+struct Lines(VecDeque<String>);
+
+impl History<String> for Lines {
+    fn read(&self, pos: usize) -> Option<String> {
+        self.0.get(pos).cloned()
+    }
+
+    fn write(&mut self, line: &String) {
+        if line.trim().is_empty() {
+            return;
+        }
+        self.0.retain(|earlier| earlier != line);
+        self.0.push_front(line.clone());
+        self.0.truncate(HISTORY);
+    }
+}
+
+/// A session's worth of input, remembering each line read.
+pub struct Readline {
+    history: Lines,
+    terminal: bool,
+}
+
+impl Readline {
+    pub fn new() -> Self {
+        Readline {
+            history: Lines::default(),
+            terminal: io::stdin().is_terminal() && Term::stderr().is_term(),
+        }
+    }
+
+    /// Whether a person is typing the input, who can be asked questions,
+    /// rather than a script.
+    pub fn is_interactive(&self) -> bool {
+        self.terminal
+    }
+
+    /// Prints `message` and reads a line, with `complete` for Tab.
+    pub fn line(&mut self, message: &str, complete: &Complete) -> io::Result<String> {
+        // A signal caught while the session was busy ends it before it
+        // waits on another line
+        if signals::pending() {
+            return Err(io::ErrorKind::Interrupted.into());
+        }
+        if !self.terminal {
+            print!("{} ", message);
+            io::stdout().flush()?;
+            return read_line();
+        }
+        // The prompt is followed by a colon of the theme's own
+        let input: Result<String, _> = Input::new()
+            .with_prompt(message.trim_end().trim_end_matches(':'))
+            .allow_empty(true)
+            .history_with(&mut self.history)
+            .completion_with(complete)
+            .interact_text();
+        input.map_err(|e| {
+            let dialoguer::Error::IO(e) = e;
+            e
+        })
+    }
+}
+
+// A line from standard input, like `read_line` but giving up when a signal
+// is caught instead of going back to waiting
+fn read_line() -> io::Result<String> {
+    let mut stdin = io::stdin().lock();
+    let mut line = Vec::new();
+    loop {
+        let available = match stdin.fill_buf() {
+            Ok(available) => available,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted && signals::pending() => return Err(e),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        if available.is_empty() {
+            if line.is_empty() {
+                println!();
+                return Err(io::ErrorKind::UnexpectedEof.into());
+            }
+            break;
+        }
+        let (used, done) = match available.iter().position(|&b| b == b'\n') {
+            Some(newline) => (newline + 1, true),
+            None => (available.len(), false),
+        };
+        line.extend_from_slice(&available[..used]);
+        stdin.consume(used);
+        if done {
+            break;
+        }
+    }
+    String::from_utf8(line).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
@@ -0,0 +1,65 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{tr, Date, TodoError};
+
+/// How often a task repeats once it is completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Recurrence {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl Recurrence {
+    /// Parses `daily`, `weekly` or `monthly`. `none` or a blank input means
+    /// the task does not repeat.
+    pub fn parse(input: &str) -> Result<Option<Self>, TodoError> {
+        match input.trim().to_lowercase().as_str() {
+            "daily" | "d" => Ok(Some(Recurrence::Daily)),
+            "weekly" | "w" => Ok(Some(Recurrence::Weekly)),
+            "monthly" | "m" => Ok(Some(Recurrence::Monthly)),
+            "none" | "" => Ok(None),
+            other => Err(TodoError::Parse(tr!("invalid-recurrence", value = other))),
+        }
+    }
+
+    /// The due date of the occurrence after one due on `date`.
+    pub fn next_date(&self, date: Date) -> Date {
+        self.next_date_on(date, date.day)
+    }
+
+    /// Like [`Recurrence::next_date`] for a series due on `day` of the
+    /// month, which `date` may fall short of when its month is shorter: a
+    /// monthly task due on the 31st comes back on the 31st after Feb 29.
+    pub fn next_date_on(&self, date: Date, day: u32) -> Date {
+        match self {
+            Recurrence::Daily => date.add_days(1),
+            Recurrence::Weekly => date.add_days(7),
+            // Adding months moves a day the month does not have back to its last
+            Recurrence::Monthly => Date { day, ..date }.add_months(1),
+        }
+    }
+}
+
+impl FromStr for Recurrence {
+    type Err = TodoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Recurrence::parse(s)?.ok_or_else(|| TodoError::Parse(tr!("recurrence-required")))
+    }
+}
+
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Recurrence::Daily => "daily",
+            Recurrence::Weekly => "weekly",
+            Recurrence::Monthly => "monthly",
+        };
+        write!(f, "{}", name)
+    }
+}
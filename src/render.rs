@@ -0,0 +1,334 @@
+//! Turning tasks into text for people to read. The engine never prints;
+//! the `todo` binary prints what these give, and programs embedding the
+//! library can show it however they like, or build views of their own from
+//! the tasks instead. Everything is in the list's language, date format and
+//! colors.
+
+use std::collections::HashSet;
+
+use crate::text::{pad, truncate, width};
+use crate::{join_ids, stats, tr, Column, Date, Task, TodoList};
+
+/// `tasks`, taken from `todo_list`, as a table with a row each in the
+/// order given, subtasks indented under their parent when it is among them.
+/// The ID, status, priority, due date and tags line up in columns, and the
+/// description comes last, cut short if the row would be wider than the
+/// list's width.
+pub fn task_list(todo_list: &TodoList, tasks: &[&Task]) -> String {
+    if tasks.is_empty() {
+        return format!("{}\n", tr!("no-tasks"));
+    }
+    table(todo_list, &[(None, tasks.to_vec())])
+}
+
+/// `tasks` like [`task_list`], grouped by project: each top-level task
+/// with subtasks comes under a progress bar saying how many of all its
+/// subtasks, shown or not, are completed, and the tasks in no project come
+/// last.
+pub fn by_project(todo_list: &TodoList, tasks: &[&Task]) -> String {
+    if tasks.is_empty() {
+        return format!("{}\n", tr!("no-tasks"));
+    }
+
+    // Groups of tasks by the top-level task they come under, in the order
+    // the first of each is listed
+    let mut groups: Vec<(usize, Vec<&Task>)> = Vec::new();
+    for task in tasks {
+        let root = todo_list.root(task.id);
+        match groups.iter_mut().find(|(id, _)| *id == root) {
+            Some((_, group)) => group.push(task),
+            None => groups.push((root, vec![task])),
+        }
+    }
+    let (projects, others): (Vec<_>, Vec<_>) = groups.into_iter().partition(|(root, _)| !todo_list.subtasks(*root).is_empty());
+
+    let mut sections: Vec<(Option<String>, Vec<&Task>)> = Vec::new();
+    for (root, group) in projects {
+        let subtasks: Vec<usize> = todo_list.with_descendants(root).into_iter().skip(1).collect();
+        let done = subtasks.iter().filter(|&&id| todo_list.task(id).is_some_and(|task| task.completed)).count();
+        let description = todo_list.task(root).map(|task| task.description.as_str()).unwrap_or_default();
+        let heading = format!(
+            "{} {}",
+            progress_bar(done, subtasks.len()),
+            tr!("project-progress", description = description, done = done, total = subtasks.len())
+        );
+        sections.push((Some(heading), group));
+    }
+    let others: Vec<&Task> = others.into_iter().flat_map(|(_, group)| group).collect();
+    if !others.is_empty() {
+        sections.push((Some(tr!("other-tasks")), others));
+    }
+    table(todo_list, &sections)
+}
+
+/// `tasks` like [`task_list`], under a heading for each column of the
+/// board, in the order of [`Column::ALL`]. A subtask in another column than
+/// its parent is listed on its own.
+pub fn board(todo_list: &TodoList, tasks: &[&Task]) -> String {
+    let sections: Vec<(Option<String>, Vec<&Task>)> = Column::ALL
+        .into_iter()
+        .map(|column| {
+            let in_column: Vec<&Task> = tasks.iter().copied().filter(|task| task.status() == column).collect();
+            (Some(todo_list.board_heading(column, in_column.len())), in_column)
+        })
+        .collect();
+    table(todo_list, &sections)
+}
+
+// Like `[######----]  60%`
+fn progress_bar(done: usize, total: usize) -> String {
+    const WIDTH: usize = 10;
+    let filled = (done * WIDTH).checked_div(total).unwrap_or(0);
+    let percent = (done * 100).checked_div(total).unwrap_or(0);
+    format!("[{}{}] {:>3}%", "#".repeat(filled), "-".repeat(WIDTH - filled), percent)
+}
+
+// However narrow the list's width, a description keeps this much of itself
+const MIN_DESCRIPTION: usize = 20;
+
+// A task's row: the ID, status, priority, due and tags cells, then the
+// description with what else there is to say about the task
+struct Row<'a> {
+    task: &'a Task,
+    cells: [String; 5],
+    description: String,
+    blocked: bool,
+}
+
+// The sections, each under its heading if it has one and a blank line
+// apart, as one table whose columns line up across all of them
+fn table(todo_list: &TodoList, sections: &[(Option<String>, Vec<&Task>)]) -> String {
+    let today = todo_list.today();
+    let rows: Vec<Vec<Row>> = sections.iter().map(|(_, tasks)| rows(todo_list, tasks, today)).collect();
+
+    let header = [tr!("show-id"), tr!("show-status"), tr!("show-priority"), tr!("show-due"), tr!("show-tags")];
+    let mut widths = header.clone().map(|label| width(&label));
+    for row in rows.iter().flatten() {
+        for (width_so_far, cell) in widths.iter_mut().zip(&row.cells) {
+            *width_so_far = (*width_so_far).max(width(cell));
+        }
+    }
+    // The description gets whatever the other columns leave over
+    let others: usize = widths.iter().map(|width| width + 2).sum();
+    let description_width = todo_list.width.map(|width| width.saturating_sub(others).max(MIN_DESCRIPTION));
+
+    let mut out = String::new();
+    if rows.iter().any(|rows| !rows.is_empty()) {
+        out.push_str(&format!("{}\n", line(&header, &tr!("show-description"), &widths)));
+    }
+    for (n, ((heading, _), rows)) in sections.iter().zip(&rows).enumerate() {
+        if n > 0 {
+            out.push('\n');
+        }
+        if let Some(heading) = heading {
+            out.push_str(&format!("{}\n", heading));
+        }
+        for row in rows {
+            let description = match description_width {
+                Some(max) => truncate(&row.description, max),
+                None => row.description.clone(),
+            };
+            let text = line(&row.cells, &description, &widths);
+            out.push_str(&format!("{}\n", todo_list.colors.style(row.task, &text, today, row.blocked)));
+        }
+    }
+    out
+}
+
+// The cells padded to their columns' widths, then the description
+fn line(cells: &[String; 5], description: &str, widths: &[usize; 5]) -> String {
+    let mut line = String::new();
+    for (cell, &column) in cells.iter().zip(widths) {
+        line.push_str(&pad(cell, column + 2));
+    }
+    line.push_str(description);
+    line
+}
+
+// The rows of `tasks`, each subtask right after its parent, indented a
+// level deeper, unless its parent is not among them
+fn rows<'a>(todo_list: &TodoList, tasks: &[&'a Task], today: Date) -> Vec<Row<'a>> {
+    let shown: HashSet<usize> = tasks.iter().map(|task| task.id).collect();
+    let mut rows = Vec::new();
+    for task in tasks.iter().filter(|task| !task.parent.is_some_and(|parent| shown.contains(&parent))) {
+        task_tree(&mut rows, todo_list, task, tasks, 0, today);
+    }
+    rows
+}
+
+// One task's row, then its subtasks' among `tasks`, a level deeper
+fn task_tree<'a>(rows: &mut Vec<Row<'a>>, todo_list: &TodoList, task: &'a Task, tasks: &[&'a Task], depth: usize, today: Date) {
+    let due = match task.due_date {
+        Some(date) => {
+            let due = if todo_list.relative_dates { date.relative_to(today) } else { todo_list.date_format.format(date) };
+            if task.is_overdue(today) { format!("{} {}", due, tr!("overdue")) } else { due }
+        }
+        None => String::new(),
+    };
+    let tags: Vec<String> = task.tags.iter().map(|tag| format!("#{}", tag)).collect();
+
+    let starts = match task.start_date.filter(|_| task.is_deferred(today)) {
+        Some(date) if todo_list.relative_dates => format!(" ({})", tr!("list-starts-relative", when = date.relative_to(today))),
+        Some(date) => format!(" ({})", tr!("list-starts", date = todo_list.date_format.format(date))),
+        None => String::new(),
+    };
+    let repeats = match task.recurrence {
+        Some(recurrence) => format!(" ({})", tr!("list-repeats", recurrence = recurrence.to_string())),
+        None => String::new(),
+    };
+    let assignee = task.assignee.as_ref().map(|assignee| format!(" @{}", assignee)).unwrap_or_default();
+    let blockers = todo_list.blockers(task.id);
+    let blocked = match blockers.as_slice() {
+        [] => String::new(),
+        ids => format!(" ({})", tr!("list-blocked-by", ids = join_ids(ids))),
+    };
+    let timing = if task.is_timing() { format!(" ({})", tr!("timer-running")) } else { String::new() };
+    let waiting = match task.waiting.as_ref().filter(|_| task.is_waiting()) {
+        Some(waiting) => {
+            let on = match &waiting.on {
+                Some(who) => tr!("list-waiting-on", who = who.as_str()),
+                None => tr!("list-waiting"),
+            };
+            let follow_up = match waiting.follow_up {
+                Some(day) if todo_list.relative_dates => {
+                    format!(", {}", tr!("list-follow-up-relative", when = day.relative_to(today)))
+                }
+                Some(day) => format!(", {}", tr!("list-follow-up", date = todo_list.date_format.format(day))),
+                None => String::new(),
+            };
+            format!(" ({}{})", on, follow_up)
+        }
+        None => String::new(),
+    };
+    let description = format!(
+        "{}{}{}{}{}{}{}{}",
+        "  ".repeat(depth),
+        task.description,
+        starts,
+        repeats,
+        blocked,
+        waiting,
+        timing,
+        assignee
+    );
+    rows.push(Row {
+        task,
+        cells: [task.id.to_string(), task.status().label(), task.priority.label(), due, tags.join(" ")],
+        description,
+        blocked: !blockers.is_empty(),
+    });
+
+    for child in tasks.iter().filter(|child| child.parent == Some(task.id)) {
+        task_tree(rows, todo_list, child, tasks, depth + 1, today);
+    }
+}
+
+/// Everything known about `task`, taken from `todo_list`: a row for each
+/// field it has, lined up, then its notes, attachments and comments.
+pub fn task_details(todo_list: &TodoList, task: &Task) -> String {
+    let today = todo_list.today();
+
+    let mut rows = vec![
+        (tr!("show-id"), task.id.to_string()),
+        (tr!("show-uuid"), task.uuid.to_string()),
+        (tr!("show-description"), task.description.clone()),
+        (tr!("show-status"), task.status().label()),
+        (tr!("show-priority"), task.priority.label()),
+    ];
+    if let Some(assignee) = &task.assignee {
+        rows.push((tr!("show-assignee"), assignee.clone()));
+    }
+    if !task.completed {
+        rows.push((tr!("show-urgency"), format!("{:.1}", todo_list.urgency.score(task, today))));
+    }
+    if let Some(due) = task.due_date {
+        let overdue = if task.is_overdue(today) { format!(" {}", tr!("overdue")) } else { String::new() };
+        rows.push((tr!("show-due"), format!("{}{}", todo_list.date_format.format(due), overdue)));
+    }
+    if let Some(recurrence) = task.recurrence {
+        rows.push((tr!("show-repeats"), recurrence.to_string()));
+    }
+    if let Some(estimate) = task.estimate {
+        rows.push((tr!("show-estimate"), estimate.to_string()));
+    }
+    if !task.tags.is_empty() {
+        rows.push((tr!("show-tags"), task.tags.join(", ")));
+    }
+    if !task.fields.is_empty() {
+        let fields: Vec<String> = task.fields.iter().map(|(name, value)| format!("{}={}", name, value)).collect();
+        rows.push((tr!("show-fields"), fields.join(", ")));
+    }
+    if let Some(created_at) = task.created_at {
+        rows.push((tr!("show-created"), created_at.to_string()));
+    }
+    if let Some(completed_at) = task.completed_at.filter(|_| task.completed) {
+        rows.push((tr!("show-completed"), completed_at.to_string()));
+    }
+    if let Some(reopened) = task.status_log.iter().rev().find(|change| !change.completed) {
+        rows.push((tr!("show-reopened"), reopened.at.to_string()));
+    }
+    if !task.time_log.is_empty() {
+        let running = if task.is_timing() { format!(" ({})", tr!("timer-running")) } else { String::new() };
+        rows.push((tr!("show-time-spent"), format!("{}{}", stats::format_time(task.time_spent(todo_list.now())), running)));
+    }
+    if task.pomodoros > 0 {
+        rows.push((tr!("show-pomodoros"), task.pomodoros.to_string()));
+    }
+    if let Some(last_reviewed) = task.last_reviewed {
+        rows.push((tr!("show-reviewed"), last_reviewed.to_string()));
+    }
+    if let Some(start) = task.start_date {
+        rows.push((tr!("show-starts"), todo_list.date_format.format(start)));
+    }
+    if let Some(until) = task.hidden_until.filter(|_| task.is_snoozed(today)) {
+        rows.push((tr!("show-snoozed"), tr!("show-snoozed-until", date = until.to_string())));
+    }
+    if let Some(waiting) = task.waiting.as_ref().filter(|_| task.is_waiting()) {
+        let since = waiting.since.to_string();
+        let value = match &waiting.on {
+            Some(who) => tr!("show-waiting-on", who = who.as_str(), since = since),
+            None => tr!("show-waiting-since", since = since),
+        };
+        rows.push((tr!("show-waiting"), value));
+        if let Some(day) = waiting.follow_up {
+            rows.push((tr!("show-follow-up"), todo_list.date_format.format(day)));
+        }
+    }
+    if let Some(parent) = task.parent {
+        rows.push((tr!("show-parent"), parent.to_string()));
+    }
+    if !task.depends_on.is_empty() {
+        rows.push((tr!("show-depends-on"), join_ids(&task.depends_on)));
+    }
+    let blockers = todo_list.blockers(task.id);
+    if !blockers.is_empty() {
+        rows.push((tr!("show-blocked-by"), join_ids(&blockers)));
+    }
+    let subtasks = todo_list.subtasks(task.id);
+    if !subtasks.is_empty() {
+        let ids: Vec<String> = subtasks.iter().map(|task| task.id.to_string()).collect();
+        rows.push((tr!("show-subtasks"), ids.join(", ")));
+    }
+    // The values line up however long the labels are in the language used
+    let columns = rows.iter().map(|(label, _)| width(label) + 1).max().unwrap_or_default();
+    let mut out = String::new();
+    for (label, value) in rows {
+        out.push_str(&format!("{} {}\n", pad(&format!("{}:", label), columns), value));
+    }
+    if let Some(notes) = &task.notes {
+        out.push_str(&format!("\n{}\n", notes));
+    }
+    if !task.attachments.is_empty() {
+        out.push_str(&format!("\n{}:\n", tr!("show-attachments")));
+        for (number, attachment) in task.attachments.iter().enumerate() {
+            out.push_str(&format!("  {}. {}\n", number + 1, attachment));
+        }
+    }
+    if !task.comments.is_empty() {
+        out.push_str(&format!("\n{}:\n", tr!("show-comments")));
+        for comment in &task.comments {
+            out.push_str(&format!("  {}  {}\n", comment.at, comment.text));
+        }
+    }
+    out
+}
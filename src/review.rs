@@ -0,0 +1,117 @@
+//! The weekly review: going through the open tasks one at a time and
+//! deciding for each whether to keep it, complete it, put it off, remove it
+//! or change its priority.
+
+use std::io::{self, Write};
+
+use todo_list::storage::{self, Storage};
+use todo_list::{tr, Priority, TodoError, TodoList};
+
+use crate::cli;
+use crate::confirm;
+
+// Prints `message` and reads one line of input, or None at the end of input
+fn prompt(message: &str) -> io::Result<Option<String>> {
+    print!("{} ", message);
+    io::stdout().flush()?;
+    let mut input = String::new();
+    if io::stdin().read_line(&mut input)? == 0 {
+        println!();
+        return Ok(None);
+    }
+    Ok(Some(input.trim().to_string()))
+}
+
+/// Walks through the open tasks, least recently reviewed first, until every
+/// one has been seen or the user quits. Each task dealt with is marked as
+/// reviewed. Returns how many were.
+pub fn run(todo_list: &mut TodoList, trash: &dyn Storage) -> Result<usize, TodoError> {
+    let ids: Vec<usize> = todo_list.review_queue().iter().map(|task| task.id).collect();
+    if ids.is_empty() {
+        println!("{}", tr!("no-tasks"));
+        return Ok(0);
+    }
+
+    let mut reviewed = 0;
+    for (position, &id) in ids.iter().enumerate() {
+        // An earlier removal may have taken this task with it
+        let Some(task) = todo_list.task(id) else {
+            continue;
+        };
+        println!("\n({}/{}) ID: {}, [{}] {}", position + 1, ids.len(), task.id, task.priority.label(), task.description);
+        if let Some(due) = task.due_date {
+            println!("  {}", tr!("review-due", date = due.to_string()));
+        }
+        if !task.tags.is_empty() {
+            println!("  {}", tr!("tags", tags = task.tags.join(", ")));
+        }
+        match task.last_reviewed {
+            Some(last_reviewed) => println!("  {}", tr!("review-last-reviewed", date = last_reviewed.date().to_string())),
+            None => println!("  {}", tr!("review-never-reviewed")),
+        }
+
+        loop {
+            let Some(choice) = prompt(&tr!("review-choices"))? else {
+                return finish(reviewed);
+            };
+            match review_task(todo_list, trash, id, &choice) {
+                Ok(Some(done)) => {
+                    if done {
+                        reviewed += 1;
+                    }
+                    break;
+                }
+                Ok(None) => return finish(reviewed),
+                Err(e) => println!("{}", tr!("error", message = e.to_string())),
+            }
+        }
+    }
+    finish(reviewed)
+}
+
+fn finish(reviewed: usize) -> Result<usize, TodoError> {
+    println!("\n{}", tr!("review-finished", count = reviewed));
+    Ok(reviewed)
+}
+
+// Carries out one choice for the task. Gives Some(true) once the task has
+// been dealt with, Some(false) to move on leaving it as it was, and None to
+// stop reviewing.
+fn review_task(todo_list: &mut TodoList, trash: &dyn Storage, id: usize, choice: &str) -> Result<Option<bool>, TodoError> {
+    match choice.to_lowercase().as_str() {
+        "k" | "keep" | "" => println!("{}", tr!("review-kept", id = id)),
+        "d" | "done" => match todo_list.complete_task(id)? {
+            Some(next_id) => println!("{}", tr!("task-completed-next", id = id, next = next_id)),
+            None => println!("{}", tr!("task-completed", id = id)),
+        },
+        "s" | "snooze" => {
+            let Some(input) = prompt(&tr!("review-snooze-for"))? else {
+                return Ok(None);
+            };
+            let days = cli::parse_span(&input).map_err(TodoError::Parse)?;
+            let due = todo_list.snooze(id, days, false)?;
+            println!("{}", tr!("task-snoozed", id = id, date = due.to_string()));
+        }
+        "r" | "remove" => {
+            let tasks = todo_list.with_subtasks(&[id]);
+            if !confirm::ask(&tr!("confirm-trash", count = tasks.len()), &tasks)? {
+                return Ok(Some(false));
+            }
+            storage::trash_tasks(todo_list, trash, &[id])?;
+            println!("{}", tr!("task-trashed", id = id));
+            // There is nothing left to mark as reviewed
+            return Ok(Some(true));
+        }
+        "p" | "priority" => {
+            let Some(input) = prompt(&tr!("review-enter-priority"))? else {
+                return Ok(None);
+            };
+            todo_list.set_priority(id, Priority::parse(&input)?)?;
+            println!("{}", tr!("priority-updated", id = id));
+        }
+        "q" | "quit" => return Ok(None),
+        _ => return Err(TodoError::Parse(tr!("review-invalid-choice", choice = choice))),
+    }
+    todo_list.mark_reviewed(id)?;
+    Ok(Some(true))
+}
@@ -0,0 +1,68 @@
+//! `todo serve`: the JSON API in `todo_list::api` over HTTP. Each request
+//! loads the list, takes the lock while it is changed and saves it again,
+//! so the command line can be used alongside the server.
+
+use std::io;
+use std::path::Path;
+
+use tiny_http::{Header, Response, Server};
+use todo_list::api;
+use todo_list::storage::Storage;
+use todo_list::{tr, TodoError};
+
+use crate::lock;
+
+/// Answers requests on `address` until the process is stopped.
+pub fn run(
+    address: &str,
+    storage: &dyn Storage,
+    trash: &dyn Storage,
+    data_file: &Path,
+    auto_complete_parents: bool,
+) -> Result<(), TodoError> {
+    let server = Server::http(address).map_err(|e| io::Error::other(tr!("cannot-listen", address = address, error = e.to_string())))?;
+    println!("{}", tr!("serving", address = address));
+
+    for mut request in server.incoming_requests() {
+        let mut body = String::new();
+        let answer = match request.as_reader().read_to_string(&mut body) {
+            Ok(_) => handle(storage, trash, data_file, auto_complete_parents, request.method().as_str(), request.url(), &body),
+            Err(e) => Err(e.into()),
+        };
+        let answer = answer.unwrap_or_else(|e| api::Response {
+            status: 500,
+            body: serde_json::json!({ "error": e.to_string() }).to_string(),
+            changed: false,
+        });
+
+        let mut response = Response::from_string(answer.body).with_status_code(answer.status);
+        if answer.status != 204 {
+            if let Ok(header) = Header::from_bytes("Content-Type", "application/json") {
+                response = response.with_header(header);
+            }
+        }
+        if let Err(e) = request.respond(response) {
+            eprintln!("{}", tr!("error", message = e.to_string()));
+        }
+    }
+    Ok(())
+}
+
+fn handle(
+    storage: &dyn Storage,
+    trash: &dyn Storage,
+    data_file: &Path,
+    auto_complete_parents: bool,
+    method: &str,
+    url: &str,
+    body: &str,
+) -> Result<api::Response, TodoError> {
+    let _lock = lock::wait(data_file)?;
+    let mut todo_list = storage.load()?;
+    todo_list.set_auto_complete_parents(auto_complete_parents);
+    let answer = api::handle(&mut todo_list, trash, method, url, body);
+    if answer.changed {
+        storage.save(&todo_list)?;
+    }
+    Ok(answer)
+}
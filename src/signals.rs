@@ -0,0 +1,65 @@
+//! Catching the signals that would otherwise end a menu session with its
+//! changes unsaved: Ctrl-C, `kill` and the terminal being closed. A caught
+//! signal only notes which one it was and cuts short whatever read the
+//! session was waiting on, which then gives an error of kind
+//! [`std::io::ErrorKind::Interrupted`]; the session asks [`take`] what
+//! happened and saves, or asks whether to, before it ends.
+
+use std::sync::atomic::{AtomicI32, Ordering};
+
+/// A signal asking the program to stop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    /// Ctrl-C, for which someone at a terminal can be asked what to do.
+    Interrupt,
+    /// `kill`, or the terminal closing; nobody is left to ask.
+    Terminate,
+}
+
+// The signal caught and not yet taken, or 0
+static CAUGHT: AtomicI32 = AtomicI32::new(0);
+
+/// Starts catching the signals, from then on for as long as the program
+/// runs.
+#[cfg(unix)]
+pub fn catch() {
+    extern "C" fn note(signal: libc::c_int) {
+        // Storing to an atomic is all a signal handler can safely do here.
+        // The terminal library raises SIGINT itself whenever a read of its
+        // is cut short, so that one must not hide a kill caught before it.
+        if signal == libc::SIGINT {
+            let _ = CAUGHT.compare_exchange(0, signal, Ordering::SeqCst, Ordering::SeqCst);
+        } else {
+            CAUGHT.store(signal, Ordering::SeqCst);
+        }
+    }
+    for signal in [libc::SIGINT, libc::SIGTERM, libc::SIGHUP] {
+        // Safe as the action is set up in full before it is installed, and
+        // its handler only stores to an atomic. Without SA_RESTART a read
+        // in progress fails with EINTR rather than going on waiting.
+        unsafe {
+            let mut action: libc::sigaction = std::mem::zeroed();
+            action.sa_sigaction = note as extern "C" fn(libc::c_int) as libc::sighandler_t;
+            libc::sigemptyset(&mut action.sa_mask);
+            libc::sigaction(signal, &action, std::ptr::null_mut());
+        }
+    }
+}
+
+#[cfg(not(unix))]
+pub fn catch() {}
+
+/// Whether a signal has been caught and not yet taken.
+pub fn pending() -> bool {
+    CAUGHT.load(Ordering::SeqCst) != 0
+}
+
+/// The signal caught since the last call, if any.
+pub fn take() -> Option<Stop> {
+    match CAUGHT.swap(0, Ordering::SeqCst) {
+        0 => None,
+        #[cfg(unix)]
+        libc::SIGINT => Some(Stop::Interrupt),
+        _ => Some(Stop::Terminate),
+    }
+}
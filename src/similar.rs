@@ -0,0 +1,33 @@
+//! Telling when two descriptions are probably the same task written twice.
+
+// Case, punctuation and spacing are ignored
+fn normalize(text: &str) -> Vec<char> {
+    let text: String = text.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect();
+    text.split_whitespace().collect::<Vec<_>>().join(" ").chars().collect()
+}
+
+// The number of single character insertions, deletions and substitutions
+// that turn `a` into `b`
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+// Whether `a` and `b` are the same once normalized, or differ by no more
+// than one edit in five characters
+pub(crate) fn similar(a: &str, b: &str) -> bool {
+    let (a, b) = (normalize(a), normalize(b));
+    let allowed = a.len().max(b.len()) / 5;
+    a.len().abs_diff(b.len()) <= allowed && edit_distance(&a, &b) <= allowed
+}
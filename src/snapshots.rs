@@ -0,0 +1,103 @@
+//! Daily snapshots of the list, for looking at it as it was on an earlier
+//! day with `todo at`. Every save also writes the whole list as JSON to a
+//! file named for the day, like `2024-03-01.json`, in the snapshot
+//! directory, so each day's file holds the list as it was last saved that
+//! day. Only the newest few are kept.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use todo_list::storage::{Page, Storage};
+use todo_list::{tr, Date, SortOrder, Task, TodoError, TodoList};
+
+/// How many days of snapshots are kept when the config file does not say.
+pub const DEFAULT_SNAPSHOTS: usize = 90;
+
+/// Storage that writes a snapshot after every save.
+pub struct SnapshotStorage {
+    inner: Box<dyn Storage>,
+    dir: PathBuf,
+    keep: usize,
+}
+
+impl SnapshotStorage {
+    /// Wraps `inner` to keep `keep` days of snapshots in `dir`, or gives it
+    /// back as it is when `keep` is 0.
+    pub fn wrap(inner: Box<dyn Storage>, dir: &Path, keep: usize) -> Box<dyn Storage> {
+        if keep == 0 {
+            return inner;
+        }
+        Box::new(SnapshotStorage { inner, dir: dir.to_path_buf(), keep })
+    }
+
+    // The list is saved either way, so a snapshot that fails is only warned
+    // about
+    fn snapshot_or_warn(&self, todo_list: &TodoList) {
+        if let Err(e) = self.snapshot(todo_list) {
+            let message = tr!("snapshot-failed", dir = self.dir.display().to_string(), error = e.to_string());
+            eprintln!("{}", tr!("warning", message = message));
+        }
+    }
+
+    fn snapshot(&self, todo_list: &TodoList) -> Result<(), TodoError> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.dir.join(format!("{}.json", Date::today()));
+        todo_list.save_tasks_with_backups(&path.to_string_lossy(), 0)?;
+        let snapshots = list(&self.dir);
+        for (_, old) in snapshots.iter().take(snapshots.len().saturating_sub(self.keep)) {
+            fs::remove_file(old)?;
+        }
+        Ok(())
+    }
+}
+
+impl Storage for SnapshotStorage {
+    fn load(&self) -> Result<TodoList, TodoError> {
+        self.inner.load()
+    }
+
+    fn save(&self, todo_list: &TodoList) -> Result<(), TodoError> {
+        self.inner.save(todo_list)?;
+        self.snapshot_or_warn(todo_list);
+        Ok(())
+    }
+
+    // The snapshot needs the whole list, which is read back for it
+    fn append(&self, task: &Task) -> Result<(), TodoError> {
+        self.inner.append(task)?;
+        self.snapshot_or_warn(&self.inner.load()?);
+        Ok(())
+    }
+
+    fn load_page(
+        &self,
+        sort: SortOrder,
+        offset: usize,
+        limit: usize,
+        keep: &dyn Fn(&Task) -> bool,
+    ) -> Result<Option<Page>, TodoError> {
+        self.inner.load_page(sort, offset, limit, keep)
+    }
+}
+
+/// The snapshot of the list as it was at the end of `day`: the one from
+/// that day, or else the last one before it, with the day it is from.
+pub fn find(dir: &Path, day: Date) -> Option<(Date, PathBuf)> {
+    list(dir).into_iter().rev().find(|(date, _)| *date <= day)
+}
+
+// The snapshots in `dir`, oldest first; other files are passed over
+fn list(dir: &Path) -> Vec<(Date, PathBuf)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut snapshots: Vec<(Date, PathBuf)> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let date = Date::parse(path.file_name()?.to_str()?.strip_suffix(".json")?).ok()?;
+            Some((date, path))
+        })
+        .collect();
+    snapshots.sort();
+    snapshots
+}
@@ -0,0 +1,250 @@
+//! A productivity report: how many tasks are open and done, how quickly they
+//! get done, which have been waiting longest, where the time tracked on them
+//! went and how much estimated effort is left.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::{Date, Estimate, Task, TodoList};
+
+/// How many days back the per-day completion counts go, today included.
+pub const REPORT_DAYS: i64 = 7;
+
+/// How many weeks back the burndown goes, this week included.
+pub const BURNDOWN_WEEKS: i64 = 6;
+
+/// How many of the oldest open tasks are listed.
+const OLDEST_SHOWN: usize = 5;
+
+/// Figures about a todo list as of one day, printed with `Display`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub pending: usize,
+    pub completed: usize,
+    /// Tasks completed on each of the last [`REPORT_DAYS`] days, oldest first.
+    pub completed_per_day: Vec<(Date, usize)>,
+    /// The mean time from creation to completion, in seconds, over the tasks
+    /// where both are known.
+    pub average_seconds_to_complete: Option<i64>,
+    /// The open tasks created longest ago, oldest first.
+    pub oldest_open: Vec<Task>,
+    /// The time tracked on each task that has any, most first, with the
+    /// pomodoros finished on it.
+    pub time_per_task: Vec<TaskTime>,
+    /// The seconds tracked on each of the last [`REPORT_DAYS`] days, oldest
+    /// first. Time is counted on the UTC day it was spent.
+    pub time_per_day: Vec<(Date, i64)>,
+    /// The estimated effort of the open tasks.
+    pub remaining: Effort,
+    /// The estimated effort that was left at the end of today and of the
+    /// same weekday in each of the [`BURNDOWN_WEEKS`] weeks before, oldest
+    /// first. Archived tasks are not counted.
+    pub burndown: Vec<(Date, Effort)>,
+}
+
+/// A total of estimates, kept apart by unit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Effort {
+    pub minutes: u32,
+    pub points: u32,
+}
+
+impl Effort {
+    // The estimates of the tasks that existed and were not yet completed at
+    // `at`, in seconds. Tasks from before creation times were recorded count
+    // as always having existed.
+    fn left_at(tasks: &[Task], at: i64) -> Self {
+        let mut effort = Effort::default();
+        for task in tasks {
+            let created = task.created_at.is_none_or(|created_at| created_at.seconds() <= at);
+            let done = task.was_completed_at(at);
+            match task.estimate.filter(|_| created && !done) {
+                Some(Estimate::Minutes(minutes)) => effort.minutes += minutes,
+                Some(Estimate::Points(points)) => effort.points += points,
+                None => {}
+            }
+        }
+        effort
+    }
+}
+
+impl fmt::Display for Effort {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.minutes, self.points) {
+            (0, 0) => write!(f, "none"),
+            (minutes, 0) => write!(f, "{}", format_time(i64::from(minutes) * 60)),
+            (0, points) => write!(f, "{}pt", points),
+            (minutes, points) => write!(f, "{} and {}pt", format_time(i64::from(minutes) * 60), points),
+        }
+    }
+}
+
+/// The time tracked on one task.
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskTime {
+    pub id: usize,
+    pub description: String,
+    pub seconds: i64,
+    pub pomodoros: u32,
+}
+
+impl Report {
+    /// Builds the report for `todo_list` as of `today`.
+    pub fn new(todo_list: &TodoList, today: Date) -> Self {
+        let tasks = todo_list.tasks();
+        let completed = tasks.iter().filter(|task| task.completed).count();
+
+        let completed_per_day = (0..REPORT_DAYS)
+            .rev()
+            .map(|days_ago| {
+                let day = today.add_days(-days_ago);
+                let count = tasks.iter()
+                    .filter(|task| task.completed && task.completed_at.is_some_and(|at| at.date() == day))
+                    .count();
+                (day, count)
+            })
+            .collect();
+
+        let durations: Vec<i64> = tasks.iter()
+            .filter(|task| task.completed)
+            .filter_map(|task| Some(task.completed_at?.seconds() - task.created_at?.seconds()))
+            .collect();
+        let average_seconds_to_complete = match durations.len() {
+            0 => None,
+            count => Some(durations.iter().sum::<i64>() / count as i64),
+        };
+
+        let mut open: Vec<&Task> = tasks.iter()
+            .filter(|task| !task.completed && task.created_at.is_some())
+            .collect();
+        open.sort_by_key(|task| (task.created_at, task.id));
+        let oldest_open = open.into_iter().take(OLDEST_SHOWN).cloned().collect();
+
+        // Running timers count up to now
+        let now = todo_list.now();
+        let mut time_per_task: Vec<TaskTime> = tasks.iter()
+            .filter(|task| !task.time_log.is_empty() || task.pomodoros > 0)
+            .map(|task| TaskTime {
+                id: task.id,
+                description: task.description.clone(),
+                seconds: task.time_spent(now),
+                pomodoros: task.pomodoros,
+            })
+            .collect();
+        time_per_task.sort_by_key(|time| (-time.seconds, time.id));
+
+        let time_per_day = (0..REPORT_DAYS)
+            .rev()
+            .map(|days_ago| {
+                let day = today.add_days(-days_ago);
+                let (day_start, day_end) = (day.to_days() * 86_400, (day.to_days() + 1) * 86_400);
+                let seconds = tasks.iter()
+                    .flat_map(|task| &task.time_log)
+                    .map(|interval| {
+                        let start = interval.start.seconds().max(day_start);
+                        let end = interval.end.unwrap_or(now).seconds().min(day_end);
+                        (end - start).max(0)
+                    })
+                    .sum();
+                (day, seconds)
+            })
+            .collect();
+
+        let remaining = Effort::left_at(tasks, now.seconds());
+        let burndown = (0..BURNDOWN_WEEKS)
+            .rev()
+            .map(|weeks_ago| {
+                let day = today.add_days(-7 * weeks_ago);
+                // The end of the day, or now for today
+                let at = ((day.to_days() + 1) * 86_400 - 1).min(now.seconds());
+                (day, Effort::left_at(tasks, at))
+            })
+            .collect();
+
+        Report {
+            pending: tasks.len() - completed,
+            completed,
+            completed_per_day,
+            average_seconds_to_complete,
+            oldest_open,
+            time_per_task,
+            time_per_day,
+            remaining,
+            burndown,
+        }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Pending:   {}", self.pending)?;
+        writeln!(f, "Completed: {}", self.completed)?;
+
+        writeln!(f, "\nCompleted in the last {} days:", REPORT_DAYS)?;
+        for (day, count) in &self.completed_per_day {
+            let line = format!("  {} {:>3} {}", day, count, "#".repeat(*count));
+            writeln!(f, "{}", line.trim_end())?;
+        }
+
+        if let Some(seconds) = self.average_seconds_to_complete {
+            writeln!(f, "\nAverage time to complete: {}", format_duration(seconds))?;
+        }
+
+        if !self.oldest_open.is_empty() {
+            writeln!(f, "\nOldest open tasks:")?;
+            for task in &self.oldest_open {
+                if let Some(created_at) = task.created_at {
+                    writeln!(f, "  ID: {}, {} (created {})", task.id, task.description, created_at.date())?;
+                }
+            }
+        }
+
+        if !self.time_per_task.is_empty() {
+            writeln!(f, "\nTime tracked:")?;
+            for time in &self.time_per_task {
+                let pomodoros = match time.pomodoros {
+                    0 => String::new(),
+                    1 => ", 1 pomodoro".to_string(),
+                    count => format!(", {} pomodoros", count),
+                };
+                writeln!(f, "  ID: {}, {} ({}{})", time.id, time.description, format_time(time.seconds), pomodoros)?;
+            }
+            writeln!(f, "\nTime tracked in the last {} days:", REPORT_DAYS)?;
+            for (day, seconds) in &self.time_per_day {
+                writeln!(f, "  {} {:>7}", day, format_time(*seconds))?;
+            }
+        }
+
+        if self.burndown.iter().any(|(_, effort)| *effort != Effort::default()) {
+            writeln!(f, "\nEstimated effort left: {}", self.remaining)?;
+            writeln!(f, "\nBurndown over the last {} weeks:", BURNDOWN_WEEKS)?;
+            for (day, effort) in &self.burndown {
+                writeln!(f, "  {} {}", day, effort)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Whole days for long spans, hours or minutes for short ones
+pub(crate) fn format_duration(seconds: i64) -> String {
+    let plural = |count: i64, unit: &str| format!("{} {}{}", count, unit, if count == 1 { "" } else { "s" });
+    if seconds >= 86_400 {
+        plural(seconds / 86_400, "day")
+    } else if seconds >= 3600 {
+        plural(seconds / 3600, "hour")
+    } else {
+        plural(seconds / 60, "minute")
+    }
+}
+
+/// Hours and minutes, like `1h 05m`, or only minutes under an hour.
+pub fn format_time(seconds: i64) -> String {
+    let minutes = seconds / 60;
+    if minutes >= 60 {
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
@@ -0,0 +1,261 @@
+//! Where a `TodoList` is kept between runs. The JSON file comes with the
+//! `native` feature; the other backends each have a cargo feature of the
+//! same name, `sqlite` and `text` on by default and `memory` off.
+
+#[cfg(feature = "memory")]
+mod memory;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+#[cfg(feature = "text")]
+mod text;
+
+use std::collections::HashMap;
+#[cfg(feature = "native")]
+use std::path::PathBuf;
+
+#[cfg(feature = "native")]
+use tracing::{info, warn};
+
+use crate::{SortOrder, Task, TodoError, TodoList, Uuid};
+
+#[cfg(feature = "memory")]
+pub use memory::InMemoryStorage;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteStorage;
+#[cfg(feature = "text")]
+pub use text::TextStorage;
+
+/// A place a todo list can be loaded from and saved to.
+pub trait Storage {
+    /// Reads the whole list. Storage that does not exist yet gives an empty
+    /// list.
+    fn load(&self) -> Result<TodoList, TodoError>;
+
+    /// Replaces everything stored with `todo_list`.
+    fn save(&self, todo_list: &TodoList) -> Result<(), TodoError>;
+
+    /// Adds a single new task without rewriting the rest of the list.
+    fn append(&self, task: &Task) -> Result<(), TodoError>;
+
+    /// Reads one page of a listing without loading the whole list: the
+    /// tasks `keep` accepts, in `sort` order, skipping the first `offset`
+    /// and stopping after `limit`. Gives None when the backend can only read
+    /// everything at once, or cannot sort that way, which is the default.
+    fn load_page(
+        &self,
+        _sort: SortOrder,
+        _offset: usize,
+        _limit: usize,
+        _keep: &dyn Fn(&Task) -> bool,
+    ) -> Result<Option<Page>, TodoError> {
+        Ok(None)
+    }
+}
+
+/// Part of a list read by [`Storage::load_page`].
+pub struct Page {
+    /// The tasks on the page, along with the open tasks they wait on so a
+    /// listing can say what blocks them. Never save this over the full list.
+    pub list: TodoList,
+    /// The IDs of the tasks on the page, in order.
+    pub ids: Vec<usize>,
+}
+
+impl Page {
+    /// The tasks on the page, in order.
+    pub fn tasks(&self) -> Vec<&Task> {
+        self.ids.iter().filter_map(|&id| self.list.task(id)).collect()
+    }
+}
+
+/// Moves completed tasks out of `todo_list` and into the list kept in
+/// `archive`, returning how many were moved. The archive is saved straight
+/// away; `todo_list` still needs saving by the caller. Saving the archive
+/// first means a crash in between duplicates tasks rather than losing them.
+pub fn archive_completed(todo_list: &mut TodoList, archive: &dyn Storage) -> Result<usize, TodoError> {
+    let archived = todo_list.archive_completed();
+    let count = archived.len();
+    if count > 0 {
+        let mut archived_list = archive.load()?;
+        archived_list.restore_tasks(archived);
+        archive.save(&archived_list)?;
+    }
+    Ok(count)
+}
+
+/// Takes the task with the given ID and its subtasks out of `archive` and
+/// puts them back in `todo_list`. Both are saved straight away, `todo_list`
+/// to `storage` first, as with [`archive_completed`] a crash in between
+/// duplicating tasks rather than losing them.
+pub fn restore_archived(todo_list: &mut TodoList, storage: &dyn Storage, archive: &dyn Storage, id: usize) -> Result<(), TodoError> {
+    let mut archived_list = archive.load()?;
+    let tasks = archived_list.take_task(id)?;
+    todo_list.restore_tasks(tasks);
+    storage.save(todo_list)?;
+    archive.save(&archived_list)
+}
+
+/// Puts the task with the given ID and its subtasks back in `todo_list`
+/// from `archive` without saving either, for a session that saves the list
+/// later or not at all. The tasks stay in the archive until
+/// [`forget_restored`] is given the UUIDs returned, once the list is saved.
+pub fn restore_archived_unsaved(todo_list: &mut TodoList, archive: &dyn Storage, id: usize) -> Result<Vec<Uuid>, TodoError> {
+    let tasks = archive.load()?.take_task(id)?;
+    let uuids = tasks.iter().map(|task| task.uuid).collect();
+    todo_list.restore_tasks(tasks);
+    Ok(uuids)
+}
+
+/// Takes the tasks with the given UUIDs out of `archive` and saves it, after
+/// [`restore_archived_unsaved`] and saving `todo_list`. Tasks no longer in
+/// `todo_list`, as the restore was undone, are left in the archive.
+pub fn forget_restored(todo_list: &TodoList, archive: &dyn Storage, uuids: &[Uuid]) -> Result<(), TodoError> {
+    let mut archived_list = archive.load()?;
+    let ids: Vec<usize> = uuids
+        .iter()
+        .filter(|uuid| todo_list.task_by_uuid(**uuid).is_some())
+        .filter_map(|uuid| archived_list.task_by_uuid(*uuid).map(|task| task.id))
+        .collect();
+    if ids.is_empty() {
+        return Ok(());
+    }
+    archived_list.remove_tasks(&ids)?;
+    archive.save(&archived_list)
+}
+
+/// Removes the tasks with the given IDs and their subtasks from `todo_list`
+/// and keeps them in `trash`, returning how many were moved. As with the
+/// archive, the trash is saved straight away and `todo_list` is left for the
+/// caller to save.
+pub fn trash_tasks(todo_list: &mut TodoList, trash: &dyn Storage, ids: &[usize]) -> Result<usize, TodoError> {
+    let mut removed = todo_list.remove_tasks(ids)?;
+    let count = removed.len();
+    let now = todo_list.now();
+    for task in &mut removed {
+        task.deleted_at = Some(now);
+    }
+    let mut trash_list = trash.load()?;
+    trash_list.restore_tasks(removed);
+    trash.save(&trash_list)?;
+    Ok(count)
+}
+
+/// Takes the task with the given ID and its subtasks out of `trash` and
+/// puts them back in `todo_list`, saving both straight away like
+/// [`restore_archived`].
+pub fn restore_trashed(todo_list: &mut TodoList, storage: &dyn Storage, trash: &dyn Storage, id: usize) -> Result<(), TodoError> {
+    let mut trash_list = trash.load()?;
+    let tasks = trash_list.take_task(id)?
+        .into_iter()
+        // Tasks brought back by an undo are already in the list
+        .filter(|task| todo_list.task(task.id).is_none())
+        .map(|task| Task { deleted_at: None, ..task })
+        .collect();
+    todo_list.restore_tasks(tasks);
+    storage.save(todo_list)?;
+    trash.save(&trash_list)
+}
+
+/// Gives every task in `todo_list`, `archive` and `trash` a new ID counting
+/// up from 1, in that order and by old ID within each, so the IDs have no
+/// gaps, and returns how many tasks got a different one. The IDs of all
+/// three are changed together so a task restored later cannot take one
+/// already in use. The archive and trash are saved straight away and
+/// `todo_list` is left for the caller to save.
+pub fn renumber(todo_list: &mut TodoList, archive: &dyn Storage, trash: &dyn Storage) -> Result<usize, TodoError> {
+    let mut lists = [archive.load()?, trash.load()?];
+    let mut ids = HashMap::new();
+    for tasks in [todo_list.tasks()].into_iter().chain(lists.iter().map(|list| list.tasks())) {
+        let mut old: Vec<usize> = tasks.iter().map(|task| task.id).collect();
+        old.sort_unstable();
+        for id in old {
+            let new = ids.len() + 1;
+            ids.insert(id, new);
+        }
+    }
+    let changed = ids.iter().filter(|(old, new)| old != new).count();
+    if changed == 0 {
+        return Ok(0);
+    }
+    todo_list.renumber(&ids);
+    for (list, storage) in lists.iter_mut().zip([archive, trash]) {
+        list.renumber(&ids);
+        storage.save(list)?;
+    }
+    Ok(changed)
+}
+
+/// Deletes tasks from `trash` for good, returning how many were deleted.
+/// With `older_than` set, only tasks trashed at least that many seconds ago
+/// are deleted.
+pub fn empty_trash(trash: &dyn Storage, older_than: Option<i64>) -> Result<usize, TodoError> {
+    let mut trash_list = trash.load()?;
+    let ids = expired(&trash_list, older_than);
+    let count = trash_list.remove_tasks(&ids)?.len();
+    trash.save(&trash_list)?;
+    Ok(count)
+}
+
+/// The IDs of the tasks in `trash_list` that [`empty_trash`] deletes.
+pub fn expired(trash_list: &TodoList, older_than: Option<i64>) -> Vec<usize> {
+    let cutoff = older_than.map(|seconds| trash_list.now().seconds() - seconds);
+    trash_list.tasks().iter()
+        .filter(|task| match (cutoff, task.deleted_at) {
+            (Some(cutoff), Some(deleted_at)) => deleted_at.seconds() <= cutoff,
+            (Some(_), None) => false,
+            (None, _) => true,
+        })
+        .map(|task| task.id)
+        .collect()
+}
+
+/// How many previous versions of a JSON file are kept when no other number
+/// is set.
+pub const DEFAULT_BACKUPS: usize = 3;
+
+/// Stores the list as a JSON file, keeping a few backups of earlier saves.
+#[cfg(feature = "native")]
+pub struct JsonStorage {
+    path: PathBuf,
+    backups: usize,
+}
+
+#[cfg(feature = "native")]
+impl JsonStorage {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        JsonStorage {
+            path: path.into(),
+            backups: DEFAULT_BACKUPS,
+        }
+    }
+
+    /// Sets how many previous versions are kept as `path.1`, `path.2`, ...
+    pub fn with_backups(mut self, backups: usize) -> Self {
+        self.backups = backups;
+        self
+    }
+}
+
+#[cfg(feature = "native")]
+impl Storage for JsonStorage {
+    fn load(&self) -> Result<TodoList, TodoError> {
+        let todo_list = TodoList::load_tasks(&self.path.to_string_lossy())
+            .inspect_err(|e| warn!(backend = "json", path = %self.path.display(), error = %e, "load failed"))?;
+        info!(backend = "json", path = %self.path.display(), tasks = todo_list.tasks().len(), "loaded");
+        Ok(todo_list)
+    }
+
+    fn save(&self, todo_list: &TodoList) -> Result<(), TodoError> {
+        todo_list.save_tasks_with_backups(&self.path.to_string_lossy(), self.backups)
+            .inspect_err(|e| warn!(backend = "json", path = %self.path.display(), error = %e, "save failed"))?;
+        info!(backend = "json", path = %self.path.display(), tasks = todo_list.tasks().len(), backups = self.backups, "saved");
+        Ok(())
+    }
+
+    // A JSON file cannot be appended to in place, so this rewrites it
+    fn append(&self, task: &Task) -> Result<(), TodoError> {
+        let mut todo_list = self.load()?;
+        todo_list.insert_task(task.clone());
+        self.save(&todo_list)
+    }
+}
@@ -0,0 +1,46 @@
+use std::sync::Mutex;
+
+use crate::{Task, TodoError, TodoList};
+
+use super::Storage;
+
+/// Keeps the list in memory only, for tests and for programs that persist
+/// it some other way. Each load gives a fresh copy of what was last saved.
+#[derive(Default)]
+pub struct InMemoryStorage {
+    saved: Mutex<(Vec<Task>, usize)>,
+}
+
+impl InMemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Storage for InMemoryStorage {
+    fn load(&self) -> Result<TodoList, TodoError> {
+        let saved = self.saved.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut todo_list = TodoList::new();
+        for task in &saved.0 {
+            todo_list.insert_task(task.clone());
+        }
+        todo_list.next_id = todo_list.next_id.max(saved.1);
+        Ok(todo_list)
+    }
+
+    fn save(&self, todo_list: &TodoList) -> Result<(), TodoError> {
+        let mut saved = self.saved.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        *saved = (todo_list.tasks().to_vec(), todo_list.next_id);
+        Ok(())
+    }
+
+    fn append(&self, task: &Task) -> Result<(), TodoError> {
+        let mut saved = self.saved.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        match saved.0.iter().position(|existing| existing.id == task.id) {
+            Some(index) => saved.0[index] = task.clone(),
+            None => saved.0.push(task.clone()),
+        }
+        saved.1 = saved.1.max(task.id + 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,212 @@
+use std::path::PathBuf;
+
+use rusqlite::functions::FunctionFlags;
+use rusqlite::{params, Connection, OpenFlags};
+use tracing::{info, trace, warn};
+
+use crate::{text, SortOrder, Task, TodoError, TodoList};
+
+use super::{Page, Storage};
+
+// The commonly queried fields get their own columns, the full task is kept
+// as JSON in `data` so new fields need no schema change
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS tasks (
+        id INTEGER PRIMARY KEY,
+        description TEXT NOT NULL,
+        completed INTEGER NOT NULL,
+        due_date TEXT,
+        priority TEXT NOT NULL,
+        data TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS tasks_due_date ON tasks (due_date);
+    CREATE TABLE IF NOT EXISTS meta (
+        key TEXT PRIMARY KEY,
+        value INTEGER NOT NULL
+    );
+";
+
+/// Stores the list in an SQLite database. Every save runs in a transaction,
+/// so a crash part way through leaves the previous list intact.
+pub struct SqliteStorage {
+    path: PathBuf,
+    read_only: bool,
+}
+
+impl SqliteStorage {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        SqliteStorage { path: path.into(), read_only: false }
+    }
+
+    /// Opens the database read-only, so it is neither created nor given
+    /// its tables when missing, and saving fails.
+    pub fn read_only(mut self) -> Self {
+        self.read_only = true;
+        self
+    }
+
+    fn open(&self) -> Result<Connection, TodoError> {
+        let connection = if self.read_only {
+            Connection::open_with_flags(&self.path, OpenFlags::SQLITE_OPEN_READ_ONLY)?
+        } else {
+            let connection = Connection::open(&self.path)?;
+            connection.execute_batch(SCHEMA)?;
+            connection
+        };
+        // SQLite's own lower() only knows ASCII, so sorting by name goes
+        // through the same folding as the list in memory
+        connection.create_scalar_function("fold", 1, FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC, |context| {
+            Ok(text::fold(&context.get::<String>(0)?))
+        })?;
+        Ok(connection)
+    }
+
+    fn read_all(&self) -> Result<TodoList, TodoError> {
+        let connection = self.open()?;
+        let mut statement = connection.prepare("SELECT data FROM tasks ORDER BY id")?;
+        let rows = statement.query_map([], |row| row.get::<_, String>(0))?;
+
+        let mut todo_list = TodoList::new();
+        for data in rows {
+            let task: Task = serde_json::from_str(&data?)?;
+            trace!(id = task.id, "read task row");
+            todo_list.insert_task(task);
+        }
+
+        let next_id: Option<i64> = connection
+            .query_row("SELECT value FROM meta WHERE key = 'next_id'", [], |row| row.get(0))
+            .ok();
+        if let Some(next_id) = next_id {
+            todo_list.next_id = todo_list.next_id.max(next_id as usize);
+        }
+        Ok(todo_list)
+    }
+
+    fn write_all(&self, todo_list: &TodoList) -> Result<(), TodoError> {
+        let mut connection = self.open()?;
+        let transaction = connection.transaction()?;
+        transaction.execute("DELETE FROM tasks", [])?;
+        transaction.execute("DELETE FROM meta WHERE key = 'next_id'", [])?;
+        for task in todo_list.tasks() {
+            insert_task(&transaction, task)?;
+        }
+        store_next_id(&transaction, todo_list.next_id)?;
+        transaction.commit()?;
+        Ok(())
+    }
+}
+
+fn insert_task(connection: &Connection, task: &Task) -> Result<(), TodoError> {
+    connection.execute(
+        "INSERT OR REPLACE INTO tasks (id, description, completed, due_date, priority, data)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            task.id as i64,
+            task.description,
+            task.completed,
+            task.due_date.map(|date| date.to_string()),
+            task.priority.to_string(),
+            serde_json::to_string(task)?,
+        ],
+    )?;
+    Ok(())
+}
+
+// The ORDER BY clause matching each sort order but urgency, which is worked
+// out from the whole task
+fn order_by(sort: SortOrder) -> Option<&'static str> {
+    Some(match sort {
+        SortOrder::Priority => "CASE priority WHEN 'high' THEN 0 WHEN 'medium' THEN 1 ELSE 2 END, id",
+        SortOrder::Due => "due_date IS NULL, due_date, CASE priority WHEN 'high' THEN 0 WHEN 'medium' THEN 1 ELSE 2 END, id",
+        SortOrder::Created => "json_extract(data, '$.created_at') IS NOT NULL, json_extract(data, '$.created_at'), id",
+        SortOrder::Alpha => "fold(description), id",
+        SortOrder::Id => "id",
+        SortOrder::Manual => "json_extract(data, '$.rank') IS NULL, json_extract(data, '$.rank'), id",
+        SortOrder::Urgency => return None,
+    })
+}
+
+fn store_next_id(connection: &Connection, next_id: usize) -> rusqlite::Result<()> {
+    connection.execute(
+        "INSERT INTO meta (key, value) VALUES ('next_id', ?1)
+         ON CONFLICT (key) DO UPDATE SET value = MAX(value, excluded.value)",
+        params![next_id as i64],
+    )?;
+    Ok(())
+}
+
+impl Storage for SqliteStorage {
+    fn load(&self) -> Result<TodoList, TodoError> {
+        // Such as an archive that was never made
+        if self.read_only && !self.path.exists() {
+            return Ok(TodoList::new());
+        }
+        let todo_list = self.read_all()
+            .inspect_err(|e| warn!(backend = "sqlite", path = %self.path.display(), error = %e, "load failed"))?;
+        info!(backend = "sqlite", path = %self.path.display(), tasks = todo_list.tasks().len(), "loaded");
+        Ok(todo_list)
+    }
+
+    fn save(&self, todo_list: &TodoList) -> Result<(), TodoError> {
+        self.write_all(todo_list)
+            .inspect_err(|e| warn!(backend = "sqlite", path = %self.path.display(), error = %e, "save failed"))?;
+        info!(backend = "sqlite", path = %self.path.display(), tasks = todo_list.tasks().len(), "saved");
+        Ok(())
+    }
+
+    fn append(&self, task: &Task) -> Result<(), TodoError> {
+        let mut connection = self.open()?;
+        let transaction = connection.transaction()?;
+        insert_task(&transaction, task)?;
+        store_next_id(&transaction, task.id + 1)?;
+        transaction.commit()?;
+        Ok(())
+    }
+
+    // Rows are read in order and parsed one at a time, so only the tasks up
+    // to the end of the page are ever parsed, plus any `keep` turns down
+    fn load_page(
+        &self,
+        sort: SortOrder,
+        offset: usize,
+        limit: usize,
+        keep: &dyn Fn(&Task) -> bool,
+    ) -> Result<Option<Page>, TodoError> {
+        let Some(order) = order_by(sort) else {
+            return Ok(None);
+        };
+        let connection = self.open()?;
+        let mut statement = connection.prepare(&format!("SELECT data FROM tasks ORDER BY {}", order))?;
+        let mut rows = statement.query([])?;
+
+        let mut page = Page { list: TodoList::new(), ids: Vec::new() };
+        let mut skipped = 0;
+        while page.ids.len() < limit {
+            let Some(row) = rows.next()? else {
+                break;
+            };
+            let task: Task = serde_json::from_str(&row.get::<_, String>(0)?)?;
+            if !keep(&task) {
+                continue;
+            }
+            if skipped < offset {
+                skipped += 1;
+                continue;
+            }
+            page.ids.push(task.id);
+            page.list.insert_task(task);
+        }
+
+        let waited_on: Vec<usize> = page.tasks().iter().flat_map(|task| task.depends_on.iter().copied()).collect();
+        let mut statement = connection.prepare("SELECT data FROM tasks WHERE id = ?1 AND completed = 0")?;
+        for id in waited_on {
+            if page.list.task(id).is_none() {
+                let data: Option<String> = statement.query_row(params![id as i64], |row| row.get(0)).ok();
+                if let Some(data) = data {
+                    page.list.insert_task(serde_json::from_str(&data)?);
+                }
+            }
+        }
+        Ok(Some(page))
+    }
+}
@@ -0,0 +1,51 @@
+use std::fs;
+use std::path::PathBuf;
+
+use tracing::{info, warn};
+
+use crate::{atomic, todotxt, Task, TodoError, TodoList};
+
+use super::Storage;
+
+/// Stores the list as a todo.txt file, so other todo.txt tools can read and
+/// edit it. IDs, subtasks, the manual order and UUIDs are kept with `id:`,
+/// `parent:`, `rank:` and `uuid:` tokens; notes, dependencies and times of
+/// day are not kept.
+pub struct TextStorage {
+    path: PathBuf,
+}
+
+impl TextStorage {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        TextStorage { path: path.into() }
+    }
+}
+
+impl Storage for TextStorage {
+    fn load(&self) -> Result<TodoList, TodoError> {
+        if !self.path.exists() {
+            return Ok(TodoList::new());
+        }
+        let todo_list = fs::read_to_string(&self.path)
+            .map_err(TodoError::from)
+            .and_then(|text| todotxt::read_list(&text))
+            .inspect_err(|e| warn!(backend = "text", path = %self.path.display(), error = %e, "load failed"))?;
+        info!(backend = "text", path = %self.path.display(), tasks = todo_list.tasks().len(), "loaded");
+        Ok(todo_list)
+    }
+
+    fn save(&self, todo_list: &TodoList) -> Result<(), TodoError> {
+        let text = todotxt::write_list(todo_list);
+        atomic::write_atomic(&self.path, 0, |writer| writer.write_all(text.as_bytes()))
+            .inspect_err(|e| warn!(backend = "text", path = %self.path.display(), error = %e, "save failed"))?;
+        info!(backend = "text", path = %self.path.display(), tasks = todo_list.tasks().len(), "saved");
+        Ok(())
+    }
+
+    // Rewrites the file, as the line has to go in the right place
+    fn append(&self, task: &Task) -> Result<(), TodoError> {
+        let mut todo_list = self.load()?;
+        todo_list.insert_task(task.clone());
+        self.save(&todo_list)
+    }
+}
@@ -0,0 +1,441 @@
+//! Help for testing code that uses the engine, behind the `testing`
+//! feature: builders for tasks and lists, and a [`MockClock`] to give them
+//! so due dates, overdue tasks and recurrences come out the same on every
+//! run.
+//!
+//! ```
+//! # use std::sync::Arc;
+//! # use todo_list::testing::{date, ListBuilder, MockClock, TaskBuilder};
+//! # use todo_list::{Recurrence, Timestamp};
+//! let clock = MockClock::at(Timestamp::parse("2024-06-01T09:00:00Z")?);
+//! let todo_list = ListBuilder::new()
+//!     .clock(Arc::new(clock.clone()))
+//!     .task(TaskBuilder::new("pay rent").due(date("2024-05-31")).recurrence(Recurrence::Monthly))
+//!     .task(TaskBuilder::new("water the plants").due(date("2024-06-03")))
+//!     .build();
+//! assert!(todo_list.task(1).unwrap().is_overdue(todo_list.today()));
+//! assert!(!todo_list.task(2).unwrap().is_overdue(todo_list.today()));
+//!
+//! clock.advance_days(3);
+//! assert!(todo_list.task(2).unwrap().is_overdue(todo_list.today()));
+//! # Ok::<(), todo_list::TodoError>(())
+//! ```
+
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+
+use crate::storage::{InMemoryStorage, Storage};
+use crate::{Clock, Column, Date, Priority, Recurrence, Task, Timestamp, TodoList, Uuid};
+
+/// The date written as YYYY-MM-DD, panicking if it is not one, which is
+/// what a test wants.
+pub fn date(text: &str) -> Date {
+    Date::parse(text).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Builds a task field by field. Anything not set is as a task just added
+/// with `todo add` has it, apart from the ID, which [`ListBuilder`] gives
+/// out when it is left at 0, and the times it was created and completed,
+/// which it fills in from the list's clock.
+#[derive(Debug, Clone)]
+pub struct TaskBuilder {
+    task: Task,
+}
+
+impl TaskBuilder {
+    pub fn new(description: impl Into<String>) -> Self {
+        TaskBuilder {
+            task: Task {
+                uuid: Uuid::new_v4(),
+                description: description.into(),
+                ..Task::default()
+            },
+        }
+    }
+
+    pub fn created_at(mut self, at: Timestamp) -> Self {
+        self.task.created_at = Some(at);
+        self
+    }
+
+    pub fn id(mut self, id: usize) -> Self {
+        self.task.id = id;
+        self
+    }
+
+    pub fn due(mut self, due: Date) -> Self {
+        self.task.due_date = Some(due);
+        self
+    }
+
+    pub fn start(mut self, start: Date) -> Self {
+        self.task.start_date = Some(start);
+        self
+    }
+
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.task.priority = priority;
+        self
+    }
+
+    /// Adds a tag; call it again for more.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.task.tags.push(tag.into());
+        self
+    }
+
+    pub fn recurrence(mut self, recurrence: Recurrence) -> Self {
+        self.task.recurrence = Some(recurrence);
+        self
+    }
+
+    pub fn column(mut self, column: Column) -> Self {
+        self.task.column = column;
+        self
+    }
+
+    pub fn parent(mut self, parent: usize) -> Self {
+        self.task.parent = Some(parent);
+        self
+    }
+
+    /// Makes the task wait on another one; call it again for more.
+    pub fn depends_on(mut self, id: usize) -> Self {
+        self.task.depends_on.push(id);
+        self
+    }
+
+    pub fn assignee(mut self, assignee: impl Into<String>) -> Self {
+        self.task.assignee = Some(assignee.into());
+        self
+    }
+
+    /// Marks the task completed at the time the list's clock gives when it
+    /// is built.
+    pub fn completed(mut self) -> Self {
+        self.task.completed = true;
+        self
+    }
+
+    /// Marks the task completed at `at`.
+    pub fn completed_at(mut self, at: Timestamp) -> Self {
+        self.task.completed = true;
+        self.task.completed_at = Some(at);
+        self
+    }
+
+    pub fn build(self) -> Task {
+        self.task
+    }
+}
+
+impl From<TaskBuilder> for Task {
+    fn from(builder: TaskBuilder) -> Self {
+        builder.build()
+    }
+}
+
+/// Builds a list from tasks, in order. Tasks without an ID get the next
+/// free one, counting from 1.
+#[derive(Default)]
+pub struct ListBuilder {
+    tasks: Vec<Task>,
+    clock: Option<Arc<dyn Clock>>,
+}
+
+impl ListBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn task(mut self, task: impl Into<Task>) -> Self {
+        self.tasks.push(task.into());
+        self
+    }
+
+    /// Gives the list `clock` instead of the system's.
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// The list, with no history to undo.
+    pub fn build(self) -> TodoList {
+        let mut todo_list = TodoList::new();
+        if let Some(clock) = self.clock {
+            todo_list.set_clock(clock);
+        }
+        let now = todo_list.now();
+        let mut next_id = self.tasks.iter().map(|task| task.id + 1).max().unwrap_or(1);
+        for mut task in self.tasks {
+            if task.id == 0 {
+                task.id = next_id;
+                next_id += 1;
+            }
+            task.created_at.get_or_insert(now);
+            if task.completed {
+                task.completed_at.get_or_insert(now);
+            }
+            todo_list.insert_task(task);
+        }
+        todo_list
+    }
+
+    /// Storage that already holds the list, for code that loads one.
+    pub fn storage(self) -> InMemoryStorage {
+        let storage = InMemoryStorage::new();
+        // Saving to memory cannot fail
+        let _ = storage.save(&self.build());
+        storage
+    }
+}
+
+/// A [`Clock`] that stands still until it is moved, for
+/// [`TodoList::set_clock`]. Clones share the time, so a test can keep one
+/// to move while the list has another.
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    seconds: Arc<AtomicI64>,
+}
+
+impl MockClock {
+    /// A clock stopped at `now`.
+    pub fn at(now: Timestamp) -> Self {
+        MockClock { seconds: Arc::new(AtomicI64::new(now.seconds())) }
+    }
+
+    /// A clock stopped at midnight UTC at the start of `day`.
+    pub fn on(day: Date) -> Self {
+        MockClock::at(Timestamp::from_seconds(day.to_days() * 86_400))
+    }
+
+    /// Moves the clock to `now`, forwards or back.
+    pub fn set(&self, now: Timestamp) {
+        self.seconds.store(now.seconds(), Ordering::SeqCst);
+    }
+
+    /// Moves the clock on by `seconds`.
+    pub fn advance(&self, seconds: i64) {
+        self.seconds.fetch_add(seconds, Ordering::SeqCst);
+    }
+
+    /// Moves the clock on by `days` whole days.
+    pub fn advance_days(&self, days: i64) {
+        self.advance(days * 86_400);
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Timestamp {
+        Timestamp::from_seconds(self.seconds.load(Ordering::SeqCst))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list_on(day: &str, tasks: Vec<TaskBuilder>) -> (MockClock, TodoList) {
+        let clock = MockClock::on(date(day));
+        let mut builder = ListBuilder::new().clock(Arc::new(clock.clone()));
+        for task in tasks {
+            builder = builder.task(task);
+        }
+        (clock, builder.build())
+    }
+
+    #[test]
+    fn tasks_fall_overdue_as_the_clock_moves() {
+        let (clock, todo_list) = list_on("2024-06-01", vec![TaskBuilder::new("water the plants").due(date("2024-06-03"))]);
+        let task = todo_list.task(1).unwrap();
+        assert!(!task.is_overdue(todo_list.today()));
+
+        clock.advance_days(2);
+        assert!(!todo_list.task(1).unwrap().is_overdue(todo_list.today()));
+        clock.advance_days(1);
+        assert!(todo_list.task(1).unwrap().is_overdue(todo_list.today()));
+        assert_eq!(todo_list.newly_overdue(todo_list.today()).len(), 1);
+    }
+
+    #[test]
+    fn completed_tasks_are_never_overdue() {
+        let (_, todo_list) = list_on("2024-06-10", vec![TaskBuilder::new("pay rent").due(date("2024-06-01")).completed()]);
+        assert!(!todo_list.task(1).unwrap().is_overdue(todo_list.today()));
+    }
+
+    #[test]
+    fn built_tasks_take_their_times_from_the_clock() {
+        let at = Timestamp::parse("2024-06-01T09:00:00Z").unwrap();
+        let clock = MockClock::at(at);
+        let todo_list = ListBuilder::new()
+            .clock(Arc::new(clock))
+            .task(TaskBuilder::new("open"))
+            .task(TaskBuilder::new("done").completed())
+            .task(TaskBuilder::new("old").created_at(Timestamp::parse("2024-01-01T00:00:00Z").unwrap()))
+            .build();
+        assert_eq!(todo_list.task(1).unwrap().created_at, Some(at));
+        assert_eq!(todo_list.task(1).unwrap().completed_at, None);
+        assert_eq!(todo_list.task(2).unwrap().completed_at, Some(at));
+        assert_eq!(todo_list.task(3).unwrap().created_at.unwrap().date(), date("2024-01-01"));
+    }
+
+    #[test]
+    fn completing_a_recurring_task_adds_the_next_one() {
+        let (clock, mut todo_list) = list_on(
+            "2024-06-01",
+            vec![TaskBuilder::new("pay rent").due(date("2024-05-31")).start(date("2024-05-25")).recurrence(Recurrence::Monthly)],
+        );
+        clock.advance_days(1);
+        let next = todo_list.complete_task(1).unwrap().unwrap();
+        let done = todo_list.task(1).unwrap();
+        assert!(done.completed);
+        assert_eq!(done.completed_at.unwrap().date(), date("2024-06-02"));
+
+        let next = todo_list.task(next).unwrap();
+        assert!(!next.completed);
+        assert_eq!(next.due_date, Some(date("2024-06-30")));
+        assert_eq!(next.start_date, Some(date("2024-06-24")));
+        assert_eq!(next.recurs_from, Some(1));
+        assert!(next.is_deferred(todo_list.today()));
+    }
+
+    #[test]
+    fn monthly_tasks_go_back_to_their_day_after_a_short_month() {
+        let (_, mut todo_list) = list_on(
+            "2024-01-31",
+            vec![TaskBuilder::new("pay rent").due(date("2024-01-31")).recurrence(Recurrence::Monthly)],
+        );
+        todo_list.set_order(&[1]).unwrap();
+        let mut id = 1;
+        let mut dues = Vec::new();
+        for _ in 0..4 {
+            id = todo_list.complete_task(id).unwrap().unwrap();
+            let next = todo_list.task(id).unwrap();
+            assert_eq!(next.rank, None);
+            dues.push(next.due_date.unwrap());
+        }
+        assert_eq!(dues, ["2024-02-29", "2024-03-31", "2024-04-30", "2024-05-31"].map(date));
+
+        todo_list.set_due_date(id, Some(date("2024-06-15"))).unwrap();
+        let id = todo_list.complete_task(id).unwrap().unwrap();
+        assert_eq!(todo_list.task(id).unwrap().due_date, Some(date("2024-07-15")));
+    }
+
+    #[test]
+    fn recurring_tasks_without_a_due_date_count_from_today() {
+        let (_, mut todo_list) = list_on("2024-06-01", vec![TaskBuilder::new("stretch").recurrence(Recurrence::Weekly)]);
+        let next = todo_list.complete_task(1).unwrap().unwrap();
+        assert_eq!(todo_list.task(next).unwrap().due_date, Some(date("2024-06-08")));
+    }
+
+    #[test]
+    fn snoozing_counts_from_the_clocks_today() {
+        let (clock, mut todo_list) = list_on(
+            "2024-06-10",
+            vec![TaskBuilder::new("overdue").due(date("2024-06-01")), TaskBuilder::new("later").due(date("2024-06-20"))],
+        );
+        assert_eq!(todo_list.snooze(1, 3, false).unwrap(), date("2024-06-13"));
+        assert_eq!(todo_list.snooze(2, 3, false).unwrap(), date("2024-06-23"));
+
+        clock.advance_days(10);
+        assert_eq!(todo_list.snooze(1, 1, false).unwrap(), date("2024-06-21"));
+    }
+
+    #[test]
+    fn hidden_tasks_come_back_when_the_clock_gets_there() {
+        let (clock, mut todo_list) = list_on("2024-06-10", vec![TaskBuilder::new("call the bank"), TaskBuilder::new("buy milk")]);
+        todo_list.snooze(1, 2, true).unwrap();
+        assert!(todo_list.task(1).unwrap().is_snoozed(todo_list.today()));
+        assert_eq!(todo_list.sorted_tasks().iter().map(|task| task.id).collect::<Vec<_>>(), [2]);
+
+        clock.advance_days(2);
+        assert!(!todo_list.task(1).unwrap().is_snoozed(todo_list.today()));
+        assert_eq!(todo_list.sorted_tasks().len(), 2);
+    }
+
+    #[test]
+    fn storage_holds_the_built_list() {
+        let storage = ListBuilder::new().task(TaskBuilder::new("buy milk")).task(TaskBuilder::new("call mum")).storage();
+        let todo_list = storage.load().unwrap();
+        assert_eq!(todo_list.tasks().len(), 2);
+        assert_eq!(todo_list.task(2).unwrap().description, "call mum");
+    }
+
+    #[test]
+    fn renumbering_drops_links_to_purged_tasks() {
+        let mut todo_list = ListBuilder::new()
+            .task(TaskBuilder::new("one").id(1))
+            .task(TaskBuilder::new("three").id(3).depends_on(1))
+            .task(TaskBuilder::new("four").id(4).depends_on(2).depends_on(3).parent(2))
+            .build();
+        let nowhere = InMemoryStorage::new();
+        assert_eq!(crate::storage::renumber(&mut todo_list, &nowhere, &nowhere).unwrap(), 2);
+
+        let three = todo_list.task(2).unwrap();
+        assert_eq!(three.description, "three");
+        assert_eq!(three.depends_on, vec![1]);
+        let four = todo_list.task(3).unwrap();
+        assert_eq!(four.description, "four");
+        assert_eq!(four.depends_on, vec![2]);
+        assert_eq!(four.parent, None);
+    }
+
+    #[test]
+    fn editing_all_changes_start_dates_and_columns() {
+        let mut todo_list = ListBuilder::new()
+            .task(TaskBuilder::new("water the plants").id(1))
+            .task(TaskBuilder::new("call mum").id(2).start(date("2024-06-01")).column(Column::InProgress))
+            .build();
+        let text = "1 water the plants t:2024-07-01 status:blocked\n2 call mum\nbuy milk t:2024-08-01 status:in-progress\n";
+        let edit = crate::batch::apply(&mut todo_list, text).unwrap();
+        assert_eq!(edit.changed, vec![1, 2]);
+        assert_eq!(edit.added, vec![3]);
+
+        let plants = todo_list.task(1).unwrap();
+        assert_eq!((plants.start_date, plants.column), (Some(date("2024-07-01")), Column::Blocked));
+        let mum = todo_list.task(2).unwrap();
+        assert_eq!((mum.start_date, mum.column), (None, Column::Todo));
+        let milk = todo_list.task(3).unwrap();
+        assert_eq!((milk.start_date, milk.column), (Some(date("2024-08-01")), Column::InProgress));
+    }
+
+    #[test]
+    fn editing_all_to_status_done_completes() {
+        let mut todo_list = ListBuilder::new().task(TaskBuilder::new("water the plants").id(1)).build();
+        let edit = crate::batch::apply(&mut todo_list, "1 water the plants status:done\n").unwrap();
+        assert_eq!(edit.completed, vec![1]);
+        assert!(todo_list.task(1).unwrap().completed);
+    }
+
+    #[test]
+    fn tags_match_whatever_their_case() {
+        let mut todo_list = ListBuilder::new().task(TaskBuilder::new("call mum @Küche").id(1)).build();
+        todo_list.add_tag(1, "ÄRGER").unwrap();
+        let task = todo_list.task(1).unwrap();
+        assert_eq!(task.tags, vec!["ärger"]);
+        assert!(task.has_tag("ärger") && task.has_tag("ÄRGER") && task.has_tag(" Ärger "));
+        assert!(task.has_context("KÜCHE"));
+        assert!(todo_list.add_tag(1, "Ärger").is_err());
+        todo_list.remove_tag(1, "ÄrGeR").unwrap();
+        assert!(todo_list.task(1).unwrap().tags.is_empty());
+    }
+
+    #[test]
+    fn restoring_without_saving_leaves_the_archive_alone() {
+        let archive = ListBuilder::new()
+            .task(TaskBuilder::new("pay rent").id(4).completed())
+            .task(TaskBuilder::new("buy milk").id(5).completed())
+            .storage();
+        let mut todo_list = ListBuilder::new().task(TaskBuilder::new("call mum").id(1)).build();
+        let rent = crate::storage::restore_archived_unsaved(&mut todo_list, &archive, 4).unwrap();
+        let milk = crate::storage::restore_archived_unsaved(&mut todo_list, &archive, 5).unwrap();
+        assert_eq!(todo_list.task(4).unwrap().description, "pay rent");
+        assert_eq!(archive.load().unwrap().tasks().len(), 2);
+
+        todo_list.undo().unwrap();
+        crate::storage::forget_restored(&todo_list, &archive, &[rent, milk].concat()).unwrap();
+        let archived = archive.load().unwrap();
+        assert!(archived.task(4).is_none());
+        assert_eq!(archived.task(5).unwrap().description, "buy milk");
+    }
+}
@@ -0,0 +1,242 @@
+//! Measuring, cutting and searching text as it appears on screen rather
+//! than by its bytes or chars. Wide characters, such as CJK and most emoji,
+//! take up two columns of a terminal, combining marks none, and what a
+//! reader sees as one character can be several chars: an accent written
+//! after its letter, a flag made of two regional indicators, or a family
+//! emoji joined from four. Cutting or matching inside one of those garbles
+//! it.
+//!
+//! Graphemes are found by the rules of Unicode's extended grapheme clusters
+//! that matter for task descriptions: marks, variation selectors, emoji
+//! modifiers and tags extend the character before them, a zero width joiner
+//! joins the characters either side, regional indicators pair up, and CR LF
+//! stays together.
+//!
+//! Searches ignore case and accents by matching [`fold`]ed text, so `Cafe`
+//! finds `café`.
+
+use icu_normalizer::DecomposingNormalizerBorrowed;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+const ZERO_WIDTH_JOINER: char = '\u{200D}';
+
+/// How many columns `text` takes up on a terminal.
+pub(crate) fn width(text: &str) -> usize {
+    text.width()
+}
+
+/// `text` followed by enough spaces to take up `columns` columns, or
+/// `text` alone if it takes more.
+pub(crate) fn pad(text: &str, columns: usize) -> String {
+    format!("{}{}", text, " ".repeat(columns.saturating_sub(width(text))))
+}
+
+/// `text` cut short with an ellipsis to take up no more than `max` columns,
+/// between graphemes so none is left in pieces. With no columns at all not
+/// even the ellipsis fits, so nothing is left.
+pub(crate) fn truncate(text: &str, max: usize) -> String {
+    if width(text) <= max {
+        return text.to_string();
+    }
+    if max == 0 {
+        return String::new();
+    }
+    let mut cut = String::new();
+    let mut used = 0;
+    for grapheme in graphemes(text) {
+        used += width(grapheme);
+        if used + 1 > max {
+            break;
+        }
+        cut.push_str(grapheme);
+    }
+    cut.push('…');
+    cut
+}
+
+/// Whether `needle` is in `haystack` as whole graphemes, so `e` is not
+/// found in an `é` written as `e` and an accent, nor one emoji in a
+/// sequence joined from it.
+pub(crate) fn contains(haystack: &str, needle: &str) -> bool {
+    if needle.is_empty() {
+        return true;
+    }
+    let mut boundaries = vec![0];
+    boundaries.extend(graphemes(haystack).scan(0, |end, grapheme| {
+        *end += grapheme.len();
+        Some(*end)
+    }));
+    haystack
+        .match_indices(needle)
+        .any(|(start, _)| boundaries.binary_search(&start).is_ok() && boundaries.binary_search(&(start + needle.len())).is_ok())
+}
+
+/// `text` in lowercase without accents or other marks, and with
+/// compatibility characters such as `ﬁ` or full-width letters written
+/// plainly, for searches that should not care how a word was typed.
+pub(crate) fn fold(text: &str) -> String {
+    DecomposingNormalizerBorrowed::new_nfkd()
+        .normalize(text)
+        .chars()
+        .filter(|&c| !is_mark(c))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// The graphemes of `text`, in order.
+pub(crate) fn graphemes(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        let mut chars = rest.char_indices();
+        let (_, first) = chars.next()?;
+        let mut previous = first;
+        // How many regional indicators in a row end the grapheme so far
+        let mut regional = usize::from(is_regional_indicator(first));
+        let mut end = rest.len();
+        for (at, c) in chars {
+            let joined = (previous == '\r' && c == '\n')
+                || (previous != '\r' && previous != '\n' && extends(c))
+                || previous == ZERO_WIDTH_JOINER
+                || (is_regional_indicator(c) && regional % 2 == 1);
+            if !joined {
+                end = at;
+                break;
+            }
+            regional = if is_regional_indicator(c) { regional + 1 } else { 0 };
+            previous = c;
+        }
+        let (grapheme, after) = rest.split_at(end);
+        rest = after;
+        Some(grapheme)
+    })
+}
+
+// Whether `c` belongs with the character before it
+fn extends(c: char) -> bool {
+    matches!(c, ZERO_WIDTH_JOINER | '\u{FE00}'..='\u{FE0F}' | '\u{1F3FB}'..='\u{1F3FF}' | '\u{E0020}'..='\u{E007F}')
+        || (c.width() == Some(0) && !c.is_control() && !c.is_whitespace() && c != '\u{200B}')
+}
+
+// Whether `c` is a mark set on the character before it, such as an accent.
+// Hangul vowels and final consonants take no width of their own either,
+// but are letters of the syllable, and joiners, variation selectors, skin
+// tones and tags are parts of emoji.
+fn is_mark(c: char) -> bool {
+    extends(c)
+        && !matches!(c, '\u{1160}'..='\u{11FF}' | '\u{D7B0}'..='\u{D7FF}')
+        && !matches!(c, ZERO_WIDTH_JOINER | '\u{FE00}'..='\u{FE0F}' | '\u{1F3FB}'..='\u{1F3FF}' | '\u{E0020}'..='\u{E007F}')
+}
+
+fn is_regional_indicator(c: char) -> bool {
+    matches!(c, '\u{1F1E6}'..='\u{1F1FF}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FAMILY: &str = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}\u{200D}\u{1F466}";
+    const FLAG_DE: &str = "\u{1F1E9}\u{1F1EA}";
+    const FLAG_FR: &str = "\u{1F1EB}\u{1F1F7}";
+    // An e with the accent written after it, rather than as one é
+    const E_ACUTE: &str = "e\u{301}";
+
+    fn split(text: &str) -> Vec<&str> {
+        graphemes(text).collect()
+    }
+
+    #[test]
+    fn plain_text_is_a_grapheme_a_char() {
+        assert_eq!(split("milk"), ["m", "i", "l", "k"]);
+        assert_eq!(split(""), Vec::<&str>::new());
+        assert_eq!(split("a\r\nb"), ["a", "\r\n", "b"]);
+        assert_eq!(width("milk"), 4);
+    }
+
+    #[test]
+    fn joined_emoji_stay_together() {
+        assert_eq!(split(&format!("a{}b", FAMILY)), ["a", FAMILY, "b"]);
+        let thumbs = "\u{1F44D}\u{1F3FD}";
+        assert_eq!(split(&format!("{}!", thumbs)), [thumbs, "!"]);
+        let heart = "\u{2764}\u{FE0F}";
+        assert_eq!(split(heart), [heart]);
+    }
+
+    #[test]
+    fn regional_indicators_pair_up_into_flags() {
+        assert_eq!(split(&format!("{}{}", FLAG_DE, FLAG_FR)), [FLAG_DE, FLAG_FR]);
+        // An odd one out stays on its own
+        assert_eq!(split(&format!("{}\u{1F1E9}", FLAG_DE)), [FLAG_DE, "\u{1F1E9}"]);
+        assert_eq!(width(FLAG_DE), 2);
+    }
+
+    #[test]
+    fn combining_marks_go_with_their_letter() {
+        assert_eq!(split(&format!("caf{}s", E_ACUTE)), ["c", "a", "f", E_ACUTE, "s"]);
+        assert_eq!(width(&format!("caf{}", E_ACUTE)), 4);
+        // A mark at the very start has nothing to go with
+        assert_eq!(split("\u{301}a"), ["\u{301}", "a"]);
+    }
+
+    #[test]
+    fn hangul_syllables() {
+        // 한 written as one char and as its three letters
+        assert_eq!(split("한국"), ["한", "국"]);
+        let jamo = "\u{1112}\u{1161}\u{11AB}";
+        assert_eq!(split(&format!("{}a", jamo)), [jamo, "a"]);
+        assert_eq!(width(jamo), 2);
+        assert_eq!(fold(jamo), jamo);
+        assert_eq!(fold("한"), jamo);
+    }
+
+    #[test]
+    fn wide_characters_take_two_columns() {
+        assert_eq!(width("日本語"), 6);
+        assert_eq!(width("ｔｏｄｏ"), 8);
+        assert_eq!(pad("日本", 6), "日本  ");
+        assert_eq!(pad("日本語", 4), "日本語");
+    }
+
+    #[test]
+    fn truncating_fits_the_columns() {
+        assert_eq!(truncate("buy milk", 8), "buy milk");
+        assert_eq!(truncate("buy milk", 5), "buy …");
+        assert_eq!(truncate("buy milk", 1), "…");
+        assert_eq!(truncate("buy milk", 0), "");
+        assert_eq!(truncate("", 0), "");
+    }
+
+    #[test]
+    fn truncating_never_splits_a_grapheme() {
+        // Half of a wide character would not fit, so it goes
+        assert_eq!(truncate("日本語", 4), "日…");
+        assert_eq!(truncate("日本語", 5), "日本…");
+        assert_eq!(truncate(&format!("{}{}{}", FLAG_DE, FLAG_FR, FLAG_DE), 5), format!("{}{}…", FLAG_DE, FLAG_FR));
+        assert_eq!(truncate(&format!("ab{}cd", FAMILY), 4), "ab…");
+        assert_eq!(truncate(&format!("caf{}s!", E_ACUTE), 5), format!("caf{}…", E_ACUTE));
+        for max in 0..12 {
+            assert!(width(&truncate(&format!("日{}語{}x", FAMILY, E_ACUTE), max)) <= max, "{}", max);
+        }
+    }
+
+    #[test]
+    fn contains_matches_whole_graphemes() {
+        assert!(contains("buy milk", "milk"));
+        assert!(contains("anything", ""));
+        assert!(!contains(&format!("caf{}", E_ACUTE), "cafe"));
+        assert!(contains(&format!("caf{} au lait", E_ACUTE), E_ACUTE));
+        assert!(!contains(FAMILY, "\u{1F469}"));
+        assert!(contains(&format!("{}{}", FLAG_DE, FLAG_FR), FLAG_FR));
+        // The indicators in the middle of two flags make neither of them
+        assert!(!contains(&format!("{}{}", FLAG_DE, FLAG_FR), "\u{1F1EA}\u{1F1EB}"));
+    }
+
+    #[test]
+    fn folding_ignores_case_accents_and_compatibility_forms() {
+        assert_eq!(fold("Café"), "cafe");
+        assert_eq!(fold(&format!("CAF{}", "E\u{301}")), "cafe");
+        assert_eq!(fold("ﬁle"), "file");
+        assert_eq!(fold("ＴＯＤＯ"), "todo");
+        assert_eq!(fold(FAMILY), FAMILY);
+    }
+}
@@ -0,0 +1,252 @@
+//! The todo.txt format (<https://github.com/todotxt/todo.txt>), one task per
+//! line:
+//!
+//! ```text
+//! (A) pay rent +home due:2024-06-01 rec:1m
+//! x water plants +home pri:C
+//! ```
+//!
+//! Tags are written as `+project` tokens. `@context` tokens stay in the
+//! description, where [`Task::contexts`] finds them. Priorities map high to `(A)` and low to `(C)`, with medium
+//! written without a priority; completed tasks keep theirs as `pri:A`, since
+//! nothing may come between the `x` and the completion date. Creation and
+//! completion times are kept to the day. Due dates, start dates and
+//! recurrence use the common `due:`, `t:` and `rec:` extensions, an open
+//! task's column of the board as `status:in-progress` when it is not todo,
+//! and the assignee and custom fields
+//! are written as `assignee:name` and `name:value` tokens, unless the value
+//! has spaces in it. Subtasks and notes have no todo.txt equivalent and are
+//! left out.
+
+#[cfg(feature = "text")]
+use tracing::{debug, trace};
+
+use crate::{tr, Column, Date, Priority, Recurrence, Task, Timestamp, TodoError, TodoList};
+
+/// Writes every task as a todo.txt line.
+pub fn export(todo_list: &TodoList) -> String {
+    todo_list.tasks().iter().map(|task| format!("{}\n", to_line(task))).collect()
+}
+
+/// Adds the tasks in todo.txt `text` to `todo_list` with new IDs and returns
+/// those IDs. Blank lines are skipped.
+pub fn import(todo_list: &mut TodoList, text: &str) -> Result<Vec<usize>, TodoError> {
+    let mut tasks = Vec::new();
+    for (line_num, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let task = from_line(line)
+            .map_err(|e| TodoError::Parse(tr!("in-line", error = e.to_string(), line = line_num + 1)))?;
+        tasks.push(task);
+    }
+    Ok(todo_list.add_imported(tasks))
+}
+
+// Like `export`, with `id:`, `parent:`, `rank:` and `uuid:` tokens added so
+// the list reads back with the same IDs and order, for use as storage
+#[cfg(feature = "text")]
+pub(crate) fn write_list(todo_list: &TodoList) -> String {
+    todo_list.tasks().iter()
+        .map(|task| {
+            let parent = task.parent.map(|parent| format!(" parent:{}", parent)).unwrap_or_default();
+            let rank = task.rank.map(|rank| format!(" rank:{}", rank)).unwrap_or_default();
+            format!("{} id:{}{}{} uuid:{}\n", to_line(task), task.id, parent, rank, task.uuid)
+        })
+        .collect()
+}
+
+// Reads what `write_list` wrote. Lines without an `id:`, such as ones added
+// by another todo.txt tool, get the next free ID.
+#[cfg(feature = "text")]
+pub(crate) fn read_list(text: &str) -> Result<TodoList, TodoError> {
+    let mut todo_list = TodoList::new();
+    let mut unnumbered = Vec::new();
+    for (line_num, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let error = |e: TodoError| TodoError::Parse(tr!("in-line", error = e.to_string(), line = line_num + 1));
+        let invalid = |token: &str| error(TodoError::Parse(tr!("todotxt-invalid", value = token)));
+
+        let (mut id, mut parent, mut rank, mut uuid) = (None, None, None, None);
+        let mut rest = Vec::new();
+        for token in line.split_whitespace() {
+            if let Some(value) = token.strip_prefix("id:") {
+                id = Some(value.parse().map_err(|_| invalid(token))?);
+            } else if let Some(value) = token.strip_prefix("parent:") {
+                parent = Some(value.parse().map_err(|_| invalid(token))?);
+            } else if let Some(value) = token.strip_prefix("rank:") {
+                rank = Some(value.parse().map_err(|_| invalid(token))?);
+            } else if let Some(value) = token.strip_prefix("uuid:") {
+                uuid = Some(value.parse().map_err(|_| invalid(token))?);
+            } else {
+                rest.push(token);
+            }
+        }
+
+        let mut task = from_line(&rest.join(" ")).map_err(error)?;
+        task.parent = parent;
+        task.rank = rank;
+        task.uuid = uuid.unwrap_or_else(crate::Uuid::new_v4);
+        trace!(line = line_num + 1, id, "read task");
+        match id {
+            Some(id) => todo_list.insert_task(Task { id, ..task }),
+            None => unnumbered.push(task),
+        }
+    }
+    debug!(tasks = todo_list.tasks().len() + unnumbered.len(), unnumbered = unnumbered.len(), "parsed todo.txt");
+    for task in unnumbered {
+        todo_list.add_synced(task);
+    }
+    Ok(todo_list)
+}
+
+pub(crate) fn to_line(task: &Task) -> String {
+    let letter = match task.priority {
+        Priority::High => Some("A"),
+        Priority::Medium => None,
+        Priority::Low => Some("C"),
+    };
+
+    let mut parts = Vec::new();
+    if task.completed {
+        parts.push("x".to_string());
+    } else if let Some(letter) = letter {
+        parts.push(format!("({})", letter));
+    }
+    // A completed task's creation date can only follow its completion date
+    let completed_at = task.completed_at.filter(|_| task.completed);
+    if let Some(completed_at) = completed_at {
+        parts.push(completed_at.date().to_string());
+    }
+    if let Some(created_at) = task.created_at.filter(|_| completed_at.is_some() || !task.completed) {
+        parts.push(created_at.date().to_string());
+    }
+    parts.push(task.description.clone());
+    parts.extend(task.tags.iter().map(|tag| format!("+{}", tag)));
+    if let Some(due) = task.due_date {
+        parts.push(format!("due:{}", due));
+    }
+    if let Some(start) = task.start_date {
+        parts.push(format!("t:{}", start));
+    }
+    if let Some(recurrence) = task.recurrence {
+        let rec = match recurrence {
+            Recurrence::Daily => "1d",
+            Recurrence::Weekly => "1w",
+            Recurrence::Monthly => "1m",
+        };
+        parts.push(format!("rec:{}", rec));
+    }
+    if let Some(letter) = letter.filter(|_| task.completed) {
+        parts.push(format!("pri:{}", letter));
+    }
+    if matches!(task.status(), Column::InProgress | Column::Blocked) {
+        parts.push(format!("status:{}", task.column));
+    }
+    if let Some(assignee) = &task.assignee {
+        parts.push(format!("assignee:{}", assignee));
+    }
+    // A value with spaces would not read back as one token
+    for (name, value) in task.fields.iter().filter(|(_, value)| !value.contains(char::is_whitespace)) {
+        parts.push(format!("{}:{}", name, value));
+    }
+    parts.join(" ")
+}
+
+pub(crate) fn from_line(line: &str) -> Result<Task, TodoError> {
+    let mut task = Task::default();
+    let mut tokens = line.split_whitespace().peekable();
+
+    if tokens.peek() == Some(&"x") {
+        task.completed = true;
+        tokens.next();
+    }
+    // Completion and creation dates come before the description, around
+    // the priority
+    let mut dates = Vec::new();
+    while let Some(token) = tokens.peek() {
+        if let Some(priority) = parse_priority(token) {
+            task.priority = priority;
+        } else if let Ok(date) = Date::parse(token) {
+            dates.push(Timestamp::from_seconds(date.to_days() * 86_400));
+        } else {
+            break;
+        }
+        tokens.next();
+    }
+    if task.completed {
+        task.completed_at = dates.first().copied();
+        task.created_at = dates.get(1).copied();
+    } else {
+        task.created_at = dates.first().copied();
+    }
+
+    let mut words = Vec::new();
+    for token in tokens {
+        if let Some(tag) = token.strip_prefix('+') {
+            if !tag.is_empty() {
+                let tag = tag.to_lowercase();
+                if !task.tags.contains(&tag) {
+                    task.tags.push(tag);
+                }
+                continue;
+            }
+        }
+        if let Some(due) = token.strip_prefix("due:") {
+            task.due_date = Some(Date::parse(due)?);
+        } else if let Some(start) = token.strip_prefix("t:") {
+            task.start_date = Some(Date::parse(start)?);
+        } else if let Some(priority) = token.strip_prefix("pri:").and_then(|letter| {
+            parse_priority(&format!("({})", letter))
+        }) {
+            task.priority = priority;
+        } else if let Some(rec) = token.strip_prefix("rec:") {
+            task.recurrence = Some(parse_recurrence(rec)?);
+        } else if let Some(column) = token.strip_prefix("status:") {
+            task.column = Column::parse(column)?;
+        } else if let Some(assignee) = token.strip_prefix("assignee:").filter(|assignee| !assignee.is_empty()) {
+            task.assignee = Some(assignee.to_string());
+        } else if let Some((name, value)) = field(token) {
+            task.fields.insert(name.to_string(), value.to_string());
+        } else {
+            words.push(token);
+        }
+    }
+
+    task.description = words.join(" ");
+    if task.description.is_empty() {
+        return Err(TodoError::EmptyDescription);
+    }
+    Ok(task)
+}
+
+// A `name:value` token for a custom field. Links like `https://...` are
+// left in the description.
+fn field(token: &str) -> Option<(&str, &str)> {
+    let (name, value) = token.split_once(':')?;
+    let valid = crate::is_field_name(name) && !value.is_empty() && !value.starts_with("//");
+    valid.then_some((name, value))
+}
+
+// `(A)` is high, `(B)` medium and anything lower is low
+fn parse_priority(token: &str) -> Option<Priority> {
+    let letter = token.strip_prefix('(')?.strip_suffix(')')?;
+    match letter {
+        "A" => Some(Priority::High),
+        "B" => Some(Priority::Medium),
+        _ if letter.len() == 1 && letter.chars().all(|c| c.is_ascii_uppercase()) => Some(Priority::Low),
+        _ => None,
+    }
+}
+
+// Recurrence such as `1d`, `1w` or `1m`, optionally with a leading `+`
+fn parse_recurrence(rec: &str) -> Result<Recurrence, TodoError> {
+    match rec.trim_start_matches('+') {
+        "d" | "1d" => Ok(Recurrence::Daily),
+        "w" | "1w" => Ok(Recurrence::Weekly),
+        "m" | "1m" => Ok(Recurrence::Monthly),
+        other => Err(TodoError::Parse(tr!("todotxt-bad-recurrence", value = other))),
+    }
+}
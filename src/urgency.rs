@@ -0,0 +1,107 @@
+//! Urgency scores in the style of Taskwarrior, so `list --sort urgency` can
+//! bring the tasks that most need doing to the top. Each open task scores
+//! the sum of:
+//!
+//! - its priority: 1 for high, 0.65 for medium and 0.3 for low,
+//! - how close it is to being due: 0.2 two weeks or more ahead, rising to 1
+//!   a week overdue, and 0 with no due date,
+//! - its age: 0 when just added, rising to 1 at a year old,
+//! - how many tags it has: 0.8 for one, 0.9 for two and 1 for three or more,
+//!
+//! each times its weight, plus the weight of every tag it carries that has
+//! one of its own. Completed tasks score 0.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::{tr, Date, Priority, Task, TodoError};
+
+/// How much each part of the score counts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Weights {
+    pub priority: f64,
+    pub due: f64,
+    pub age: f64,
+    pub tags: f64,
+    /// Added for each of these tags a task carries, keyed by tag.
+    pub tag: HashMap<String, f64>,
+}
+
+impl Default for Weights {
+    fn default() -> Self {
+        Weights {
+            priority: 6.0,
+            due: 12.0,
+            age: 2.0,
+            tags: 1.0,
+            tag: HashMap::new(),
+        }
+    }
+}
+
+impl Weights {
+    /// Parses comma separated `name=weight` pairs such as
+    /// `due=8,age=0,tag.next=15`, starting from the defaults. The names are
+    /// `priority`, `due`, `age`, `tags` and `tag.` followed by a tag.
+    pub fn parse(input: &str) -> Result<Self, TodoError> {
+        let mut weights = Weights::default();
+        for pair in input.split(',').map(str::trim).filter(|pair| !pair.is_empty()) {
+            let error = || TodoError::Parse(tr!("invalid-urgency-weight", value = pair));
+            let (name, value) = pair.split_once('=').ok_or_else(error)?;
+            let value: f64 = value.trim().parse().map_err(|_| error())?;
+            match name.trim().to_lowercase().as_str() {
+                "priority" => weights.priority = value,
+                "due" => weights.due = value,
+                "age" => weights.age = value,
+                "tags" => weights.tags = value,
+                name => match name.strip_prefix("tag.").filter(|tag| !tag.is_empty()) {
+                    Some(tag) => {
+                        weights.tag.insert(tag.to_string(), value);
+                    }
+                    None => return Err(error()),
+                },
+            }
+        }
+        Ok(weights)
+    }
+
+    /// How urgent `task` is on `today`; higher is more urgent.
+    pub fn score(&self, task: &Task, today: Date) -> f64 {
+        if task.completed {
+            return 0.0;
+        }
+        let priority = match task.priority {
+            Priority::High => 1.0,
+            Priority::Medium => 0.65,
+            Priority::Low => 0.3,
+        };
+        let due = match task.due_date {
+            Some(due) => {
+                let overdue = (today.to_days() - due.to_days()) as f64;
+                ((overdue + 14.0) * 0.8 / 21.0 + 0.2).clamp(0.2, 1.0)
+            }
+            None => 0.0,
+        };
+        let age = match task.created_at {
+            Some(created_at) => ((today.to_days() - created_at.date().to_days()) as f64 / 365.0).clamp(0.0, 1.0),
+            None => 0.0,
+        };
+        let tags = match task.tags.len() {
+            0 => 0.0,
+            1 => 0.8,
+            2 => 0.9,
+            _ => 1.0,
+        };
+        let tag: f64 = task.tags.iter().filter_map(|tag| self.tag.get(tag)).sum();
+
+        self.priority * priority + self.due * due + self.age * age + self.tags * tags + tag
+    }
+}
+
+impl FromStr for Weights {
+    type Err = TodoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Weights::parse(s)
+    }
+}